@@ -558,7 +558,7 @@ pub fn generate_circuit(
     Ok(res)
 }
 
-#[derive(Clone, Debug, Copy)]
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
 #[pyclass(module = "qiskit._accelerate.euler_one_qubit_decomposer")]
 pub enum EulerBasis {
     U321,
@@ -575,6 +575,11 @@ pub enum EulerBasis {
     XZX,
 }
 
+/// The names of all the valid [EulerBasis] variants, in declaration order.
+pub const EULER_BASIS_NAMES: [&str; 12] = [
+    "U321", "U3", "U", "PSX", "ZSX", "ZSXX", "U1X", "RR", "ZYZ", "ZXZ", "XYX", "XZX",
+];
+
 impl EulerBasis {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -19,6 +19,7 @@
 // In order to avoid copying we want to use `MatRef<c64>` or `MatMut<c64>`.
 
 use approx::{abs_diff_eq, relative_eq};
+use hashbrown::{HashMap, HashSet};
 use num_complex::{Complex, Complex64, ComplexFloat};
 use num_traits::Zero;
 use pyo3::exceptions::{PyIndexError, PyValueError};
@@ -26,33 +27,47 @@ use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
 use pyo3::Python;
 use smallvec::{smallvec, SmallVec};
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 use std::f64::consts::{FRAC_1_SQRT_2, PI};
-use std::ops::Deref;
+use std::ops::{Deref, Range};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::thread::LocalKey;
 
 use faer::Side::Lower;
 use faer::{prelude::*, scale, ComplexField, Mat, MatRef};
 use faer_ext::{IntoFaer, IntoFaerComplex, IntoNdarray, IntoNdarrayComplex};
 use ndarray::linalg::kron;
 use ndarray::prelude::*;
-use ndarray::Zip;
-use numpy::PyReadonlyArray2;
+use ndarray::{CowArray, Zip};
 use numpy::{IntoPyArray, ToPyArray};
+use numpy::{PyArray1, PyArray2, PyArray3, PyReadonlyArray1, PyReadonlyArray2, PyReadonlyArray3};
 use pyo3::pybacked::PyBackedStr;
+use pyo3::types::PyDict;
 
 use crate::convert_2q_block_matrix::change_basis;
 use crate::euler_one_qubit_decomposer::{
     angles_from_unitary, det_one_qubit, unitary_to_gate_sequence_inner, EulerBasis,
-    OneQubitGateSequence, ANGLE_ZERO_EPSILON,
+    OneQubitGateSequence, ANGLE_ZERO_EPSILON, EULER_BASIS_NAMES,
 };
 use crate::utils;
-use crate::QiskitError;
+use crate::{getenv_use_multiple_threads, QiskitError};
 
 use rand::prelude::*;
 use rand_distr::StandardNormal;
 use rand_pcg::Pcg64Mcg;
+use rayon::prelude::*;
 
-use qiskit_circuit::gate_matrix::{CX_GATE, H_GATE, ONE_QUBIT_IDENTITY, SX_GATE, X_GATE};
-use qiskit_circuit::SliceOrInt;
+use qiskit_circuit::circuit_data::CircuitData;
+use qiskit_circuit::gate_matrix::{
+    phase_gate, r_gate, rx_gate, ry_gate, rz_gate, u1_gate, u2_gate, u3_gate, u_gate, CX_GATE,
+    CY_GATE, CZ_GATE, DCX_GATE, ECR_GATE, H_GATE, ISWAP_GATE, ONE_QUBIT_IDENTITY, SDG_GATE,
+    SWAP_GATE, SXDG_GATE, SX_GATE, S_GATE, TDG_GATE, T_GATE, X_GATE, Y_GATE, Z_GATE,
+};
+use qiskit_circuit::operations::{Param, StandardGate};
+use qiskit_circuit::{Qubit, SliceOrInt};
 
 const PI2: f64 = PI / 2.0;
 const PI4: f64 = PI / 4.0;
@@ -219,6 +234,60 @@ fn decompose_two_qubit_product_gate(
     Ok((l, r, phase))
 }
 
+/// If ``special_unitary`` is a local gate to within ``LOCAL_GATE_ATOL``, return its factors as
+/// given by ``decompose_two_qubit_product_gate``.
+fn local_gate_factors(
+    special_unitary: ArrayView2<Complex64>,
+) -> Option<(Array2<Complex64>, Array2<Complex64>, f64)> {
+    let (l, r, phase) = decompose_two_qubit_product_gate(special_unitary).ok()?;
+    let product = kron(&l, &r) * (C1_IM * phase).exp();
+    abs_diff_eq!(product.view(), special_unitary, epsilon = LOCAL_GATE_ATOL)
+        .then_some((l, r, phase))
+}
+
+/// If ``special_unitary`` is the SWAP gate up to a global phase, return the argument of that
+/// phase. This only compares matrix elements, so it is much cheaper than the eigendecomposition
+/// it lets the Weyl decomposition skip.
+fn swap_gate_phase(special_unitary: ArrayView2<Complex64>) -> Option<f64> {
+    let phase = special_unitary[[0, 0]];
+    let swap = aview2(&SWAP_GATE).mapv(|x| x * phase);
+    abs_diff_eq!(swap.view(), special_unitary, epsilon = SWAP_GATE_ATOL).then(|| phase.arg())
+}
+
+/// A running sum of phases which, if ``compensated``, uses Kahan's compensated summation, so that
+/// the rounding error doesn't grow with the number of terms.
+#[derive(Clone, Copy, Debug)]
+struct PhaseSum {
+    sum: f64,
+    compensation: f64,
+    compensated: bool,
+}
+
+impl PhaseSum {
+    fn new(initial: f64, compensated: bool) -> Self {
+        PhaseSum {
+            sum: initial,
+            compensation: 0.,
+            compensated,
+        }
+    }
+
+    fn add(&mut self, phase: f64) {
+        if !self.compensated {
+            self.sum += phase;
+            return;
+        }
+        let term = phase - self.compensation;
+        let sum = self.sum + term;
+        self.compensation = (sum - self.sum) - term;
+        self.sum = sum;
+    }
+
+    fn value(&self) -> f64 {
+        self.sum
+    }
+}
+
 fn __weyl_coordinates(unitary: MatRef<c64>) -> [f64; 3] {
     let uscaled = scale(C1 / unitary.determinant().powf(0.25)) * unitary;
     let uup = transform_from_magic_basis(uscaled);
@@ -268,6 +337,391 @@ fn __weyl_coordinates(unitary: MatRef<c64>) -> [f64; 3] {
     [cs[1], cs[0], cs[2]]
 }
 
+/// Return an error naming ``context`` unless ``m`` is a 4x4 matrix, as expected of the matrix of
+/// a 2-qubit gate.
+fn check_two_qubit_shape(context: &str, m: ArrayView2<Complex64>) -> PyResult<()> {
+    if m.shape() != [4, 4] {
+        return Err(QiskitError::new_err(format!(
+            "{context}: expected a 4x4 unitary, got {:?}",
+            m.shape()
+        )));
+    }
+    Ok(())
+}
+
+/// Interpolate between the Weyl coordinates :math:`w_1` and :math:`w_2` of the 2-qubit unitaries
+/// ``u1`` and ``u2``, returning the coordinates of :math:`(1 - t) w_1 + t w_2` brought back into
+/// the Weyl chamber. For :math:`0 \le t \le 1` this traces the straight line between the two
+/// points of the chamber, which is useful for sweeping a family of gates between two targets.
+#[pyfunction]
+#[pyo3(text_signature = "(u1, u2, t, /)")]
+pub fn weyl_interpolate(
+    u1: PyReadonlyArray2<Complex64>,
+    u2: PyReadonlyArray2<Complex64>,
+    t: f64,
+) -> PyResult<[f64; 3]> {
+    let (u1, u2) = (u1.as_array(), u2.as_array());
+    for (name, unitary) in [("u1", &u1), ("u2", &u2)] {
+        check_two_qubit_shape(&format!("weyl_interpolate {name}"), unitary.view())?;
+    }
+    let w1 = __weyl_coordinates(u1.into_faer_complex());
+    let w2 = __weyl_coordinates(u2.into_faer_complex());
+    let [a, b, c] = [0, 1, 2].map(|i| (1. - t) * w1[i] + t * w2[i]);
+    Ok(__weyl_coordinates(ud(a, b, c).view().into_faer_complex()))
+}
+
+/// Return whether the 2-qubit unitaries ``u1`` and ``u2`` are locally equivalent, that is whether
+/// their Weyl coordinates agree within ``atol``. On the face :math:`a = \pi/4` of the Weyl
+/// chamber, where :math:`(\pi/4, b, c)` and :math:`(\pi/4, b, -c)` are the same point, the sign of
+/// :math:`c` is ignored.
+#[pyfunction]
+#[pyo3(signature = (u1, u2, atol=1e-9))]
+pub fn locally_equivalent(
+    u1: PyReadonlyArray2<Complex64>,
+    u2: PyReadonlyArray2<Complex64>,
+    atol: f64,
+) -> PyResult<bool> {
+    let (u1, u2) = (u1.as_array(), u2.as_array());
+    for (name, unitary) in [("u1", &u1), ("u2", &u2)] {
+        check_two_qubit_shape(&format!("locally_equivalent {name}"), unitary.view())?;
+    }
+    let w1 = __weyl_coordinates(u1.into_faer_complex());
+    let w2 = __weyl_coordinates(u2.into_faer_complex());
+    Ok(weyl_coordinates_close(w1, w2, atol))
+}
+
+/// Whether the Weyl coordinates ``w1`` and ``w2`` describe the same point of the Weyl chamber
+/// within ``atol``, identifying :math:`(\pi/4, b, c)` with :math:`(\pi/4, b, -c)` on its face.
+fn weyl_coordinates_close(w1: [f64; 3], w2: [f64; 3], atol: f64) -> bool {
+    let [a1, b1, c1] = w1;
+    let [a2, b2, c2] = w2;
+    let close = |x: f64, y: f64| (x - y).abs() <= atol;
+    let on_face = close(a1, PI4) && close(a2, PI4);
+    close(a1, a2) && close(b1, b2) && (close(c1, c2) || (on_face && close(c1, -c2)))
+}
+
+/// Return the Euclidean distance from the Weyl coordinates :math:`(a, b, c)` of the 2-qubit
+/// ``unitary`` to the polytope of perfect entanglers, which is 0 if ``unitary`` is a perfect
+/// entangler.
+///
+/// In the Weyl chamber :math:`\pi/4 \ge a \ge b \ge |c|`, the perfect entanglers are the points
+/// with :math:`a + b \ge \pi/4` and :math:`b + |c| \le \pi/4`. A point outside violates only one
+/// of the two, and its projection onto the bounding plane is a perfect entangler, so the distance
+/// is the violation divided by :math:`\sqrt{2}`. It is largest, :math:`\pi / (4\sqrt{2})`, for
+/// local gates and for gates locally equivalent to SWAP.
+#[pyfunction]
+#[pyo3(text_signature = "(unitary, /)")]
+pub fn distance_to_perfect_entangler(unitary: PyReadonlyArray2<Complex64>) -> PyResult<f64> {
+    let unitary = unitary.as_array();
+    check_two_qubit_shape("distance_to_perfect_entangler", unitary)?;
+    let [a, b, c] = __weyl_coordinates(unitary.into_faer_complex());
+    let violation = (PI4 - a - b).max(b + c.abs() - PI4);
+    Ok(violation.max(0.) * FRAC_1_SQRT_2)
+}
+
+/// Return a coarse label for the local equivalence class of the 2-qubit ``unitary``, from its
+/// Weyl coordinates :math:`(a, b, c)` compared within ``atol``.
+///
+/// The classes are checked in the same order as the specializations of
+/// :class:`.TwoQubitWeylDecomposition`, and the first match is returned:
+///
+/// * ``"Local"``: :math:`(0, 0, 0)`, a product of single-qubit gates.
+/// * ``"SWAP"``: :math:`(\pi/4, \pi/4, \pm\pi/4)`.
+/// * ``"SWAP-root"``: :math:`(a, a, \pm a)`, a partial SWAP such as :math:`\sqrt{SWAP}`.
+/// * ``"ControlledPhase"``: :math:`(a, 0, 0)`, such as CX, CZ or a controlled phase gate.
+/// * ``"iSWAP-like"``: :math:`(a, a, 0)`, the XY family such as iSWAP or :math:`\sqrt{iSWAP}`.
+/// * ``"PerfectEntangler"``: any other perfect entangler, see
+///   :func:`.distance_to_perfect_entangler`.
+/// * ``"General"``: anything else.
+#[pyfunction]
+#[pyo3(signature = (unitary, atol=1e-9))]
+pub fn gate_class(unitary: PyReadonlyArray2<Complex64>, atol: f64) -> PyResult<String> {
+    let unitary = unitary.as_array();
+    check_two_qubit_shape("gate_class", unitary)?;
+    let [a, b, c] = __weyl_coordinates(unitary.into_faer_complex());
+    let close = |x: f64, y: f64| (x - y).abs() <= atol;
+    let class = if close(a, 0.) && close(b, 0.) && close(c, 0.) {
+        "Local"
+    } else if close(a, PI4) && close(b, PI4) && close(c.abs(), PI4) {
+        "SWAP"
+    } else if close(a, b) && close(b, c.abs()) {
+        "SWAP-root"
+    } else if close(b, 0.) && close(c, 0.) {
+        "ControlledPhase"
+    } else if close(a, b) && close(c, 0.) {
+        "iSWAP-like"
+    } else if a + b >= PI4 - atol && b + c.abs() <= PI4 + atol {
+        "PerfectEntangler"
+    } else {
+        "General"
+    };
+    Ok(class.to_string())
+}
+
+/// The two-qubit Pauli matrix with index ``x0 + 2 z0 + 4 x1 + 8 z1``, where ``x`` and ``z``
+/// select :math:`X`, :math:`Z` or, with both set, :math:`Y` on each qubit.
+fn two_qubit_pauli(index: usize) -> Array2<Complex64> {
+    let single = |x: usize, z: usize| match (x, z) {
+        (0, 0) => aview2(&ONE_QUBIT_IDENTITY).to_owned(),
+        (1, 0) => aview2(&X_GATE).to_owned(),
+        (0, 1) => aview2(&Z_GATE).to_owned(),
+        _ => aview2(&Y_GATE).to_owned(),
+    };
+    kron(
+        &single((index >> 2) & 1, (index >> 3) & 1),
+        &single(index & 1, (index >> 1) & 1),
+    )
+}
+
+/// Encode the images of :math:`X_0`, :math:`Z_0`, :math:`X_1` and :math:`Z_1` under conjugation
+/// by ``unitary`` in 5 bits each, the index of the image in ``paulis`` and its sign. This
+/// identifies a Clifford up to its global phase. Return ``None`` if an image isn't a Pauli up to
+/// a sign to within ``atol``, that is if ``unitary`` isn't a Clifford.
+fn clifford_tableau_key(
+    unitary: ArrayView2<Complex64>,
+    paulis: &[Array2<Complex64>],
+    atol: f64,
+) -> Option<u32> {
+    let adjoint = unitary.t().mapv(|x| x.conj());
+    let mut key = 0;
+    for (position, generator) in [1, 2, 4, 8].into_iter().enumerate() {
+        let image = unitary.dot(&paulis[generator]).dot(&adjoint);
+        let code = paulis.iter().enumerate().find_map(|(index, pauli)| {
+            // The coefficient of `pauli` in the image is Tr(pauli image) / 4.
+            let coefficient = pauli
+                .iter()
+                .zip(image.t().iter())
+                .map(|(p, m)| p * m)
+                .sum::<Complex64>()
+                / 4.;
+            if (coefficient - 1.).norm() <= atol {
+                Some(index as u32)
+            } else if (coefficient + 1.).norm() <= atol {
+                Some(index as u32 | 16)
+            } else {
+                None
+            }
+        })?;
+        key |= code << (5 * position);
+    }
+    Some(key)
+}
+
+/// The circuit of ``h``, ``s`` and ``cx`` gates for each two-qubit Clifford, keyed by
+/// [clifford_tableau_key]. The circuits have the fewest ``cx`` gates, and the fewest
+/// single-qubit gates among those; they are found by a search over the Clifford group on the
+/// first call.
+fn two_qubit_clifford_circuits() -> &'static HashMap<u32, TwoQubitSequenceVec> {
+    static CIRCUITS: OnceLock<HashMap<u32, TwoQubitSequenceVec>> = OnceLock::new();
+    CIRCUITS.get_or_init(|| {
+        let paulis: Vec<_> = (0..16).map(two_qubit_pauli).collect();
+        let generators: Vec<_> = [
+            ("h", &[0][..]),
+            ("h", &[1]),
+            ("s", &[0]),
+            ("s", &[1]),
+            ("cx", &[0, 1]),
+            ("cx", &[1, 0]),
+        ]
+        .into_iter()
+        .map(|(name, qubits)| {
+            let gate = (name.to_string(), smallvec![], SmallVec::from_slice(qubits));
+            let matrix = compute_unitary(&vec![gate.clone()], 0.).unwrap();
+            let cost = if qubits.len() == 2 {
+                CLIFFORD_CX_COST
+            } else {
+                1
+            };
+            (gate, matrix, cost)
+        })
+        .collect();
+        // Dijkstra's algorithm over the group, ties broken by the order the circuits are found
+        // in so that the result is deterministic.
+        let mut circuits = HashMap::new();
+        let mut candidates: Vec<(TwoQubitSequenceVec, Array2<Complex64>)> =
+            vec![(Vec::new(), Array2::eye(4))];
+        let mut queue = BinaryHeap::from([Reverse((0, 0))]);
+        while let Some(Reverse((cost, candidate))) = queue.pop() {
+            let (sequence, matrix) = std::mem::take(&mut candidates[candidate]);
+            let key = clifford_tableau_key(matrix.view(), &paulis, CLIFFORD_SEARCH_ATOL).unwrap();
+            if circuits.contains_key(&key) {
+                continue;
+            }
+            for (gate, gate_matrix, gate_cost) in &generators {
+                let next = gate_matrix.dot(&matrix);
+                let next_key = clifford_tableau_key(next.view(), &paulis, CLIFFORD_SEARCH_ATOL);
+                if next_key.is_some_and(|next_key| !circuits.contains_key(&next_key)) {
+                    let mut next_sequence = sequence.clone();
+                    next_sequence.push(gate.clone());
+                    candidates.push((next_sequence, next));
+                    queue.push(Reverse((cost + gate_cost, candidates.len() - 1)));
+                }
+            }
+            circuits.insert(key, sequence);
+        }
+        circuits
+    })
+}
+
+/// Synthesize the two-qubit Clifford ``unitary`` directly as a circuit of ``h``, ``s`` and
+/// ``cx`` gates, with the fewest ``cx`` gates and the fewest single-qubit gates among those.
+///
+/// This avoids computing the continuous angles of a general synthesis, which are all Clifford
+/// angles for such a target. ``unitary`` is a Clifford if conjugating each Pauli by it gives a
+/// Pauli up to a sign, which is checked to within ``atol``; a ``QiskitError`` is raised if it
+/// isn't. The global phase of ``unitary`` is set on the returned circuit.
+#[pyfunction]
+#[pyo3(signature = (unitary, atol=1e-9))]
+pub fn two_qubit_clifford_circuit(
+    py: Python,
+    unitary: PyReadonlyArray2<Complex64>,
+    atol: f64,
+) -> PyResult<CircuitData> {
+    let unitary = unitary.as_array();
+    check_two_qubit_shape("two_qubit_clifford_circuit", unitary)?;
+    let not_clifford =
+        || QiskitError::new_err("two_qubit_clifford_circuit: the unitary is not a Clifford");
+    let paulis: Vec<_> = (0..16).map(two_qubit_pauli).collect();
+    let sequence = clifford_tableau_key(unitary, &paulis, atol)
+        .and_then(|key| two_qubit_clifford_circuits().get(&key))
+        .ok_or_else(not_clifford)?;
+    // The circuit matches the target up to a phase, read off its largest element.
+    let matrix = compute_unitary(sequence, 0.)?;
+    let (index, _) = matrix
+        .indexed_iter()
+        .max_by(|x, y| x.1.norm().total_cmp(&y.1.norm()))
+        .unwrap();
+    let global_phase = (unitary[index] / matrix[index]).arg();
+    let phased = matrix * (C1_IM * global_phase).exp();
+    if !abs_diff_eq!(phased.view(), unitary, epsilon = atol) {
+        return Err(not_clifford());
+    }
+    CircuitData::from_standard_gates(
+        py,
+        2,
+        sequence.iter().map(|(name, _, qubits)| {
+            let gate = match name.as_str() {
+                "h" => StandardGate::HGate,
+                "s" => StandardGate::SGate,
+                _ => StandardGate::CXGate,
+            };
+            let qubits = qubits.iter().map(|qubit| Qubit(*qubit as u32)).collect();
+            (gate, smallvec![], qubits)
+        }),
+        Param::Float(global_phase),
+    )
+}
+
+type LocalEquivalenceMaps = (
+    Py<PyArray2<Complex64>>,
+    Py<PyArray2<Complex64>>,
+    Py<PyArray2<Complex64>>,
+    Py<PyArray2<Complex64>>,
+);
+
+/// Return single-qubit unitaries ``(la, lb, ra, rb)`` with
+/// :math:`V = (L_a \otimes L_b) U (R_a \otimes R_b)` for the locally equivalent 2-qubit
+/// unitaries ``u`` and ``v``, composed from the Weyl decompositions of both. The global phase
+/// between ``u`` and ``v`` is absorbed into ``la``.
+///
+/// Raises ``QiskitError`` if ``u`` and ``v`` are not locally equivalent within ``atol``, as decided
+/// by :func:`locally_equivalent`.
+#[pyfunction]
+#[pyo3(signature = (u, v, atol=1e-9))]
+pub fn local_equivalence_maps(
+    py: Python,
+    u: PyReadonlyArray2<Complex64>,
+    v: PyReadonlyArray2<Complex64>,
+    atol: f64,
+) -> PyResult<LocalEquivalenceMaps> {
+    let [la, lb, ra, rb] = local_equivalence_maps_inner(u.as_array(), v.as_array(), atol)?;
+    Ok((
+        la.into_pyarray_bound(py).unbind(),
+        lb.into_pyarray_bound(py).unbind(),
+        ra.into_pyarray_bound(py).unbind(),
+        rb.into_pyarray_bound(py).unbind(),
+    ))
+}
+
+fn local_equivalence_maps_inner(
+    u: ArrayView2<Complex64>,
+    v: ArrayView2<Complex64>,
+    atol: f64,
+) -> PyResult<[Array2<Complex64>; 4]> {
+    let du = TwoQubitWeylDecomposition::new_inner(u, None, None)?;
+    let dv = TwoQubitWeylDecomposition::new_inner(v, None, None)?;
+    let close = |x: f64, y: f64| (x - y).abs() <= atol;
+    let same_c = close(du.c, dv.c);
+    let on_face = close(du.a, PI4) && close(dv.a, PI4);
+    if !(close(du.a, dv.a) && close(du.b, dv.b) && (same_c || (on_face && close(du.c, -dv.c)))) {
+        return Err(QiskitError::new_err(format!(
+            "local_equivalence_maps: the unitaries are not locally equivalent, their Weyl \
+            coordinates are ({}, {}, {}) and ({}, {}, {})",
+            du.a, du.b, du.c, dv.a, dv.b, dv.c
+        )));
+    }
+    let phase = (C1_IM * (dv.global_phase - du.global_phase)).exp();
+    let k1ld = transpose_conjugate(du.K1l.view());
+    let k1rd = transpose_conjugate(du.K1r.view());
+    let k2ld = transpose_conjugate(du.K2l.view());
+    let k2rd = transpose_conjugate(du.K2r.view());
+    if same_c {
+        Ok([
+            dv.K1l.dot(&k1ld) * phase,
+            dv.K1r.dot(&k1rd),
+            k2ld.dot(&dv.K2l),
+            k2rd.dot(&dv.K2r),
+        ])
+    } else {
+        // On the face a = pi/4, Ud(pi/4, b, -c) = i (XZ x I) Ud(pi/4, b, c) (Z x X).
+        let x = aview2(&X_GATE);
+        let z = aview2(&Z_GATE);
+        Ok([
+            dv.K1l.dot(&x).dot(&z).dot(&k1ld) * (C1_IM * phase),
+            dv.K1r.dot(&k1rd),
+            k2ld.dot(&z).dot(&dv.K2l),
+            k2rd.dot(&x).dot(&dv.K2r),
+        ])
+    }
+}
+
+/// Group the 2-qubit ``unitaries`` by their Weyl coordinates :math:`(a, b, c)`.
+///
+/// Each unitary joins the first group whose first member has all coordinates within ``atol`` of
+/// its own, otherwise it starts a new group. As in :func:`.locally_equivalent`, the sign of
+/// :math:`c` is ignored on the face :math:`a = \pi/4` of the Weyl chamber. Returns the indices
+/// of the members of each group.
+#[pyfunction]
+#[pyo3(text_signature = "(unitaries, atol, /)")]
+pub fn cluster_by_weyl(
+    unitaries: PyReadonlyArray3<Complex64>,
+    atol: f64,
+) -> PyResult<Vec<Vec<usize>>> {
+    let unitaries = unitaries.as_array();
+    if unitaries.shape()[1..] != [4, 4] {
+        return Err(QiskitError::new_err(format!(
+            "cluster_by_weyl: expected an (N, 4, 4) array, got {:?}",
+            unitaries.shape()
+        )));
+    }
+    let mut representatives: Vec<[f64; 3]> = Vec::new();
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    for (idx, unitary) in unitaries.outer_iter().enumerate() {
+        let coords = __weyl_coordinates(unitary.into_faer_complex());
+        let cluster = representatives
+            .iter()
+            .position(|rep| weyl_coordinates_close(*rep, coords, atol));
+        match cluster {
+            Some(cluster) => clusters[cluster].push(idx),
+            None => {
+                representatives.push(coords);
+                clusters.push(vec![idx]);
+            }
+        }
+    }
+    Ok(clusters)
+}
+
 #[pyfunction]
 #[pyo3(text_signature = "(basis_b, basis_fidelity, unitary, /")]
 pub fn _num_basis_gates(
@@ -279,6 +733,56 @@ pub fn _num_basis_gates(
     __num_basis_gates(basis_b, basis_fidelity, u)
 }
 
+/// Return the total number of CX gates needed to synthesize every 2-qubit unitary in
+/// ``unitaries``, given as an ``(N, 4, 4)`` array, assuming a CX fidelity of ``basis_fidelity``.
+#[pyfunction]
+#[pyo3(signature = (unitaries, basis_fidelity=1.0))]
+pub fn total_cx_for_blocks(
+    unitaries: PyReadonlyArray3<Complex64>,
+    basis_fidelity: f64,
+) -> PyResult<usize> {
+    let unitaries = unitaries.as_array();
+    if unitaries.shape()[1..] != [4, 4] {
+        return Err(QiskitError::new_err(format!(
+            "total_cx_for_blocks: expected an (N, 4, 4) array, got {:?}",
+            unitaries.shape()
+        )));
+    }
+    let num_cx = |unitary: ArrayView2<Complex64>| {
+        __num_basis_gates(0., basis_fidelity, unitary.into_faer_complex())
+    };
+    Ok(if getenv_use_multiple_threads() {
+        unitaries.outer_iter().into_par_iter().map(num_cx).sum()
+    } else {
+        unitaries.outer_iter().map(num_cx).sum()
+    })
+}
+
+/// Return the number of CX gates needed to synthesize the 2-qubit ``unitary``, assuming a CX
+/// fidelity of ``basis_fidelity``. If ``swap_is_free`` is true, a trailing SWAP costs nothing (for
+/// example because routing can absorb it into the layout), so the count is the smaller of the
+/// counts for ``unitary`` and for its mirror ``SWAP @ unitary``.
+#[pyfunction]
+#[pyo3(signature = (unitary, swap_is_free=false, basis_fidelity=1.0))]
+pub fn effective_cx_count(
+    unitary: PyReadonlyArray2<Complex64>,
+    swap_is_free: bool,
+    basis_fidelity: f64,
+) -> PyResult<usize> {
+    let unitary = unitary.as_array();
+    check_two_qubit_shape("effective_cx_count", unitary)?;
+    let num_cx = __num_basis_gates(0., basis_fidelity, unitary.into_faer_complex());
+    if !swap_is_free {
+        return Ok(num_cx);
+    }
+    let mirror = aview2(&SWAP_GATE).dot(&unitary);
+    Ok(num_cx.min(__num_basis_gates(
+        0.,
+        basis_fidelity,
+        mirror.view().into_faer_complex(),
+    )))
+}
+
 fn __num_basis_gates(basis_b: f64, basis_fidelity: f64, unitary: MatRef<c64>) -> usize {
     let [a, b, c] = __weyl_coordinates(unitary);
     let traces = [
@@ -307,15 +811,400 @@ fn __num_basis_gates(basis_b: f64, basis_fidelity: f64, unitary: MatRef<c64>) ->
         .0
 }
 
+/// Compute the Makhlin local invariants ``[G1.real, G1.imag, G2.real]`` of the canonical gate
+/// :math:`e^{i (a XX + b YY + c ZZ)}` directly from its Weyl coordinates, in the same form as
+/// :func:`.two_qubit_local_invariants`. This uses Eq. 30 of Zhang et al., Phys. Rev. A 67,
+/// 042313 (2003), with the coordinates doubled for the reduced Weyl chamber.
+#[pyfunction]
+#[pyo3(text_signature = "(a, b, c, /)")]
+pub fn makhlin_from_weyl(a: f64, b: f64, c: f64) -> [f64; 3] {
+    let cos_prod = [a, b, c]
+        .iter()
+        .map(|x| (2. * x).cos().powi(2))
+        .product::<f64>();
+    let sin_prod = [a, b, c]
+        .iter()
+        .map(|x| (2. * x).sin().powi(2))
+        .product::<f64>();
+    let g1_imag = [a, b, c].iter().map(|x| (4. * x).sin()).product::<f64>() / 4.;
+    let cos4_prod = [a, b, c].iter().map(|x| (4. * x).cos()).product::<f64>();
+    [
+        cos_prod - sin_prod,
+        g1_imag,
+        4. * cos_prod - 4. * sin_prod - cos4_prod,
+    ]
+}
+
+/// Compute the expected traces :math:`\text{Tr}(U \cdot U_\text{target}^{\dag})` of the best
+/// decompositions of a target :math:`\sim U_d(a, b, c)` with :math:`0, 1, 2, 3` uses of a
+/// supercontrolled basis gate :math:`\sim U_d(\pi/4, b, 0)`.
+#[pyfunction]
+#[pyo3(text_signature = "(target_a, target_b, target_c, basis_b, /)")]
+pub fn kak_traces(target_a: f64, target_b: f64, target_c: f64, basis_b: f64) -> [Complex64; 4] {
+    [
+        4. * Complex64::new(
+            target_a.cos() * target_b.cos() * target_c.cos(),
+            target_a.sin() * target_b.sin() * target_c.sin(),
+        ),
+        4. * Complex64::new(
+            (PI4 - target_a).cos() * (basis_b - target_b).cos() * target_c.cos(),
+            (PI4 - target_a).sin() * (basis_b - target_b).sin() * target_c.sin(),
+        ),
+        Complex64::new(4. * target_c.cos(), 0.),
+        Complex64::new(4., 0.),
+    ]
+}
+
+/// Compute the expected fidelities of the best decompositions of the 2-qubit ``unitary`` with
+/// :math:`0, 1, 2, 3` CX gates of fidelity ``cx_fidelity``, weighted in the same way as when a
+/// :class:`.TwoQubitBasisDecomposer` chooses the number of basis gates, which picks the index of
+/// the largest one.
+#[pyfunction]
+#[pyo3(text_signature = "(unitary, cx_fidelity, /)")]
+pub fn fidelity_curve(
+    unitary: PyReadonlyArray2<Complex64>,
+    cx_fidelity: f64,
+) -> PyResult<[f64; 4]> {
+    let unitary = unitary.as_array();
+    check_two_qubit_shape("fidelity_curve", unitary)?;
+    let target = TwoQubitWeylDecomposition::new_inner(unitary, Some(DEFAULT_FIDELITY), None)?;
+    let traces = kak_traces(target.a, target.b, target.c, 0.);
+    Ok(std::array::from_fn(|n| {
+        traces[n].trace_to_fid() * cx_fidelity.powi(n as i32)
+    }))
+}
+
+/// Compute the average gate infidelity :math:`1 - \bar{F}` between two 2-qubit unitaries
+/// ``u`` and ``v`` from :math:`\text{Tr}(U \cdot V^{\dag})`.
+#[pyfunction]
+#[pyo3(text_signature = "(u, v, /)")]
+pub fn average_gate_infidelity(
+    u: PyReadonlyArray2<Complex64>,
+    v: PyReadonlyArray2<Complex64>,
+) -> PyResult<f64> {
+    let u = u.as_array();
+    let v = v.as_array();
+    check_two_qubit_shape("average_gate_infidelity u", u)?;
+    check_two_qubit_shape("average_gate_infidelity v", v)?;
+    Ok(1. - average_gate_fidelity(u, v))
+}
+
+/// The average gate fidelity between two 2-qubit unitaries ``u`` and ``v``, computed from
+/// :math:`\text{Tr}(U \cdot V^{\dag})`.
+fn average_gate_fidelity(u: ArrayView2<Complex64>, v: ArrayView2<Complex64>) -> f64 {
+    let trace: Complex64 = u
+        .iter()
+        .zip(v.iter())
+        .map(|(u_elem, v_elem)| u_elem * v_elem.conj())
+        .sum();
+    trace.trace_to_fid()
+}
+
+/// Synthesize the 2-qubit ``unitary`` with ``decomposer``, multiply out the synthesized gates
+/// and return the average gate fidelity of the result with ``unitary``.
+///
+/// Unlike the expected fidelity of the decomposition, this includes every approximation and
+/// floating-point error of the synthesis, but the basis gates are taken to be ideal, so the
+/// ``basis_fidelity`` of ``decomposer`` only affects how many of them are used. The fidelity
+/// depends on :math:`|\text{Tr}(U \cdot U_\text{target}^{\dag})|` alone, so an error in the
+/// global phase on its own doesn't show up in it, while an error in the phase of a single-qubit
+/// gate does.
+#[pyfunction]
+#[pyo3(signature = (decomposer, unitary, approximate=true))]
+pub fn synthesis_fidelity(
+    decomposer: &TwoQubitBasisDecomposer,
+    unitary: PyReadonlyArray2<Complex64>,
+    approximate: bool,
+) -> PyResult<f64> {
+    let unitary = unitary.as_array();
+    check_two_qubit_shape("synthesis_fidelity", unitary)?;
+    let synthesized = decomposer.synthesized_unitary(unitary, approximate)?;
+    Ok(average_gate_fidelity(synthesized.view(), unitary))
+}
+
+/// Find the angle :math:`\theta` for which a single :math:`RZZ(\theta)` with optimal single-qubit
+/// gates best approximates the 2-qubit ``unitary``, and the average gate fidelity achieved.
+///
+/// As :math:`RZZ(\theta) \sim U_d(\theta/2, 0, 0)`, the trace with a target
+/// :math:`\sim U_d(a, b, c)` is :math:`4|\cos(a - \theta/2)\cos(b)\cos(c) +
+/// j\sin(a - \theta/2)\sin(b)\sin(c)|`, which is maximal at :math:`\theta = 2a` within the Weyl
+/// chamber.
+#[pyfunction]
+#[pyo3(text_signature = "(unitary, /)")]
+pub fn best_single_rzz_angle(unitary: PyReadonlyArray2<Complex64>) -> PyResult<(f64, f64)> {
+    let unitary = unitary.as_array();
+    check_two_qubit_shape("best_single_rzz_angle", unitary)?;
+    let [a, b, c] = __weyl_coordinates(unitary.into_faer_complex());
+    let trace = Complex64::new(4. * b.cos() * c.cos(), 0.);
+    Ok((2. * a, trace.trace_to_fid()))
+}
+
+/// A standard gate with its parameters and qubits, as taken by
+/// [CircuitData::from_standard_gates].
+type StandardInstruction = (StandardGate, SmallVec<[Param; 3]>, SmallVec<[Qubit; 2]>);
+
+/// The multisets of at most ``max_uses`` of the ``allowed_angles``, grouped by their size, each
+/// as the Weyl coordinate :math:`-\sum_j \theta_j / 2` of the product of the :math:`RZZ(\theta_j)`
+/// together with the indices of the angles.
+fn rzz_angle_multisets(allowed_angles: &[f64], max_uses: usize) -> Vec<Vec<(f64, Vec<usize>)>> {
+    let mut multisets: Vec<Vec<(f64, Vec<usize>)>> = vec![vec![(0., Vec::new())]];
+    for uses in 1..=max_uses {
+        let next = multisets[uses - 1]
+            .iter()
+            .flat_map(|(coordinate, indices)| {
+                // Only extending with angles from the last index on enumerates each multiset once.
+                let first = indices.last().copied().unwrap_or(0);
+                (first..allowed_angles.len()).map(move |index| {
+                    let mut indices = indices.clone();
+                    indices.push(index);
+                    (coordinate - allowed_angles[index] / 2., indices)
+                })
+            })
+            .collect();
+        multisets.push(next);
+    }
+    multisets
+}
+
+/// Find the best approximation of the 2-qubit ``unitary`` by :math:`RZZ` gates whose angles are
+/// taken from ``allowed_angles``, possibly repeated, with at most ``max_uses`` of them in total,
+/// together with single-qubit :class:`.RZGate` and :class:`.RXGate` gates. Return the circuit and
+/// its average gate fidelity with ``unitary``.
+///
+/// Each of the :math:`XX`, :math:`YY` and :math:`ZZ` interactions of the Weyl decomposition of
+/// ``unitary`` is realized by a product of :math:`RZZ` gates, conjugated by single-qubit gates for
+/// the first two, and the combination of products that best matches the Weyl coordinates of
+/// ``unitary`` is chosen, preferring fewer :math:`RZZ` gates. As the number of combinations grows
+/// quickly, a ``QiskitError`` is raised if more than ``1_000_000`` of them would be compared.
+#[pyfunction]
+#[pyo3(text_signature = "(unitary, allowed_angles, max_uses, /)")]
+pub fn best_fixed_angle_rzz(
+    py: Python,
+    unitary: PyReadonlyArray2<Complex64>,
+    allowed_angles: Vec<f64>,
+    max_uses: u8,
+) -> PyResult<(CircuitData, f64)> {
+    let unitary = unitary.as_array();
+    check_two_qubit_shape("best_fixed_angle_rzz", unitary)?;
+    let max_uses = max_uses as usize;
+    // The number of multisets of each size, computed before enumerating any of them.
+    let mut counts = vec![1.; max_uses + 1];
+    for uses in 1..=max_uses {
+        counts[uses] = counts[uses - 1] * (allowed_angles.len() + uses - 1) as f64 / uses as f64;
+    }
+    let splits = || {
+        (0..=max_uses).flat_map(move |total| {
+            (0..=total).flat_map(move |uses_a| {
+                (0..=total - uses_a).map(move |uses_b| [uses_a, uses_b, total - uses_a - uses_b])
+            })
+        })
+    };
+    let num_combinations: f64 = splits()
+        .map(|split| split.iter().map(|uses| counts[*uses]).product::<f64>())
+        .sum();
+    if num_combinations > FIXED_ANGLE_RZZ_MAX_COMBINATIONS {
+        return Err(QiskitError::new_err(format!(
+            "best_fixed_angle_rzz: {num_combinations} combinations of {} angles with up to \
+             {max_uses} uses exceed the limit of {FIXED_ANGLE_RZZ_MAX_COMBINATIONS}",
+            allowed_angles.len()
+        )));
+    }
+    let target = TwoQubitWeylDecomposition::new_inner(unitary, None, None)?;
+    let multisets = rzz_angle_multisets(&allowed_angles, max_uses);
+    let mut best: Option<(f64, [&Vec<usize>; 3])> = None;
+    // The splits are ordered by their total number of uses, so ties keep the fewest gates.
+    for [uses_a, uses_b, uses_c] in splits() {
+        for (x, indices_a) in &multisets[uses_a] {
+            for (y, indices_b) in &multisets[uses_b] {
+                for (z, indices_c) in &multisets[uses_c] {
+                    let [da, db, dc] = [target.a - x, target.b - y, target.c - z];
+                    let trace = 4.
+                        * Complex64::new(
+                            da.cos() * db.cos() * dc.cos(),
+                            da.sin() * db.sin() * dc.sin(),
+                        );
+                    let fidelity = trace.trace_to_fid();
+                    if best.map_or(true, |(best_fidelity, _)| fidelity > best_fidelity) {
+                        best = Some((fidelity, [indices_a, indices_b, indices_c]));
+                    }
+                }
+            }
+        }
+    }
+    let (fidelity, [indices_a, indices_b, indices_c]) = best.unwrap();
+
+    // exp(i x XX) = (H ⊗ H) exp(i x ZZ) (H ⊗ H) and exp(i y YY) = (V ⊗ V) exp(i y ZZ) (V† ⊗ V†)
+    // with V = S H, so each interaction is a product of RZZ gates between single-qubit gates.
+    let h = aview2(&H_GATE).to_owned();
+    let v = aview2(&S_GATE).dot(&h);
+    let v_dag = v.t().mapv(|x| x.conj());
+    let mut gates: Vec<StandardInstruction> = Vec::new();
+    let mut global_phase = target.global_phase;
+    let mut pending = [target.K2r.clone(), target.K2l.clone()];
+    let mut flush = |pending: &mut [Array2<Complex64>; 2], gates: &mut Vec<StandardInstruction>| {
+        for (qubit, matrix) in pending.iter_mut().enumerate() {
+            let sequence = unitary_to_gate_sequence_inner(
+                matrix.view(),
+                &[EulerBasis::ZXZ],
+                qubit,
+                None,
+                true,
+                None,
+            )
+            .unwrap();
+            global_phase += sequence.global_phase;
+            gates.extend(sequence.gates.into_iter().map(|(name, params)| {
+                let gate = match name.as_str() {
+                    "rz" => StandardGate::RZGate,
+                    _ => StandardGate::RXGate,
+                };
+                (
+                    gate,
+                    params.into_iter().map(Param::Float).collect(),
+                    smallvec![Qubit(qubit as u32)],
+                )
+            }));
+            *matrix = Array2::eye(2);
+        }
+    };
+    for (indices, before, after) in [
+        (indices_c, None, None),
+        (indices_b, Some(&v_dag), Some(&v)),
+        (indices_a, Some(&h), Some(&h)),
+    ] {
+        if indices.is_empty() {
+            continue;
+        }
+        if let Some(before) = before {
+            pending = pending.map(|matrix| before.dot(&matrix));
+        }
+        flush(&mut pending, &mut gates);
+        gates.extend(indices.iter().map(|index| {
+            (
+                StandardGate::RZZGate,
+                smallvec![Param::Float(allowed_angles[*index])],
+                smallvec![Qubit(0), Qubit(1)],
+            )
+        }));
+        if let Some(after) = after {
+            pending = [after.clone(), after.clone()];
+        }
+    }
+    pending = [target.K1r.dot(&pending[0]), target.K1l.dot(&pending[1])];
+    flush(&mut pending, &mut gates);
+    let circuit = CircuitData::from_standard_gates(py, 2, gates, Param::Float(global_phase))?;
+    Ok((circuit, fidelity))
+}
+
+/// Return the Weyl coordinates ``[a, b, c]`` of ``unitary`` together with the
+/// :class:`.Specialization` that :class:`.TwoQubitWeylDecomposition` would choose for it at the
+/// given ``fidelity``. Only the eigenvalues needed for the coordinates are computed; the local
+/// :math:`K` matrices of the decomposition are never built.
+#[pyfunction]
+#[pyo3(signature = (unitary, fidelity=DEFAULT_FIDELITY))]
+pub fn weyl_coordinates_and_specialization(
+    unitary: PyReadonlyArray2<Complex64>,
+    fidelity: Option<f64>,
+) -> PyResult<([f64; 3], Specialization)> {
+    let unitary = unitary.as_array();
+    check_two_qubit_shape("weyl_coordinates_and_specialization", unitary)?;
+    let [a, b, c] = __weyl_coordinates(unitary.into_faer_complex());
+    Ok(([a, b, c], select_specialization(a, b, c, fidelity)))
+}
+
+/// Build the canonical gate :math:`e^{i (a XX + b YY + c ZZ)}` for each row ``(a, b, c)`` of the
+/// ``(N, 3)`` array ``coords``, returning an ``(N, 4, 4)`` array.
+#[pyfunction]
+#[pyo3(text_signature = "(coords, /)")]
+pub fn ud_batch(py: Python, coords: PyReadonlyArray2<f64>) -> PyResult<Py<PyArray3<Complex64>>> {
+    let coords = coords.as_array();
+    if coords.shape()[1] != 3 {
+        return Err(QiskitError::new_err(format!(
+            "ud_batch: expected coordinates of shape (N, 3), got {:?}",
+            coords.shape()
+        )));
+    }
+    let mut out: Array3<Complex64> = Array3::zeros((coords.nrows(), 4, 4));
+    for (row, mut matrix) in coords.outer_iter().zip(out.outer_iter_mut()) {
+        matrix.assign(&ud(row[0], row[1], row[2]));
+    }
+    Ok(out.into_pyarray_bound(py).unbind())
+}
+
+/// Parse an Euler basis name, raising a :class:`.QiskitError` listing the valid names if it is
+/// not one of them.
+fn parse_euler_basis(euler_basis: &str) -> PyResult<EulerBasis> {
+    EulerBasis::from_str(euler_basis).map_err(|_| {
+        QiskitError::new_err(format!(
+            "Invalid euler_basis '{}', expected one of: {}",
+            euler_basis,
+            EULER_BASIS_NAMES.join(", ")
+        ))
+    })
+}
+
 /// A good approximation to the best value x to get the minimum
 /// trace distance for :math:`U_d(x, x, x)` from :math:`U_d(a, b, c)`.
-fn closest_partial_swap(a: f64, b: f64, c: f64) -> f64 {
+///
+/// With :math:`m = (a + b + c) / 3` this is the polynomial
+///
+/// .. math::
+///
+///     x = m + (a - m)(b - m)(c - m) \frac{6 + (a - b)^2 + (b - c)^2 + (c - a)^2}{18},
+///
+/// which reduces to the exact optimum :math:`x = a` when the three coordinates are equal.
+#[pyfunction]
+#[pyo3(text_signature = "(a, b, c, /)")]
+pub fn closest_partial_swap(a: f64, b: f64, c: f64) -> f64 {
     let m = (a + b + c) / 3.;
     let [am, bm, cm] = [a - m, b - m, c - m];
     let [ab, bc, ca] = [a - b, b - c, c - a];
     m + am * bm * cm * (6. + ab * ab + bc * bc + ca * ca) / 18.
 }
 
+/// Choose the specialization of the Weyl coordinates :math:`(a, b, c)` for the requested
+/// ``fidelity``, trying the candidates from most to least specialized.
+fn select_specialization(a: f64, b: f64, c: f64, fidelity: Option<f64>) -> Specialization {
+    let is_close = |ap: f64, bp: f64, cp: f64| -> bool {
+        let [da, db, dc] = [a - ap, b - bp, c - cp];
+        let tr = 4.
+            * Complex64::new(
+                da.cos() * db.cos() * dc.cos(),
+                da.sin() * db.sin() * dc.sin(),
+            );
+        match fidelity {
+            Some(fid) => tr.trace_to_fid() >= fid,
+            // Set to false here to default to general specialization in the absence of a
+            // fidelity and provided specialization.
+            None => false,
+        }
+    };
+
+    let closest_abc = closest_partial_swap(a, b, c);
+    let closest_ab_minus_c = closest_partial_swap(a, b, -c);
+    if is_close(0., 0., 0.) {
+        Specialization::IdEquiv
+    } else if is_close(PI4, PI4, PI4) || is_close(PI4, PI4, -PI4) {
+        Specialization::SWAPEquiv
+    } else if is_close(closest_abc, closest_abc, closest_abc) {
+        Specialization::PartialSWAPEquiv
+    } else if is_close(closest_ab_minus_c, closest_ab_minus_c, -closest_ab_minus_c) {
+        Specialization::PartialSWAPFlipEquiv
+    } else if is_close(a, 0., 0.) {
+        Specialization::ControlledEquiv
+    } else if is_close(PI4, PI4, c) {
+        Specialization::MirrorControlledEquiv
+    } else if is_close((a + b) / 2., (a + b) / 2., c) {
+        Specialization::fSimaabEquiv
+    } else if is_close(a, (b + c) / 2., (b + c) / 2.) {
+        Specialization::fSimabbEquiv
+    } else if is_close(a, (b - c) / 2., (c - b) / 2.) {
+        Specialization::fSimabmbEquiv
+    } else {
+        Specialization::General
+    }
+}
+
 fn rx_matrix(theta: f64) -> Array2<Complex64> {
     let half_theta = theta / 2.;
     let cos = Complex64::new(half_theta.cos(), 0.);
@@ -338,43 +1227,247 @@ fn rz_matrix(theta: f64) -> Array2<Complex64> {
     ]
 }
 
-fn compute_unitary(sequence: &TwoQubitSequenceVec, global_phase: f64) -> Array2<Complex64> {
-    let identity = aview2(&ONE_QUBIT_IDENTITY);
+/// The matrix :math:`e^{i (a XX + b YY + c ZZ)}` of the canonical gate.
+fn ud(a: f64, b: f64, c: f64) -> Array2<Complex64> {
+    let zero = Complex64::new(0., 0.);
+    let phase_p = (C1_IM * c).exp();
+    let phase_m = (-C1_IM * c).exp();
+    let [cos_m, sin_m] = [(a - b).cos(), (a - b).sin()];
+    let [cos_p, sin_p] = [(a + b).cos(), (a + b).sin()];
+    array![
+        [phase_p * cos_m, zero, zero, C1_IM * phase_p * sin_m],
+        [zero, phase_m * cos_p, C1_IM * phase_m * sin_p, zero],
+        [zero, C1_IM * phase_m * sin_p, phase_m * cos_p, zero],
+        [C1_IM * phase_p * sin_m, zero, zero, phase_p * cos_m],
+    ]
+}
+
+/// Left-multiply the two-qubit ``matrix`` in place by the single-qubit ``gate`` acting on
+/// ``qubit``, without materializing the Kronecker product with the identity.
+#[inline]
+fn apply_1q_gate(matrix: &mut [[Complex64; 4]; 4], gate: &[[Complex64; 2]; 2], qubit: u8) {
+    let stride = 1 << qubit;
+    for row in (0..4).filter(|row| row & stride == 0) {
+        let (upper, lower) = matrix.split_at_mut(row + stride);
+        for (a, b) in upper[row].iter_mut().zip(lower[0].iter_mut()) {
+            (*a, *b) = (
+                gate[0][0] * *a + gate[0][1] * *b,
+                gate[1][0] * *a + gate[1][1] * *b,
+            );
+        }
+    }
+}
+
+/// Left-multiply the two-qubit ``matrix`` in place by the two-qubit ``gate`` acting on
+/// ``qubits``, which is either ``[0, 1]`` or ``[1, 0]``.
+#[inline]
+fn apply_2q_gate(matrix: &mut [[Complex64; 4]; 4], gate: &[[Complex64; 4]; 4], qubits: &[u8]) {
+    // Swapping the qubits of the gate exchanges the basis states |01> and |10>.
+    let swapped = qubits == [1, 0];
+    let index = |i: usize| if swapped { [0, 2, 1, 3][i] } else { i };
+    let mut out = [[Complex64::new(0., 0.); 4]; 4];
+    for (row, out_row) in out.iter_mut().enumerate() {
+        for (col, out_elem) in out_row.iter_mut().enumerate() {
+            *out_elem = (0..4)
+                .map(|k| gate[index(row)][index(k)] * matrix[k][col])
+                .sum();
+        }
+    }
+    *matrix = out;
+}
+
+/// Multiply out the gates of ``sequence`` with ``global_phase`` into a unitary, taking the
+/// matrices of the two-qubit gates from ``two_qubit_matrix``. The matrices of the single-qubit
+/// gates are taken from the matrix cache if ``use_matrix_cache``.
+fn sequence_matrix(
+    sequence: &TwoQubitSequenceVec,
+    global_phase: f64,
+    use_matrix_cache: bool,
+    two_qubit_matrix: impl Fn(&str, &[f64]) -> PyResult<[[Complex64; 4]; 4]>,
+) -> PyResult<Array2<Complex64>> {
     let phase = Complex64::new(0., global_phase).exp();
-    let mut matrix = Array2::from_diag(&arr1(&[phase, phase, phase, phase]));
-    sequence
-        .iter()
-        .map(|inst| {
-            // This only gets called by get_sx_vz_3cx_efficient_euler()
-            // which only uses sx, x, rz, and cx gates for the circuit
-            // sequence. If we get a different gate this is getting called
-            // by something else and is invalid.
-            let gate_matrix = match inst.0.as_ref() {
-                "sx" => aview2(&SX_GATE).to_owned(),
-                "rz" => rz_matrix(inst.1[0]),
-                "cx" => aview2(&CX_GATE).to_owned(),
-                "x" => aview2(&X_GATE).to_owned(),
-                _ => unreachable!("Undefined gate"),
-            };
-            (gate_matrix, &inst.2)
-        })
-        .for_each(|(op_matrix, q_list)| {
-            let result = match q_list.as_slice() {
-                [0] => Some(kron(&identity, &op_matrix)),
-                [1] => Some(kron(&op_matrix, &identity)),
-                [1, 0] => Some(change_basis(op_matrix.view())),
-                [] => Some(Array2::eye(4)),
-                _ => None,
+    let mut matrix = [[Complex64::new(0., 0.); 4]; 4];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[i] = phase;
+    }
+    for (name, params, qubits) in sequence {
+        if qubits.len() == 2 {
+            apply_2q_gate(&mut matrix, &two_qubit_matrix(name, params)?, qubits);
+        } else {
+            let gate = if use_matrix_cache {
+                cached_gate_matrix(
+                    &ONE_QUBIT_MATRIX_CACHE,
+                    &CACHED_ONE_QUBIT_GATES,
+                    name,
+                    params,
+                    one_qubit_gate_matrix,
+                )?
+            } else {
+                one_qubit_gate_matrix(name, params)?
             };
-            matrix = match result {
-                Some(result) => result.dot(&matrix),
-                None => op_matrix.dot(&matrix),
-            }
-        });
-    matrix
+            apply_1q_gate(&mut matrix, &gate, qubits[0]);
+        }
+    }
+    Ok(aview2(&matrix).to_owned())
+}
+
+/// The key of a parametric gate in a matrix cache: the position of its name in the list of
+/// cached gates, and the bit patterns of its parameters.
+type GateMatrixKey = (u8, [u64; 3]);
+type GateMatrixCache<const N: usize> = RefCell<HashMap<GateMatrixKey, [[Complex64; N]; N]>>;
+
+// The parametric gates whose matrices are cached. The fixed gates use the constant matrices from
+// `gate_matrix`, which cost nothing to construct.
+const CACHED_ONE_QUBIT_GATES: [&str; 9] = ["rx", "ry", "rz", "p", "u1", "r", "u2", "u", "u3"];
+const CACHED_TWO_QUBIT_GATES: [&str; 5] = ["rxx", "ryy", "rzz", "cp", "crz"];
+
+thread_local! {
+    // The matrices of the parametric gates multiplied out by `sequence_matrix`, which repeat
+    // across targets when the synthesized angles come from a fixed set, as for the fixed-angle
+    // gates of the pulse optimal decompositions or for Clifford targets. Each thread has its own
+    // caches, so the threads of a parallel synthesis never wait for each other, and each cache is
+    // cleared once it holds `GATE_MATRIX_CACHE_CAPACITY` matrices. An entry of the single-qubit
+    // cache takes 96 bytes and one of the two-qubit cache 288 bytes, and the hash table of 1024
+    // entries has 2048 slots, so the caches of a thread take at most about 800 KiB.
+    static ONE_QUBIT_MATRIX_CACHE: GateMatrixCache<2> = RefCell::new(HashMap::new());
+    static TWO_QUBIT_MATRIX_CACHE: GateMatrixCache<4> = RefCell::new(HashMap::new());
+}
+
+/// Return the matrix of the gate ``name`` with ``params`` from ``cache`` if it is one of the
+/// ``cached`` gates, computing it with ``matrix`` and storing it on a miss. The parameters are
+/// matched by their bit patterns, so a cached matrix is exactly the one ``matrix`` returns.
+fn cached_gate_matrix<const N: usize>(
+    cache: &'static LocalKey<GateMatrixCache<N>>,
+    cached: &[&str],
+    name: &str,
+    params: &[f64],
+    matrix: impl Fn(&str, &[f64]) -> PyResult<[[Complex64; N]; N]>,
+) -> PyResult<[[Complex64; N]; N]> {
+    let Some(index) = cached.iter().position(|gate| *gate == name) else {
+        return matrix(name, params);
+    };
+    let mut bits = [0; 3];
+    for (bit, param) in bits.iter_mut().zip(params) {
+        // Adding zero turns -0.0 into 0.0, which has the same matrix but other bits.
+        *bit = (param + 0.).to_bits();
+    }
+    let key = (index as u8, bits);
+    cache.with(|cache| {
+        if let Some(gate) = cache.borrow().get(&key) {
+            return Ok(*gate);
+        }
+        let gate = matrix(name, params)?;
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= GATE_MATRIX_CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(key, gate);
+        Ok(gate)
+    })
+}
+
+/// Compute the unitary of the gates ``sequence`` with ``global_phase``, in which a two-qubit
+/// gate on qubits ``[1, 0]`` is applied with its qubits reversed.
+///
+/// Every standard gate the decomposers emit is supported, but a gate only known by the
+/// decomposer that produced it, like ``USER_GATE``, results in an error.
+pub fn compute_unitary(
+    sequence: &TwoQubitSequenceVec,
+    global_phase: f64,
+) -> PyResult<Array2<Complex64>> {
+    compute_unitary_inner(sequence, global_phase, true)
+}
+
+/// Compute the unitary like [compute_unitary], taking the matrices of the parametric gates from
+/// the matrix caches only if ``use_matrix_cache``.
+fn compute_unitary_inner(
+    sequence: &TwoQubitSequenceVec,
+    global_phase: f64,
+    use_matrix_cache: bool,
+) -> PyResult<Array2<Complex64>> {
+    sequence_matrix(sequence, global_phase, use_matrix_cache, |name, params| {
+        if use_matrix_cache {
+            cached_gate_matrix(
+                &TWO_QUBIT_MATRIX_CACHE,
+                &CACHED_TWO_QUBIT_GATES,
+                name,
+                params,
+                two_qubit_gate_matrix,
+            )
+        } else {
+            two_qubit_gate_matrix(name, params)
+        }
+    })
+}
+
+/// Compute the unitary of the gate ``sequence``, including its global phase, as the synthesis
+/// does to check or score a sequence.
+///
+/// With ``use_matrix_cache=False``, the matrices of the parametric gates are built for every gate
+/// instead of being taken from the per-thread matrix caches, so the two can be compared.
+#[pyfunction]
+#[pyo3(signature = (sequence, use_matrix_cache=true))]
+pub fn gate_sequence_unitary(
+    py: Python,
+    sequence: &TwoQubitGateSequence,
+    use_matrix_cache: bool,
+) -> PyResult<PyObject> {
+    let unitary = compute_unitary_inner(&sequence.gates, sequence.global_phase, use_matrix_cache)?;
+    Ok(unitary.into_pyarray_bound(py).into())
+}
+
+/// Compute the unitary of ``sequence``, including its global phase, see [compute_unitary].
+pub fn sequence_to_unitary(sequence: &TwoQubitGateSequence) -> PyResult<Array2<Complex64>> {
+    compute_unitary(&sequence.gates, sequence.global_phase)
 }
 
 const DEFAULT_FIDELITY: f64 = 1.0 - 1.0e-9;
+// The lowest fidelity accepted for a specialization that is forced without a requested fidelity.
+const FORCED_SPECIALIZATION_MIN_FIDELITY: f64 = 0.99;
+// The euler bases tried by `generate_sequence` when minimizing the number or the error of the
+// single-qubit gates.
+const MINIMIZE_1Q_BASES: [EulerBasis; 3] = [EulerBasis::ZYZ, EulerBasis::ZXZ, EulerBasis::ZSX];
+// Absolute tolerance below which the Weyl coordinate `c` of a basis gate is treated as zero.
+const BASIS_C_ATOL: f64 = 1.0e-9;
+// Absolute tolerance on the fidelity below which a decomposition counts as exact when the basis
+// gate is ideal. This only absorbs rounding errors in the traces.
+const EXACT_FIDELITY_ATOL: f64 = 8.0 * f64::EPSILON;
+// Absolute tolerance on the Weyl coordinates below which a target is treated as locally
+// equivalent to the basis gate.
+const BASIS_COORDINATES_ATOL: f64 = 1.0e-12;
+// Absolute tolerance on the determinant below which a target is treated as already in SU(4).
+const SU4_DET_ATOL: f64 = 1.0e-12;
+// Absolute tolerance on the argument of the determinant of a target below which it is treated as
+// lying on the branch cut of `arg` at -pi.
+const DET_BRANCH_CUT_ATOL: f64 = 1.0e-9;
+// Absolute tolerance on the matrix elements below which a target is treated as a local gate.
+const LOCAL_GATE_ATOL: f64 = 1.0e-12;
+// Absolute tolerance on the matrix elements below which a target is treated as the SWAP gate.
+const SWAP_GATE_ATOL: f64 = 1.0e-12;
+// The number of attempts to diagonalize `M2` in the Weyl decomposition before giving up.
+const DEFAULT_MAX_DIAGONALIZATION_ITERATIONS: usize = 100;
+// Absolute tolerance on the imaginary (real) parts of the elements of `M2` below which it is
+// treated as purely real (imaginary) and diagonalized directly.
+const M2_REAL_OR_IMAG_ATOL: f64 = 1.0e-13;
+// Absolute tolerance on the elements of `U^dagger U - I` below which a basis gate matrix is
+// accepted as unitary.
+const BASIS_UNITARY_ATOL: f64 = 1.0e-8;
+// Absolute tolerance on the moduli of the entries of a diagonal to absorb, which must be 1 for the
+// diagonal to be unitary.
+const DIAGONAL_UNIT_MODULUS_ATOL: f64 = 1.0e-8;
+// The cost of a `cx` relative to a single-qubit gate in the search for Clifford circuits, large
+// enough that circuits with fewer `cx` gates are always preferred.
+const CLIFFORD_CX_COST: usize = 64;
+// Absolute tolerance on the Pauli coefficients of the circuits enumerated in the search for
+// Clifford circuits, which are exact up to rounding errors.
+const CLIFFORD_SEARCH_ATOL: f64 = 1.0e-9;
+// The number of matrices each cache of `cached_gate_matrix` holds before it is cleared.
+const GATE_MATRIX_CACHE_CAPACITY: usize = 1024;
+// The largest number of combinations of angles that `best_fixed_angle_rzz` compares.
+const FIXED_ANGLE_RZZ_MAX_COMBINATIONS: f64 = 1.0e6;
+// The fidelity down to which `generate_sequence` snaps a target to a more specialized form when
+// asked to prefer specializations.
+const PREFER_SPECIALIZED_FIDELITY: f64 = 1.0 - 1.0e-3;
 const C1_IM: Complex64 = Complex64::new(0.0, 1.0);
 
 #[derive(Clone, Debug, Copy)]
@@ -417,6 +1510,33 @@ impl Specialization {
         Ok((py.get_type_bound::<Self>().getattr("_from_u8")?, (val,)).into_py(py))
     }
 
+    /// The number of parameters bound by the specialization.
+    fn num_params(&self) -> u8 {
+        match self {
+            Self::IdEquiv | Self::SWAPEquiv => 0,
+            Self::PartialSWAPEquiv | Self::PartialSWAPFlipEquiv => 3,
+            Self::ControlledEquiv | Self::MirrorControlledEquiv => 4,
+            Self::fSimaabEquiv | Self::fSimabbEquiv | Self::fSimabmbEquiv => 5,
+            Self::General => 6,
+        }
+    }
+
+    /// A short description of the class of gates the specialization applies to.
+    fn description(&self) -> &'static str {
+        match self {
+            Self::General => "U ~ Ud(a, b, c): generic two-qubit gate",
+            Self::IdEquiv => "U ~ Ud(0, 0, 0) ~ Id",
+            Self::SWAPEquiv => "U ~ Ud(pi/4, pi/4, pi/4) ~ SWAP",
+            Self::PartialSWAPEquiv => "U ~ Ud(a, a, a) ~ SWAP^alpha",
+            Self::PartialSWAPFlipEquiv => "U ~ Ud(a, a, -a) ~ SWAP^alpha",
+            Self::ControlledEquiv => "U ~ Ud(a, 0, 0) ~ Ctrl-U",
+            Self::MirrorControlledEquiv => "U ~ Ud(pi/4, pi/4, a) ~ SWAP . Ctrl-U",
+            Self::fSimaabEquiv => "U ~ Ud(a, a, b): fSim-like",
+            Self::fSimabbEquiv => "U ~ Ud(a, b, b): fSim-like",
+            Self::fSimabmbEquiv => "U ~ Ud(a, b, -b): fSim-like",
+        }
+    }
+
     #[staticmethod]
     fn _from_u8(val: u8) -> PyResult<Self> {
         match val {
@@ -437,152 +1557,298 @@ impl Specialization {
     }
 }
 
-#[derive(Clone, Debug)]
-#[allow(non_snake_case)]
-#[pyclass(module = "qiskit._accelerate.two_qubit_decompose", subclass)]
-pub struct TwoQubitWeylDecomposition {
-    #[pyo3(get)]
-    a: f64,
-    #[pyo3(get)]
-    b: f64,
-    #[pyo3(get)]
-    c: f64,
-    #[pyo3(get)]
-    global_phase: f64,
-    K1l: Array2<Complex64>,
-    K2l: Array2<Complex64>,
-    K1r: Array2<Complex64>,
-    K2r: Array2<Complex64>,
-    #[pyo3(get)]
-    specialization: Specialization,
-    default_euler_basis: EulerBasis,
-    #[pyo3(get)]
-    requested_fidelity: Option<f64>,
-    #[pyo3(get)]
-    calculated_fidelity: f64,
-    unitary_matrix: Array2<Complex64>,
+/// The gates used for the interaction part of :meth:`TwoQubitWeylDecomposition.circuit`.
+///
+/// ``Rotations`` emits ``rxx``, ``ryy`` and ``rzz`` (and ``swap`` for the specializations that
+/// use it). ``CXRZ`` emits each interaction as ``cx``, ``rz``, ``cx`` conjugated by ``h`` and
+/// ``s`` as needed, and each ``swap`` as three ``cx``.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+#[pyclass(module = "qiskit._accelerate.two_qubit_decompose")]
+enum WeylBasis {
+    Rotations,
+    CXRZ,
 }
 
-impl TwoQubitWeylDecomposition {
-    fn weyl_gate(
-        &self,
-        simplify: bool,
-        sequence: &mut TwoQubitSequenceVec,
-        atol: f64,
-        global_phase: &mut f64,
-    ) {
-        match self.specialization {
-            Specialization::MirrorControlledEquiv => {
-                sequence.push(("swap".to_string(), SmallVec::new(), smallvec![0, 1]));
-                sequence.push((
-                    "rzz".to_string(),
-                    smallvec![(PI4 - self.c) * 2.],
-                    smallvec![0, 1],
-                ));
-                *global_phase += PI4
-            }
-            Specialization::SWAPEquiv => {
-                sequence.push(("swap".to_string(), SmallVec::new(), smallvec![0, 1]));
-                *global_phase -= 3. * PI / 4.
-            }
-            _ => {
-                if !simplify || self.a.abs() > atol {
-                    sequence.push(("rxx".to_string(), smallvec![-self.a * 2.], smallvec![0, 1]));
-                }
-                if !simplify || self.b.abs() > atol {
-                    sequence.push(("ryy".to_string(), smallvec![-self.b * 2.], smallvec![0, 1]));
-                }
-                if !simplify || self.c.abs() > atol {
-                    sequence.push(("rzz".to_string(), smallvec![-self.c * 2.], smallvec![0, 1]));
-                }
+/// The qubit ordering of an input matrix.
+///
+/// ``Little`` is Qiskit's convention, in which qubit 0 is the least significant bit of the row
+/// and column indices. ``Big`` is the convention of most textbooks and of many other frameworks,
+/// in which qubit 0 is the most significant bit. A big-endian matrix is brought into Qiskit's
+/// convention by swapping its two qubits.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+#[pyclass(module = "qiskit._accelerate.two_qubit_decompose")]
+enum Endianness {
+    Little,
+    Big,
+}
+
+impl Endianness {
+    /// Return the 2-qubit ``unitary`` given in this ordering in Qiskit's little-endian ordering.
+    fn to_little_endian(self, unitary: ArrayView2<Complex64>) -> Array2<Complex64> {
+        match self {
+            Endianness::Little => unitary.to_owned(),
+            Endianness::Big => change_basis(unitary),
+        }
+    }
+}
+
+/// The order in which the gates of a sequence are multiplied into its unitary.
+///
+/// ``RightToLeft`` is Qiskit's convention, in which the first gate of a sequence is applied
+/// first and so is the rightmost factor of the unitary. ``LeftToRight`` is the convention of
+/// simulators that multiply the gates of a list from left to right, in which the first gate is
+/// the leftmost factor. A sequence is brought into the latter convention by reversing the order
+/// of its gates, without inverting them.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+#[pyclass(module = "qiskit._accelerate.two_qubit_decompose")]
+enum MultiplicationOrder {
+    RightToLeft,
+    LeftToRight,
+}
+
+/// The options of :meth:`.TwoQubitBasisDecomposer.generate_sequence` that choose between
+/// equivalent sequences or post-process the synthesized one, given as keyword arguments with the
+/// names of the attributes. See :meth:`~.TwoQubitBasisDecomposer.generate_sequence` for their
+/// meaning.
+#[derive(Clone, Debug)]
+#[pyclass(module = "qiskit._accelerate.two_qubit_decompose", get_all, set_all)]
+pub struct SequenceOptions {
+    min_acceptable_fidelity: Option<f64>,
+    balance_single_qubit: bool,
+    schedule: bool,
+    snap_angles: Option<f64>,
+    absorb_global_phase: bool,
+    minimize_1q: bool,
+    euler_basis_q1_error: Option<(f64, f64)>,
+    depth_optimal: bool,
+    use_power: bool,
+    prefer_specialized: bool,
+    input_endianness: Endianness,
+    pad_identities: bool,
+    convention: MultiplicationOrder,
+    verify_atol: Option<f64>,
+    optimize_1q: bool,
+}
+
+impl Default for SequenceOptions {
+    fn default() -> Self {
+        SequenceOptions {
+            min_acceptable_fidelity: None,
+            balance_single_qubit: false,
+            schedule: false,
+            snap_angles: None,
+            absorb_global_phase: false,
+            minimize_1q: false,
+            euler_basis_q1_error: None,
+            depth_optimal: false,
+            use_power: false,
+            prefer_specialized: false,
+            input_endianness: Endianness::Little,
+            pad_identities: false,
+            convention: MultiplicationOrder::RightToLeft,
+            verify_atol: None,
+            optimize_1q: false,
+        }
+    }
+}
+
+#[pymethods]
+impl SequenceOptions {
+    #[new]
+    #[pyo3(signature = (**kwargs))]
+    fn new(py: Python, kwargs: Option<&Bound<PyDict>>) -> PyResult<Self> {
+        let options = Bound::new(py, SequenceOptions::default())?;
+        if let Some(kwargs) = kwargs {
+            for (name, value) in kwargs {
+                let name: PyBackedStr = name.extract()?;
+                options.setattr(&*name, value)?;
             }
         }
+        let options = options.borrow().clone();
+        Ok(options)
     }
 }
 
-static IPZ: [[Complex64; 2]; 2] = [
-    [C1_IM, Complex64::new(0., 0.)],
-    [Complex64::new(0., 0.), Complex64::new(0., -1.)],
-];
-static IPY: [[Complex64; 2]; 2] = [
-    [Complex64::new(0., 0.), Complex64::new(1., 0.)],
-    [Complex64::new(-1., 0.), Complex64::new(0., 0.)],
-];
-static IPX: [[Complex64; 2]; 2] = [
-    [Complex64::new(0., 0.), C1_IM],
-    [C1_IM, Complex64::new(0., 0.)],
-];
+/// The options of :meth:`.TwoQubitWeylDecomposition.circuit` that select how the decomposition
+/// is turned into gates, given as keyword arguments with the names of the attributes. See
+/// :meth:`~.TwoQubitWeylDecomposition.circuit` for their meaning.
+#[derive(Clone, Debug)]
+#[pyclass(module = "qiskit._accelerate.two_qubit_decompose", get_all, set_all)]
+pub struct WeylCircuitOptions {
+    simplify: bool,
+    atol: Option<f64>,
+    clifford: bool,
+    avoid_ryy: bool,
+    drop_atol: Option<f64>,
+    basis: WeylBasis,
+    absorb_swap: bool,
+    high_precision: bool,
+}
+
+impl Default for WeylCircuitOptions {
+    fn default() -> Self {
+        WeylCircuitOptions {
+            simplify: false,
+            atol: None,
+            clifford: false,
+            avoid_ryy: false,
+            drop_atol: None,
+            basis: WeylBasis::Rotations,
+            absorb_swap: false,
+            high_precision: false,
+        }
+    }
+}
 
 #[pymethods]
-impl TwoQubitWeylDecomposition {
-    #[staticmethod]
-    fn _from_state(
-        angles: [f64; 4],
-        matrices: [PyReadonlyArray2<Complex64>; 5],
-        specialization: Specialization,
-        default_euler_basis: EulerBasis,
-        calculated_fidelity: f64,
-        requested_fidelity: Option<f64>,
-    ) -> Self {
-        let [a, b, c, global_phase] = angles;
-        Self {
-            a,
-            b,
-            c,
-            global_phase,
-            K1l: matrices[0].as_array().to_owned(),
-            K1r: matrices[1].as_array().to_owned(),
-            K2l: matrices[2].as_array().to_owned(),
-            K2r: matrices[3].as_array().to_owned(),
-            specialization,
-            default_euler_basis,
-            calculated_fidelity,
-            requested_fidelity,
-            unitary_matrix: matrices[4].as_array().to_owned(),
+impl WeylCircuitOptions {
+    #[new]
+    #[pyo3(signature = (**kwargs))]
+    fn new(py: Python, kwargs: Option<&Bound<PyDict>>) -> PyResult<Self> {
+        let options = Bound::new(py, WeylCircuitOptions::default())?;
+        if let Some(kwargs) = kwargs {
+            for (name, value) in kwargs {
+                let name: PyBackedStr = name.extract()?;
+                options.setattr(&*name, value)?;
+            }
         }
+        let options = options.borrow().clone();
+        Ok(options)
     }
+}
 
-    fn __reduce__(&self, py: Python) -> PyResult<Py<PyAny>> {
-        Ok((
-            py.get_type_bound::<Self>().getattr("_from_state")?,
-            (
-                [self.a, self.b, self.c, self.global_phase],
-                [
-                    self.K1l.to_pyarray_bound(py),
-                    self.K1r.to_pyarray_bound(py),
-                    self.K2l.to_pyarray_bound(py),
-                    self.K2r.to_pyarray_bound(py),
-                    self.unitary_matrix.to_pyarray_bound(py),
-                ],
-                self.specialization,
-                self.default_euler_basis,
-                self.calculated_fidelity,
-                self.requested_fidelity,
-            ),
+#[derive(Clone, Debug)]
+#[allow(non_snake_case)]
+#[pyclass(module = "qiskit._accelerate.two_qubit_decompose", subclass)]
+pub struct TwoQubitWeylDecomposition {
+    #[pyo3(get)]
+    a: f64,
+    #[pyo3(get)]
+    b: f64,
+    #[pyo3(get)]
+    c: f64,
+    #[pyo3(get)]
+    global_phase: f64,
+    K1l: Array2<Complex64>,
+    K2l: Array2<Complex64>,
+    K1r: Array2<Complex64>,
+    K2r: Array2<Complex64>,
+    #[pyo3(get)]
+    specialization: Specialization,
+    default_euler_basis: EulerBasis,
+    #[pyo3(get)]
+    requested_fidelity: Option<f64>,
+    #[pyo3(get)]
+    calculated_fidelity: f64,
+    /// The number of attempts the diagonalization of :math:`M_2` took. Values above 1 flag
+    /// targets that are numerically close to a degeneracy.
+    #[pyo3(get)]
+    diagonalization_iterations: usize,
+    unitary_matrix: Array2<Complex64>,
+}
+
+impl TwoQubitWeylDecomposition {
+    pub fn new_inner(
+        unitary_matrix: ArrayView2<Complex64>,
+        fidelity: Option<f64>,
+        _specialization: Option<Specialization>,
+    ) -> PyResult<Self> {
+        Self::new_inner_with_max_iterations(
+            unitary_matrix,
+            fidelity,
+            _specialization,
+            DEFAULT_MAX_DIAGONALIZATION_ITERATIONS,
         )
-            .into_py(py))
     }
 
-    #[new]
-    #[pyo3(signature=(unitary_matrix, fidelity=DEFAULT_FIDELITY, _specialization=None))]
-    fn new(
-        unitary_matrix: PyReadonlyArray2<Complex64>,
+    /// Like ``new_inner``, but with at most ``max_iterations`` attempts to diagonalize ``M2``
+    /// before returning an error, instead of the default of 100. The direct attempt for purely
+    /// real or imaginary ``M2`` counts towards the limit like the randomized ones, so with
+    /// ``max_iterations == 0`` only the local and SWAP targets, which skip the diagonalization,
+    /// can be decomposed.
+    pub fn new_inner_with_max_iterations(
+        unitary_matrix: ArrayView2<Complex64>,
         fidelity: Option<f64>,
         _specialization: Option<Specialization>,
+        max_iterations: usize,
     ) -> PyResult<Self> {
         let ipz: ArrayView2<Complex64> = aview2(&IPZ);
         let ipy: ArrayView2<Complex64> = aview2(&IPY);
         let ipx: ArrayView2<Complex64> = aview2(&IPX);
 
-        let mut u = unitary_matrix.as_array().to_owned();
-        let unitary_matrix = unitary_matrix.as_array().to_owned();
+        // The input may come from Python with any memory layout, such as a Fortran-ordered or
+        // strided array. The copies below are made in standard layout so that nothing downstream
+        // depends on it.
+        let mut u = unitary_matrix.as_standard_layout().into_owned();
+        let unitary_matrix = u.clone();
         let det_u = u.view().into_faer_complex().determinant().to_num_complex();
-        let det_pow = det_u.powf(-0.25);
-        u.mapv_inplace(|x| x * det_pow);
-        let mut global_phase = det_u.arg() / 4.;
+        // Targets already in SU(4) are used as given, since normalizing them would only add
+        // rounding errors.
+        let mut global_phase = 0.;
+        if (det_u - 1.).norm() >= SU4_DET_ATOL {
+            // `arg` jumps from pi to -pi across the negative real axis, which would make the
+            // global phase of targets on either side of it differ by pi / 2. Arguments at the
+            // cut are moved to the branch above pi so that nearby targets get nearby phases.
+            let mut det_arg = det_u.arg();
+            if det_arg < -PI + DET_BRANCH_CUT_ATOL {
+                det_arg += TWO_PI;
+            }
+            let det_pow = (-C1_IM * (det_arg / 4.)).exp() * det_u.norm().powf(-0.25);
+            u.mapv_inplace(|x| x * det_pow);
+            global_phase = det_arg / 4.;
+        }
+        let default_euler_basis = EulerBasis::ZYZ;
+        // For local gates `M2` is the identity up to numerical noise, so the eigenvectors below are
+        // arbitrary and give correct but needlessly non-trivial K matrices. Split them directly.
+        if _specialization.is_none() {
+            if let Some((k1l, k1r, phase)) = local_gate_factors(u.view()) {
+                return Ok(TwoQubitWeylDecomposition {
+                    a: 0.,
+                    b: 0.,
+                    c: 0.,
+                    global_phase: global_phase + phase,
+                    K1l: k1l,
+                    K1r: k1r,
+                    K2l: Array2::eye(2),
+                    K2r: Array2::eye(2),
+                    specialization: select_specialization(0., 0., 0., fidelity),
+                    default_euler_basis,
+                    requested_fidelity: fidelity,
+                    calculated_fidelity: 1.0,
+                    diagonalization_iterations: 0,
+                    unitary_matrix,
+                });
+            }
+            // A literal SWAP is common in routed circuits, and :math:`U_d(\pi/4, \pi/4, \pi/4)`
+            // is :math:`e^{i\pi/4} \text{SWAP}`, so it needs no eigendecomposition either. The
+            // other specializations a low requested fidelity could select take the general path.
+            if let Some(phase) = swap_gate_phase(u.view()) {
+                let specialization = select_specialization(PI4, PI4, PI4, fidelity);
+                if matches!(
+                    specialization,
+                    Specialization::SWAPEquiv | Specialization::General
+                ) {
+                    return Ok(TwoQubitWeylDecomposition {
+                        a: PI4,
+                        b: PI4,
+                        c: PI4,
+                        global_phase: global_phase + phase - PI4,
+                        K1l: Array2::eye(2),
+                        K1r: Array2::eye(2),
+                        K2l: Array2::eye(2),
+                        K2r: Array2::eye(2),
+                        specialization,
+                        default_euler_basis,
+                        requested_fidelity: fidelity,
+                        calculated_fidelity: 1.0,
+                        diagonalization_iterations: 0,
+                        unitary_matrix,
+                    });
+                }
+            }
+        }
         let u_p = magic_basis_transform(u.view(), MagicBasisTransform::OutOf);
         let m2 = u_p.t().dot(&u_p);
-        let default_euler_basis = EulerBasis::ZYZ;
 
         // M2 is a symmetric complex matrix. We need to decompose it as M2 = P D P^T where
         // P ∈ SO(4), D is diagonal with unit-magnitude elements.
@@ -596,52 +1862,59 @@ impl TwoQubitWeylDecomposition {
         // Mixing them together _should_ account for any degeneracy problems, but it's not
         // guaranteed, so we repeat it a little bit.  The fixed seed is to make failures
         // deterministic; the value is not important.
-        let mut state = Pcg64Mcg::seed_from_u64(2023);
         let mut found = false;
+        let mut diagonalization_iterations = 0;
         let mut d: Array1<Complex64> = Array1::zeros(0);
         let mut p: Array2<Complex64> = Array2::zeros((0, 0));
-        for i in 0..100 {
-            let rand_a: f64;
-            let rand_b: f64;
-            // For debugging the algorithm use the same RNG values from the
-            // previous Python implementation for the first random trial.
-            // In most cases this loop only executes a single iteration and
-            // using the same rng values rules out possible RNG differences
-            // as the root cause of a test failure
-            if i == 0 {
-                rand_a = 1.2602066112249388;
-                rand_b = 0.22317849046722027;
+        // Fast path: if `M2` is (up to numerical noise) purely real or purely imaginary, which is
+        // the case e.g. for real-orthogonal targets in the CNOT class or for local gates, then the
+        // nonzero part is a real-symmetric matrix whose eigenvectors diagonalize `M2` exactly, even
+        // with degenerate eigenvalues, so no random mixing is needed.
+        let m2_is_real = m2.iter().all(|x| x.im.abs() < M2_REAL_OR_IMAG_ATOL);
+        let m2_is_imag = !m2_is_real && m2.iter().all(|x| x.re.abs() < M2_REAL_OR_IMAG_ATOL);
+        if (m2_is_real || m2_is_imag) && max_iterations > 0 {
+            diagonalization_iterations += 1;
+            let m2_part = if m2_is_real {
+                m2.mapv(|val| val.re)
             } else {
-                rand_a = state.sample(StandardNormal);
-                rand_b = state.sample(StandardNormal);
-            }
-            let m2_real = m2.mapv(|val| rand_a * val.re + rand_b * val.im);
-            let p_inner = m2_real
-                .view()
-                .into_faer()
-                .selfadjoint_eigendecomposition(Lower)
-                .u()
-                .into_ndarray()
-                .mapv(Complex64::from);
-            let d_inner = p_inner.t().dot(&m2).dot(&p_inner).diag().to_owned();
-            let mut diag_d: Array2<Complex64> = Array2::zeros((4, 4));
-            diag_d
-                .diag_mut()
-                .iter_mut()
-                .enumerate()
-                .for_each(|(index, x)| *x = d_inner[index]);
-
-            let compare = p_inner.dot(&diag_d).dot(&p_inner.t());
-            found = abs_diff_eq!(compare.view(), m2, epsilon = 1.0e-13);
-            if found {
+                m2.mapv(|val| val.im)
+            };
+            if let Some((p_inner, d_inner)) = diagonalize_m2(m2.view(), m2_part.view()) {
                 p = p_inner;
                 d = d_inner;
-                break;
+                found = true;
+            }
+        }
+        if !found {
+            let mut state = Pcg64Mcg::seed_from_u64(2023);
+            for i in 0..max_iterations - diagonalization_iterations {
+                let rand_a: f64;
+                let rand_b: f64;
+                // For debugging the algorithm use the same RNG values from the
+                // previous Python implementation for the first random trial.
+                // In most cases this loop only executes a single iteration and
+                // using the same rng values rules out possible RNG differences
+                // as the root cause of a test failure
+                if i == 0 {
+                    rand_a = 1.2602066112249388;
+                    rand_b = 0.22317849046722027;
+                } else {
+                    rand_a = state.sample(StandardNormal);
+                    rand_b = state.sample(StandardNormal);
+                }
+                let m2_real = m2.mapv(|val| rand_a * val.re + rand_b * val.im);
+                diagonalization_iterations += 1;
+                if let Some((p_inner, d_inner)) = diagonalize_m2(m2.view(), m2_real.view()) {
+                    p = p_inner;
+                    d = d_inner;
+                    found = true;
+                    break;
+                }
             }
         }
         if !found {
             return Err(QiskitError::new_err(format!(
-                "TwoQubitWeylDecomposition: failed to diagonalize M2. Please report this at https://github.com/Qiskit/qiskit-terra/issues/4159. Input: {:?}", unitary_matrix
+                "TwoQubitWeylDecomposition: failed to diagonalize M2 in {} attempts. Please report this at https://github.com/Qiskit/qiskit-terra/issues/4159. Input: {:?}", diagonalization_iterations, unitary_matrix
             )));
         }
         let mut d = -d.map(|x| x.arg() / 2.);
@@ -734,49 +2007,10 @@ impl TwoQubitWeylDecomposition {
             global_phase -= PI2;
         }
         let [a, b, c] = [cs[1], cs[0], cs[2]];
-        let is_close = |ap: f64, bp: f64, cp: f64| -> bool {
-            let [da, db, dc] = [a - ap, b - bp, c - cp];
-            let tr = 4.
-                * Complex64::new(
-                    da.cos() * db.cos() * dc.cos(),
-                    da.sin() * db.sin() * dc.sin(),
-                );
-            match fidelity {
-                Some(fid) => tr.trace_to_fid() >= fid,
-                // Set to false here to default to general specialization in the absence of a
-                // fidelity and provided specialization.
-                None => false,
-            }
-        };
-
-        let closest_abc = closest_partial_swap(a, b, c);
-        let closest_ab_minus_c = closest_partial_swap(a, b, -c);
         let mut flipped_from_original = false;
         let specialization = match _specialization {
             Some(specialization) => specialization,
-            None => {
-                if is_close(0., 0., 0.) {
-                    Specialization::IdEquiv
-                } else if is_close(PI4, PI4, PI4) || is_close(PI4, PI4, -PI4) {
-                    Specialization::SWAPEquiv
-                } else if is_close(closest_abc, closest_abc, closest_abc) {
-                    Specialization::PartialSWAPEquiv
-                } else if is_close(closest_ab_minus_c, closest_ab_minus_c, -closest_ab_minus_c) {
-                    Specialization::PartialSWAPFlipEquiv
-                } else if is_close(a, 0., 0.) {
-                    Specialization::ControlledEquiv
-                } else if is_close(PI4, PI4, c) {
-                    Specialization::MirrorControlledEquiv
-                } else if is_close((a + b) / 2., (a + b) / 2., c) {
-                    Specialization::fSimaabEquiv
-                } else if is_close(a, (b + c) / 2., (b + c) / 2.) {
-                    Specialization::fSimabbEquiv
-                } else if is_close(a, (b - c) / 2., (c - b) / 2.) {
-                    Specialization::fSimabmbEquiv
-                } else {
-                    Specialization::General
-                }
-            }
+            None => select_specialization(a, b, c, fidelity),
         };
         let general = TwoQubitWeylDecomposition {
             a,
@@ -791,6 +2025,7 @@ impl TwoQubitWeylDecomposition {
             default_euler_basis,
             requested_fidelity: fidelity,
             calculated_fidelity: -1.0,
+            diagonalization_iterations,
             unitary_matrix,
         };
         let mut specialized: TwoQubitWeylDecomposition = match specialization {
@@ -941,6 +2176,10 @@ impl TwoQubitWeylDecomposition {
             // This gate binds 5 parameters, we make it canonical by setting:
             //
             // :math:`K2_l = Ry(\theta_l)\cdot Rz(\lambda_l)`.
+            //
+            // This includes the number conserving Givens (XX+YY) rotations at :math:`\beta = 0`.
+            // They have the same :math:`Rz \otimes Rz` symmetry as any other :math:`\beta`, so
+            // they need no separate specialization.
             Specialization::fSimaabEquiv => {
                 let [k2ltheta, k2lphi, k2llambda, k2lphase] =
                     angles_from_unitary(general.K2l.view(), EulerBasis::ZYZ);
@@ -1037,70 +2276,499 @@ impl TwoQubitWeylDecomposition {
                     fid
                 )));
             }
+        } else if _specialization.is_some()
+            && specialized.calculated_fidelity < FORCED_SPECIALIZATION_MIN_FIDELITY
+        {
+            // Without a requested fidelity a forced specialization would otherwise be trusted
+            // blindly, so reject ones that clearly don't match the target.
+            return Err(QiskitError::new_err(format!(
+                "Specialization: {:?} was forced, but its calculated fidelity: {} is below {}",
+                specialized.specialization,
+                specialized.calculated_fidelity,
+                FORCED_SPECIALIZATION_MIN_FIDELITY
+            )));
         }
         specialized.global_phase += tr.arg();
         Ok(specialized)
     }
 
-    #[allow(non_snake_case)]
-    #[getter]
-    fn K1l(&self, py: Python) -> PyObject {
-        self.K1l.to_pyarray_bound(py).into()
-    }
-
-    #[allow(non_snake_case)]
-    #[getter]
-    fn K1r(&self, py: Python) -> PyObject {
-        self.K1r.to_pyarray_bound(py).into()
+    /// The unitary :math:`e^{i\phi} K_1 U_d(a, b, c) K_2` realized by the decomposition, which
+    /// differs from the target by the approximation error of the specialization.
+    fn specialized_unitary(&self) -> Array2<Complex64> {
+        let k1 = kron(&self.K1l, &self.K1r);
+        let k2 = kron(&self.K2l, &self.K2r);
+        k1.dot(&self.weyl_gate_matrix()).dot(&k2)
     }
 
-    #[allow(non_snake_case)]
-    #[getter]
-    fn K2l(&self, py: Python) -> PyObject {
-        self.K2l.to_pyarray_bound(py).into()
+    /// The entangling core :math:`e^{i\phi} U_d(a, b, c)` of the decomposition, without the
+    /// local gates :math:`K_1` and :math:`K_2`.
+    pub fn weyl_gate_matrix(&self) -> Array2<Complex64> {
+        ud(self.a, self.b, self.c) * (C1_IM * self.global_phase).exp()
     }
 
-    #[allow(non_snake_case)]
-    #[getter]
-    fn K2r(&self, py: Python) -> PyObject {
-        self.K2r.to_pyarray_bound(py).into()
+    /// The Weyl coordinates and global phase ``[a, b, c, global_phase]``, together with borrowed
+    /// views of the local gates ``[K1l, K1r, K2l, K2r]``, without copying any of the matrices.
+    pub fn parts(&self) -> ([f64; 4], [ArrayView2<Complex64>; 4]) {
+        (
+            [self.a, self.b, self.c, self.global_phase],
+            [
+                self.K1l.view(),
+                self.K1r.view(),
+                self.K2l.view(),
+                self.K2r.view(),
+            ],
+        )
     }
 
-    #[getter]
-    fn unitary_matrix(&self, py: Python) -> PyObject {
-        self.unitary_matrix.to_pyarray_bound(py).into()
+    /// The factors ``(K1l, K1r, K2l, K2r, d)`` of the decomposition for tensor-network
+    /// contraction, where ``d`` holds the eigenvalues of the entangling core
+    /// :math:`e^{i\phi} U_d(a, b, c)`, which is diagonal in the magic basis:
+    ///
+    /// .. math::
+    ///
+    ///     d = e^{i\phi} (e^{i(a - b + c)}, e^{i(-a + b + c)}, e^{i(a + b - c)}, e^{-i(a + b + c)}).
+    ///
+    /// The magic basis vectors are, in order, :math:`(|00\rangle + |11\rangle)/\sqrt{2}`,
+    /// :math:`i(|00\rangle - |11\rangle)/\sqrt{2}`, :math:`i(|01\rangle + |10\rangle)/\sqrt{2}`
+    /// and :math:`(|01\rangle - |10\rangle)/\sqrt{2}`; with :math:`B` the matrix that has them as
+    /// columns, the target is
+    ///
+    /// .. math::
+    ///
+    ///     U = (K_{1l} \otimes K_{1r}) B \operatorname{diag}(d) B^\dagger (K_{2l} \otimes K_{2r}),
+    ///
+    /// so a state is contracted first with ``K2l`` and ``K2r`` on their qubits, then with
+    /// :math:`B^\dagger`, the phases ``d``, :math:`B`, and finally ``K1l`` and ``K1r``.
+    pub fn to_tensor_factors(
+        &self,
+    ) -> (
+        Array2<Complex64>,
+        Array2<Complex64>,
+        Array2<Complex64>,
+        Array2<Complex64>,
+        [Complex64; 4],
+    ) {
+        let (a, b, c) = (self.a, self.b, self.c);
+        let d = [a - b + c, -a + b + c, a + b - c, -a - b - c]
+            .map(|angle| (C1_IM * (angle + self.global_phase)).exp());
+        (
+            self.K1l.clone(),
+            self.K1r.clone(),
+            self.K2l.clone(),
+            self.K2r.clone(),
+            d,
+        )
     }
 
-    #[pyo3(signature = (euler_basis=None, simplify=false, atol=None))]
-    fn circuit(
+    /// Push the interaction part of the decomposition onto ``sequence`` using the gates of
+    /// ``options.basis``. With ``simplify``, an interaction whose coordinate is at most
+    /// ``drop_atol`` in magnitude is left out; ``atol`` is the tolerance for recognizing Clifford
+    /// angles. With ``absorb_swap``, the SWAP of the specializations that have one is left out.
+    fn weyl_gate(
         &self,
-        euler_basis: Option<PyBackedStr>,
-        simplify: bool,
-        atol: Option<f64>,
-    ) -> PyResult<TwoQubitGateSequence> {
-        let euler_basis: EulerBasis = match euler_basis {
-            Some(basis) => EulerBasis::__new__(basis.deref())?,
-            None => self.default_euler_basis,
+        options: &WeylCircuitOptions,
+        sequence: &mut TwoQubitSequenceVec,
+        global_phase: &mut f64,
+    ) {
+        let WeylCircuitOptions {
+            simplify,
+            clifford,
+            avoid_ryy,
+            absorb_swap,
+            basis,
+            ..
+        } = *options;
+        let atol = options.atol.unwrap_or(ANGLE_ZERO_EPSILON);
+        let drop_atol = options
+            .drop_atol
+            .or(options.atol)
+            .unwrap_or(ANGLE_ZERO_EPSILON);
+        let push_swap = |sequence: &mut TwoQubitSequenceVec| match basis {
+            WeylBasis::Rotations => {
+                sequence.push(("swap".to_string(), SmallVec::new(), smallvec![0, 1]))
+            }
+            WeylBasis::CXRZ => {
+                for qubits in [[0, 1], [1, 0], [0, 1]] {
+                    sequence.push(("cx".to_string(), SmallVec::new(), SmallVec::from(qubits)));
+                }
+            }
         };
-        let target_1q_basis_list: Vec<EulerBasis> = vec![euler_basis];
+        match self.specialization {
+            Specialization::MirrorControlledEquiv => {
+                if !absorb_swap {
+                    push_swap(sequence);
+                }
+                let theta = (PI4 - self.c) * 2.;
+                match basis {
+                    WeylBasis::Rotations => {
+                        sequence.push(("rzz".to_string(), smallvec![theta], smallvec![0, 1]))
+                    }
+                    WeylBasis::CXRZ => push_cxrz_interaction(sequence, "rzz", theta),
+                }
+                *global_phase += PI4
+            }
+            Specialization::SWAPEquiv => {
+                if !absorb_swap {
+                    push_swap(sequence);
+                }
+                *global_phase -= 3. * PI / 4.
+            }
+            _ => {
+                for (name, coordinate) in [("rxx", self.a), ("ryy", self.b), ("rzz", self.c)] {
+                    if simplify && coordinate.abs() <= drop_atol {
+                        continue;
+                    }
+                    let theta = -coordinate * 2.;
+                    if clifford
+                        && push_clifford_interaction(sequence, global_phase, name, theta, atol)
+                    {
+                        continue;
+                    }
+                    if basis == WeylBasis::CXRZ {
+                        push_cxrz_interaction(sequence, name, theta);
+                    } else if avoid_ryy && name == "ryy" {
+                        // RYY(theta) = (S x S) RXX(theta) (Sdg x Sdg)
+                        for qubit in 0..2 {
+                            sequence.push(("sdg".to_string(), SmallVec::new(), smallvec![qubit]));
+                        }
+                        sequence.push(("rxx".to_string(), smallvec![theta], smallvec![0, 1]));
+                        for qubit in 0..2 {
+                            sequence.push(("s".to_string(), SmallVec::new(), smallvec![qubit]));
+                        }
+                    } else {
+                        sequence.push((name.to_string(), smallvec![theta], smallvec![0, 1]));
+                    }
+                }
+            }
+        }
+    }
+}
 
-        let mut gate_sequence = Vec::new();
-        let mut global_phase: f64 = self.global_phase;
+/// If ``theta`` is :math:`\pm\pi/2`, push the Clifford realization of the interaction ``name``
+/// (one of ``rxx``, ``ryy`` or ``rzz``) with angle ``theta`` onto ``sequence`` and return
+/// ``true``. The ``ZZ`` interaction is realized as a ``cz`` with ``s``/``sdg`` on both qubits,
+/// and the ``XX`` and ``YY`` interactions by conjugating it with ``h`` and ``s``.
+fn push_clifford_interaction(
+    sequence: &mut TwoQubitSequenceVec,
+    global_phase: &mut f64,
+    name: &str,
+    theta: f64,
+    atol: f64,
+) -> bool {
+    if (theta.abs() - PI2).abs() > atol {
+        return false;
+    }
+    let (pre, post) = zz_basis_change(name);
+    push_1q(sequence, pre);
+    // RZZ(-pi/2) = e^{i pi/4} CZ (Sdg x Sdg) and RZZ(pi/2) = e^{-i pi/4} CZ (S x S)
+    if theta < 0. {
+        push_1q(sequence, &["sdg"]);
+        *global_phase += PI4;
+    } else {
+        push_1q(sequence, &["s"]);
+        *global_phase -= PI4;
+    }
+    sequence.push(("cz".to_string(), SmallVec::new(), smallvec![0, 1]));
+    push_1q(sequence, post);
+    true
+}
 
-        let c2r = unitary_to_gate_sequence_inner(
-            self.K2r.view(),
-            &target_1q_basis_list,
-            0,
-            None,
-            simplify,
-            atol,
-        )
-        .unwrap();
-        for gate in c2r.gates {
-            gate_sequence.push((gate.0, gate.1, smallvec![0]))
-        }
-        global_phase += c2r.global_phase;
-        let c2l = unitary_to_gate_sequence_inner(
+/// Push the interaction ``name`` (one of ``rxx``, ``ryy`` or ``rzz``) with angle ``theta`` onto
+/// ``sequence`` as ``RZZ(theta) = CX (I x RZ(theta)) CX``, changing basis with ``h`` and ``s``
+/// for the ``XX`` and ``YY`` interactions.
+fn push_cxrz_interaction(sequence: &mut TwoQubitSequenceVec, name: &str, theta: f64) {
+    let (pre, post) = zz_basis_change(name);
+    push_1q(sequence, pre);
+    sequence.push(("cx".to_string(), SmallVec::new(), smallvec![0, 1]));
+    sequence.push(("rz".to_string(), smallvec![theta], smallvec![1]));
+    sequence.push(("cx".to_string(), SmallVec::new(), smallvec![0, 1]));
+    push_1q(sequence, post);
+}
+
+/// The single-qubit gates applied to both qubits before and after a ``ZZ`` interaction to turn it
+/// into the interaction ``name`` (one of ``rxx``, ``ryy`` or ``rzz``).
+fn zz_basis_change(name: &str) -> (&'static [&'static str], &'static [&'static str]) {
+    match name {
+        "rxx" => (&["h"], &["h"]),
+        "ryy" => (&["sdg", "h"], &["h", "s"]),
+        _ => (&[], &[]),
+    }
+}
+
+/// Push each of ``gates`` onto ``sequence`` on both qubits.
+fn push_1q(sequence: &mut TwoQubitSequenceVec, gates: &[&str]) {
+    for gate in gates {
+        for qubit in 0..2 {
+            sequence.push((gate.to_string(), SmallVec::new(), smallvec![qubit]));
+        }
+    }
+}
+
+/// Try to diagonalize the complex-symmetric ``m2`` as ``P D P^T`` using the eigenvectors of the
+/// real-symmetric ``m2_real``. Returns ``None`` if the eigenvectors don't diagonalize ``m2``.
+fn diagonalize_m2(
+    m2: ArrayView2<Complex64>,
+    m2_real: ArrayView2<f64>,
+) -> Option<(Array2<Complex64>, Array1<Complex64>)> {
+    let p_inner = m2_real
+        .into_faer()
+        .selfadjoint_eigendecomposition(Lower)
+        .u()
+        .into_ndarray()
+        .mapv(Complex64::from);
+    let d_inner = p_inner.t().dot(&m2).dot(&p_inner).diag().to_owned();
+    let mut diag_d: Array2<Complex64> = Array2::zeros((4, 4));
+    diag_d
+        .diag_mut()
+        .iter_mut()
+        .enumerate()
+        .for_each(|(index, x)| *x = d_inner[index]);
+
+    let compare = p_inner.dot(&diag_d).dot(&p_inner.t());
+    if abs_diff_eq!(compare.view(), m2, epsilon = 1.0e-13) {
+        Some((p_inner, d_inner))
+    } else {
+        None
+    }
+}
+
+static IPZ: [[Complex64; 2]; 2] = [
+    [C1_IM, Complex64::new(0., 0.)],
+    [Complex64::new(0., 0.), Complex64::new(0., -1.)],
+];
+static IPY: [[Complex64; 2]; 2] = [
+    [Complex64::new(0., 0.), Complex64::new(1., 0.)],
+    [Complex64::new(-1., 0.), Complex64::new(0., 0.)],
+];
+static IPX: [[Complex64; 2]; 2] = [
+    [Complex64::new(0., 0.), C1_IM],
+    [C1_IM, Complex64::new(0., 0.)],
+];
+
+#[pymethods]
+impl TwoQubitWeylDecomposition {
+    #[staticmethod]
+    #[pyo3(signature = (angles, matrices, specialization, default_euler_basis, calculated_fidelity, requested_fidelity, diagonalization_iterations=1))]
+    fn _from_state(
+        angles: [f64; 4],
+        matrices: [PyReadonlyArray2<Complex64>; 5],
+        specialization: Specialization,
+        default_euler_basis: EulerBasis,
+        calculated_fidelity: f64,
+        requested_fidelity: Option<f64>,
+        diagonalization_iterations: usize,
+    ) -> Self {
+        let [a, b, c, global_phase] = angles;
+        Self {
+            a,
+            b,
+            c,
+            global_phase,
+            K1l: matrices[0].as_array().to_owned(),
+            K1r: matrices[1].as_array().to_owned(),
+            K2l: matrices[2].as_array().to_owned(),
+            K2r: matrices[3].as_array().to_owned(),
+            specialization,
+            default_euler_basis,
+            calculated_fidelity,
+            requested_fidelity,
+            diagonalization_iterations,
+            unitary_matrix: matrices[4].as_array().to_owned(),
+        }
+    }
+
+    fn __reduce__(&self, py: Python) -> PyResult<Py<PyAny>> {
+        Ok((
+            py.get_type_bound::<Self>().getattr("_from_state")?,
+            (
+                [self.a, self.b, self.c, self.global_phase],
+                [
+                    self.K1l.to_pyarray_bound(py),
+                    self.K1r.to_pyarray_bound(py),
+                    self.K2l.to_pyarray_bound(py),
+                    self.K2r.to_pyarray_bound(py),
+                    self.unitary_matrix.to_pyarray_bound(py),
+                ],
+                self.specialization,
+                self.default_euler_basis,
+                self.calculated_fidelity,
+                self.requested_fidelity,
+                self.diagonalization_iterations,
+            ),
+        )
+            .into_py(py))
+    }
+
+    #[new]
+    #[pyo3(signature=(unitary_matrix, fidelity=DEFAULT_FIDELITY, _specialization=None, *, input_endianness=Endianness::Little, max_diagonalization_iterations=DEFAULT_MAX_DIAGONALIZATION_ITERATIONS))]
+    fn new(
+        unitary_matrix: PyReadonlyArray2<Complex64>,
+        fidelity: Option<f64>,
+        _specialization: Option<Specialization>,
+        input_endianness: Endianness,
+        max_diagonalization_iterations: usize,
+    ) -> PyResult<Self> {
+        let unitary_matrix = input_endianness.to_little_endian(unitary_matrix.as_array());
+        TwoQubitWeylDecomposition::new_inner_with_max_iterations(
+            unitary_matrix.view(),
+            fidelity,
+            _specialization,
+            max_diagonalization_iterations,
+        )
+    }
+
+    /// Return the :class:`.Specialization` that would be chosen for ``unitary_matrix`` at the
+    /// given ``fidelity``, without building the decomposition itself.
+    #[staticmethod]
+    #[pyo3(signature=(unitary_matrix, fidelity=DEFAULT_FIDELITY))]
+    fn classify(
+        unitary_matrix: PyReadonlyArray2<Complex64>,
+        fidelity: Option<f64>,
+    ) -> PyResult<Specialization> {
+        let unitary_matrix = unitary_matrix.as_array();
+        check_two_qubit_shape("TwoQubitWeylDecomposition.classify", unitary_matrix)?;
+        let [a, b, c] = __weyl_coordinates(unitary_matrix.into_faer_complex());
+        Ok(select_specialization(a, b, c, fidelity))
+    }
+
+    #[allow(non_snake_case)]
+    #[getter]
+    fn K1l(&self, py: Python) -> PyObject {
+        self.K1l.to_pyarray_bound(py).into()
+    }
+
+    #[allow(non_snake_case)]
+    #[getter]
+    fn K1r(&self, py: Python) -> PyObject {
+        self.K1r.to_pyarray_bound(py).into()
+    }
+
+    #[allow(non_snake_case)]
+    #[getter]
+    fn K2l(&self, py: Python) -> PyObject {
+        self.K2l.to_pyarray_bound(py).into()
+    }
+
+    #[allow(non_snake_case)]
+    #[getter]
+    fn K2r(&self, py: Python) -> PyObject {
+        self.K2r.to_pyarray_bound(py).into()
+    }
+
+    #[getter]
+    fn unitary_matrix(&self, py: Python) -> PyObject {
+        self.unitary_matrix.to_pyarray_bound(py).into()
+    }
+
+    /// Whether the interaction part of the decomposition contains a SWAP, which happens for the
+    /// ``MirrorControlledEquiv`` and ``SWAPEquiv`` specializations.
+    #[getter]
+    fn has_swap(&self) -> bool {
+        matches!(
+            self.specialization,
+            Specialization::MirrorControlledEquiv | Specialization::SWAPEquiv
+        )
+    }
+
+    /// Return the OpenQASM 3 gate statements of the canonical (interaction) part of the
+    /// decomposition, acting on the qubits ``q[0]`` and ``q[1]``.
+    ///
+    /// The single-qubit components and the global phase are not included.
+    fn to_canonical_qasm_lines(&self) -> Vec<String> {
+        let mut sequence: TwoQubitSequenceVec = Vec::new();
+        let mut global_phase = 0.;
+        self.weyl_gate(
+            &WeylCircuitOptions::default(),
+            &mut sequence,
+            &mut global_phase,
+        );
+        sequence
+            .iter()
+            .map(|(name, params, qubits)| {
+                let qubits = qubits
+                    .iter()
+                    .map(|qubit| format!("q[{qubit}]"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if params.is_empty() {
+                    format!("{name} {qubits};")
+                } else {
+                    // Avoid emitting `-0` for the interactions with a zero coordinate.
+                    let params = params
+                        .iter()
+                        .map(|param| (param + 0.).to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("{name}({params}) {qubits};")
+                }
+            })
+            .collect()
+    }
+
+    /// Return the single-qubit components ``K1l``, ``K1r``, ``K2l`` and ``K2r`` (in that order)
+    /// as ``[theta, phi, lambda, phase]`` Euler angles in the given ``basis``.
+    fn euler_angles(&self, basis: EulerBasis) -> [[f64; 4]; 4] {
+        [
+            angles_from_unitary(self.K1l.view(), basis),
+            angles_from_unitary(self.K1r.view(), basis),
+            angles_from_unitary(self.K2l.view(), basis),
+            angles_from_unitary(self.K2r.view(), basis),
+        ]
+    }
+
+    /// Return the decomposition as a gate sequence, with the options given by the attributes of
+    /// ``options``, a :class:`.WeylCircuitOptions`.
+    ///
+    /// With ``simplify``, single-qubit gates are simplified to within ``atol`` and interactions
+    /// whose coordinate is at most ``drop_atol`` in magnitude are left out. ``drop_atol``
+    /// defaults to ``atol``; setting it apart from ``atol`` keeps the number of interactions
+    /// stable for targets whose coordinates are close to zero without changing the single-qubit
+    /// simplification. ``basis`` selects the gates of the interaction part.
+    ///
+    /// With ``absorb_swap``, the SWAP of the ``MirrorControlledEquiv`` and ``SWAPEquiv``
+    /// specializations is left out and the final single-qubit gates are applied to the exchanged
+    /// qubits instead, so the sequence followed by a SWAP equals the one without
+    /// ``absorb_swap``. :attr:`has_swap` tells whether there is a SWAP to leave out.
+    ///
+    /// With ``high_precision``, the phases of the parts of the sequence are added up with
+    /// compensated summation to validate the global phase of the default summation.
+    #[pyo3(signature = (euler_basis=None, options=None))]
+    fn circuit(
+        &self,
+        euler_basis: Option<PyBackedStr>,
+        options: Option<WeylCircuitOptions>,
+    ) -> PyResult<TwoQubitGateSequence> {
+        let options = options.unwrap_or_default();
+        let WeylCircuitOptions {
+            simplify,
+            atol,
+            absorb_swap,
+            high_precision,
+            ..
+        } = options;
+        let euler_basis: EulerBasis = match euler_basis {
+            Some(basis) => parse_euler_basis(basis.deref())?,
+            None => self.default_euler_basis,
+        };
+        let target_1q_basis_list: Vec<EulerBasis> = vec![euler_basis];
+
+        let mut gate_sequence = Vec::new();
+        let mut global_phase = PhaseSum::new(self.global_phase, high_precision);
+
+        let c2r = unitary_to_gate_sequence_inner(
+            self.K2r.view(),
+            &target_1q_basis_list,
+            0,
+            None,
+            simplify,
+            atol,
+        )
+        .unwrap();
+        for gate in c2r.gates {
+            gate_sequence.push((gate.0, gate.1, smallvec![0]))
+        }
+        global_phase.add(c2r.global_phase);
+        let c2l = unitary_to_gate_sequence_inner(
             self.K2l.view(),
             &target_1q_basis_list,
             1,
@@ -1112,13 +2780,16 @@ impl TwoQubitWeylDecomposition {
         for gate in c2l.gates {
             gate_sequence.push((gate.0, gate.1, smallvec![1]))
         }
-        global_phase += c2l.global_phase;
-        self.weyl_gate(
-            simplify,
-            &mut gate_sequence,
-            atol.unwrap_or(ANGLE_ZERO_EPSILON),
-            &mut global_phase,
-        );
+        global_phase.add(c2l.global_phase);
+        let mut weyl_phase = 0.;
+        self.weyl_gate(&options, &mut gate_sequence, &mut weyl_phase);
+        global_phase.add(weyl_phase);
+        // Without the SWAP, the states of the two qubits are exchanged at this point.
+        let (qubit_r, qubit_l) = if absorb_swap && self.has_swap() {
+            (1, 0)
+        } else {
+            (0, 1)
+        };
         let c1r = unitary_to_gate_sequence_inner(
             self.K1r.view(),
             &target_1q_basis_list,
@@ -1129,9 +2800,9 @@ impl TwoQubitWeylDecomposition {
         )
         .unwrap();
         for gate in c1r.gates {
-            gate_sequence.push((gate.0, gate.1, smallvec![0]))
+            gate_sequence.push((gate.0, gate.1, smallvec![qubit_r]))
         }
-        global_phase += c2r.global_phase;
+        global_phase.add(c1r.global_phase);
         let c1l = unitary_to_gate_sequence_inner(
             self.K1l.view(),
             &target_1q_basis_list,
@@ -1142,16 +2813,80 @@ impl TwoQubitWeylDecomposition {
         )
         .unwrap();
         for gate in c1l.gates {
-            gate_sequence.push((gate.0, gate.1, smallvec![1]))
+            gate_sequence.push((gate.0, gate.1, smallvec![qubit_l]))
         }
+        global_phase.add(c1l.global_phase);
         Ok(TwoQubitGateSequence {
             gates: gate_sequence,
-            global_phase,
+            global_phase: global_phase.value(),
         })
     }
 }
 
-type TwoQubitSequenceVec = Vec<(String, SmallVec<[f64; 3]>, SmallVec<[u8; 2]>)>;
+/// The ``(name, params, qubits)`` gates of a two-qubit sequence. The numerical synthesis works
+/// with float parameters throughout; ``TwoQubitSequenceVec<Param>`` holds them as circuit
+/// parameters instead, so parameterized gates can be represented as well.
+type TwoQubitSequenceVec<P = f64> = Vec<(String, SmallVec<[P; 3]>, SmallVec<[u8; 2]>)>;
+
+/// The kind of a segment of a [TwoQubitGateSequence], as returned by
+/// [TwoQubitGateSequence::segments].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SegmentKind {
+    /// Consecutive single-qubit gates on the given wire.
+    OneQubit(u8),
+    /// A single two-qubit gate.
+    Entangler,
+}
+
+/// The part of a decomposition a gate of a synthesized sequence comes from, as returned by
+/// ``TwoQubitBasisDecomposer.generate_sequence_with_sources``.
+///
+/// ``K2r`` and ``K2l`` are the single-qubit gates on qubits 0 and 1 before the first basis gate,
+/// which realize the target's :math:`K_{2r}` and :math:`K_{2l}` together with the local gates of
+/// the basis decomposition, and ``K1r`` and ``K1l`` are those after the last basis gate. ``Weyl``
+/// gates sit between two basis gates and realize the Weyl coordinates of the target, and
+/// ``Basis`` is a basis gate. Without basis gates, the single-qubit gates are ``K1r`` and ``K1l``.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+#[pyclass(module = "qiskit._accelerate.two_qubit_decompose")]
+enum GateSource {
+    K1l,
+    K1r,
+    K2l,
+    K2r,
+    Weyl,
+    Basis,
+}
+
+/// Label each gate of ``gates``, as emitted by the synthesis with basis gates between layers of
+/// single-qubit gates, with the part of the decomposition it comes from.
+fn gate_sources(gates: &TwoQubitSequenceVec) -> Vec<GateSource> {
+    let num_basis = gates.iter().filter(|gate| gate.2.len() == 2).count();
+    let mut seen_basis = 0;
+    gates
+        .iter()
+        .map(|(_, _, qubits)| match qubits[..] {
+            [_, _] => {
+                seen_basis += 1;
+                GateSource::Basis
+            }
+            [qubit] if seen_basis == num_basis => {
+                if qubit == 0 {
+                    GateSource::K1r
+                } else {
+                    GateSource::K1l
+                }
+            }
+            [qubit] if seen_basis == 0 => {
+                if qubit == 0 {
+                    GateSource::K2r
+                } else {
+                    GateSource::K2l
+                }
+            }
+            _ => GateSource::Weyl,
+        })
+        .collect()
+}
 
 #[pyclass(sequence)]
 pub struct TwoQubitGateSequence {
@@ -1183,6 +2918,35 @@ impl TwoQubitGateSequence {
         Ok(self.gates.len())
     }
 
+    /// Return the sequence implementing the inverse unitary, with the gates in reverse order
+    /// and each gate inverted. An ``iswap`` is inverted by conjugating it with ``z`` gates, so
+    /// the inverse can have more gates than the sequence.
+    ///
+    /// Single-qubit gates that aren't known here are passed to ``inverse_1q``, if given, as
+    /// ``inverse_1q(name, params)``, which must return the ``(name, params)`` of their inverse.
+    #[pyo3(signature = (inverse_1q=None))]
+    fn inverse(&self, inverse_1q: Option<&Bound<PyAny>>) -> PyResult<TwoQubitGateSequence> {
+        let gates = self
+            .gates
+            .iter()
+            .rev()
+            .map(|gate| {
+                if gate.2.len() == 2 {
+                    invert_2q_gate(gate)
+                } else {
+                    Ok(vec![invert_1q_gate(gate, inverse_1q)?])
+                }
+            })
+            .collect::<PyResult<Vec<TwoQubitSequenceVec>>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+        Ok(TwoQubitGateSequence {
+            gates,
+            global_phase: -self.global_phase,
+        })
+    }
+
     fn __getitem__(&self, py: Python, idx: SliceOrInt) -> PyResult<PyObject> {
         match idx {
             SliceOrInt::Slice(slc) => {
@@ -1226,776 +2990,3992 @@ impl TwoQubitGateSequence {
         }
     }
 }
-#[allow(non_snake_case)]
-#[pyclass(module = "qiskit._accelerate.two_qubit_decompose", subclass)]
-pub struct TwoQubitBasisDecomposer {
-    gate: String,
-    basis_fidelity: f64,
-    euler_basis: EulerBasis,
-    pulse_optimize: Option<bool>,
-    basis_decomposer: TwoQubitWeylDecomposition,
-    #[pyo3(get)]
-    super_controlled: bool,
-    u0l: Array2<Complex64>,
-    u0r: Array2<Complex64>,
-    u1l: Array2<Complex64>,
-    u1ra: Array2<Complex64>,
-    u1rb: Array2<Complex64>,
-    u2la: Array2<Complex64>,
-    u2lb: Array2<Complex64>,
-    u2ra: Array2<Complex64>,
-    u2rb: Array2<Complex64>,
-    u3l: Array2<Complex64>,
-    u3r: Array2<Complex64>,
-    q0l: Array2<Complex64>,
-    q0r: Array2<Complex64>,
-    q1la: Array2<Complex64>,
-    q1lb: Array2<Complex64>,
-    q1ra: Array2<Complex64>,
-    q1rb: Array2<Complex64>,
-    q2l: Array2<Complex64>,
-    q2r: Array2<Complex64>,
-}
-impl TwoQubitBasisDecomposer {
-    fn decomp1_inner(
-        &self,
-        target: &TwoQubitWeylDecomposition,
-    ) -> SmallVec<[Array2<Complex64>; 8]> {
-        // FIXME: fix for z!=0 and c!=0 using closest reflection (not always in the Weyl chamber)
-        smallvec![
-            transpose_conjugate(self.basis_decomposer.K2r.view()).dot(&target.K2r),
-            transpose_conjugate(self.basis_decomposer.K2l.view()).dot(&target.K2l),
-            target
-                .K1r
-                .dot(&transpose_conjugate(self.basis_decomposer.K1r.view())),
-            target
-                .K1l
-                .dot(&transpose_conjugate(self.basis_decomposer.K1l.view())),
-        ]
+impl TwoQubitGateSequence {
+    /// The gates of the sequence with their parameters as [Param::Float].
+    pub fn gates_with_params(&self) -> TwoQubitSequenceVec<Param> {
+        self.gates
+            .iter()
+            .map(|(name, params, qubits)| {
+                let params = params.iter().map(|param| Param::Float(*param)).collect();
+                (name.clone(), params, qubits.clone())
+            })
+            .collect()
     }
 
-    fn decomp2_supercontrolled_inner(
+    /// Build a sequence from gates with [Param] parameters, which must all be floats for the
+    /// numerical routines of the sequence.
+    pub fn from_gates_with_params(
+        gates: TwoQubitSequenceVec<Param>,
+        global_phase: f64,
+    ) -> PyResult<Self> {
+        let gates = gates
+            .into_iter()
+            .map(|(name, params, qubits)| -> PyResult<_> {
+                let params = params
+                    .into_iter()
+                    .map(|param| match param {
+                        Param::Float(value) => Ok(value),
+                        _ => Err(QiskitError::new_err(format!(
+                            "TwoQubitGateSequence: gate {} has a non-float parameter",
+                            name
+                        ))),
+                    })
+                    .collect::<PyResult<_>>()?;
+                Ok((name, params, qubits))
+            })
+            .collect::<PyResult<_>>()?;
+        Ok(TwoQubitGateSequence {
+            gates,
+            global_phase,
+        })
+    }
+
+    /// Split the gates into consecutive segments, each either a single two-qubit gate or a
+    /// maximal run of single-qubit gates on one wire, and return the kind and the range of gate
+    /// indices of each segment in order.
+    pub fn segments(&self) -> Vec<(SegmentKind, Range<usize>)> {
+        let mut segments: Vec<(SegmentKind, Range<usize>)> = Vec::new();
+        for (index, (_, _, qubits)) in self.gates.iter().enumerate() {
+            let kind = if qubits.len() == 2 {
+                SegmentKind::Entangler
+            } else {
+                SegmentKind::OneQubit(qubits[0])
+            };
+            match segments.last_mut() {
+                Some((last_kind, range))
+                    if kind != SegmentKind::Entangler && *last_kind == kind =>
+                {
+                    range.end = index + 1
+                }
+                _ => segments.push((kind, index..index + 1)),
+            }
+        }
+        segments
+    }
+
+    /// Move single-qubit gates that commute with an adjacent ``cx`` across it, whenever that
+    /// reduces the number of single-qubit layers between the two-qubit gates. Diagonal gates
+    /// commute through the control and X rotations through the target; rotations that end up
+    /// next to a rotation of the same kind are merged.
+    fn balance_single_qubit(&mut self) {
+        let mut segments: Vec<TwoQubitSequenceVec> = vec![Vec::new()];
+        let mut two_qubit_gates: TwoQubitSequenceVec = Vec::new();
+        for gate in self.gates.drain(..) {
+            if gate.2.len() == 2 {
+                two_qubit_gates.push(gate);
+                segments.push(Vec::new());
+            } else {
+                segments.last_mut().unwrap().push(gate);
+            }
+        }
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (k, two_qubit_gate) in two_qubit_gates.iter().enumerate() {
+                if two_qubit_gate.0 != "cx" {
+                    continue;
+                }
+                for (wire, is_control) in
+                    [(two_qubit_gate.2[0], true), (two_qubit_gate.2[1], false)]
+                {
+                    for forward in [true, false] {
+                        let (src, dst) = if forward { (k, k + 1) } else { (k + 1, k) };
+                        let src_idx = if forward {
+                            segments[src].iter().rposition(|gate| gate.2[0] == wire)
+                        } else {
+                            segments[src].iter().position(|gate| gate.2[0] == wire)
+                        };
+                        let Some(src_idx) = src_idx else {
+                            continue;
+                        };
+                        if !commutes_through_cx(&segments[src][src_idx].0, is_control) {
+                            continue;
+                        }
+                        let before =
+                            one_qubit_depth(&segments[src]) + one_qubit_depth(&segments[dst]);
+                        let mut new_src = segments[src].clone();
+                        let mut new_dst = segments[dst].clone();
+                        let gate = new_src.remove(src_idx);
+                        insert_merged(&mut new_dst, gate, forward);
+                        if one_qubit_depth(&new_src) + one_qubit_depth(&new_dst) < before {
+                            segments[src] = new_src;
+                            segments[dst] = new_dst;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+        let mut segments = segments.into_iter();
+        self.gates.extend(segments.next().unwrap());
+        for (two_qubit_gate, segment) in two_qubit_gates.into_iter().zip(segments) {
+            self.gates.push(two_qubit_gate);
+            self.gates.extend(segment);
+        }
+    }
+
+    /// Reorder the single-qubit gates between consecutive two-qubit gates so that gates which
+    /// can run in parallel on the two wires are adjacent. The order of the gates on each wire
+    /// is unchanged, so this doesn't change the unitary of the sequence.
+    fn schedule(&mut self) {
+        self.interleave_wires(false);
+    }
+
+    /// Like :meth:`schedule`, but also fill the idle positions of the shorter wire between
+    /// consecutive two-qubit gates with ``id`` gates, so both wires have a gate in every layer.
+    fn pad_identities(&mut self) {
+        self.interleave_wires(true);
+    }
+
+    fn interleave_wires(&mut self, pad: bool) {
+        let mut gates = Vec::with_capacity(self.gates.len());
+        let mut wires: [TwoQubitSequenceVec; 2] = [Vec::new(), Vec::new()];
+        let flush = |wires: &mut [TwoQubitSequenceVec; 2], gates: &mut TwoQubitSequenceVec| {
+            let [first, second] = wires;
+            let depth = first.len().max(second.len());
+            let mut first = first.drain(..);
+            let mut second = second.drain(..);
+            let identity = |qubit: u8| ("id".to_string(), smallvec![], smallvec![qubit]);
+            for _ in 0..depth {
+                if pad {
+                    gates.push(first.next().unwrap_or_else(|| identity(0)));
+                    gates.push(second.next().unwrap_or_else(|| identity(1)));
+                } else {
+                    gates.extend(first.next().into_iter().chain(second.next()));
+                }
+            }
+        };
+        for gate in self.gates.drain(..) {
+            if gate.2.len() == 2 {
+                flush(&mut wires, &mut gates);
+                gates.push(gate);
+            } else {
+                wires[gate.2[0] as usize].push(gate);
+            }
+        }
+        flush(&mut wires, &mut gates);
+        self.gates = gates;
+    }
+
+    /// Move the global phase of the sequence into single-qubit gates on the first wire, leaving
+    /// a global phase of zero. The gates are taken from the first of the Euler ``bases`` that can
+    /// realize the phase, see [global_phase_gates], and a :class:`.QiskitError` is raised if none
+    /// of them can.
+    fn absorb_global_phase(&mut self, bases: &[EulerBasis]) -> PyResult<()> {
+        let phase = (self.global_phase + PI).rem_euclid(TWO_PI) - PI;
+        if phase.abs() < ANGLE_ZERO_EPSILON {
+            self.global_phase = 0.;
+            return Ok(());
+        }
+        let Some(gates) = bases
+            .iter()
+            .find_map(|basis| global_phase_gates(*basis, phase))
+        else {
+            let names: Vec<&str> = bases.iter().map(|basis| basis.as_str()).collect();
+            return Err(QiskitError::new_err(format!(
+                "TwoQubitGateSequence: a global phase of {phase} can't be realized by the gates \
+                 of the Euler bases {}",
+                names.join(", ")
+            )));
+        };
+        self.global_phase = 0.;
+        self.gates.extend(
+            gates
+                .into_iter()
+                .map(|(name, params)| (name.to_string(), params, smallvec![0])),
+        );
+        Ok(())
+    }
+
+    /// The duration of the critical path of the sequence, given the duration of each gate by
+    /// name. Gates on different wires run in parallel, and a two-qubit gate starts once both of
+    /// its wires are free.
+    fn critical_path_duration(&self, durations: &HashMap<String, f64>) -> PyResult<f64> {
+        let mut wires = [0.; 2];
+        for (name, _, qubits) in &self.gates {
+            let duration = durations.get(name).ok_or_else(|| {
+                QiskitError::new_err(format!("No duration given for gate {name}"))
+            })?;
+            let start = qubits
+                .iter()
+                .map(|qubit| wires[*qubit as usize])
+                .fold(0., f64::max);
+            for qubit in qubits {
+                wires[*qubit as usize] = start + duration;
+            }
+        }
+        Ok(wires[0].max(wires[1]))
+    }
+
+    /// The depth of the sequence when every gate takes one time step and is scheduled as soon
+    /// as possible.
+    fn depth(&self) -> usize {
+        let mut wires = [0; 2];
+        for (_, _, qubits) in &self.gates {
+            let start = qubits
+                .iter()
+                .map(|qubit| wires[*qubit as usize])
+                .max()
+                .unwrap_or(0);
+            for qubit in qubits {
+                wires[*qubit as usize] = start + 1;
+            }
+        }
+        wires[0].max(wires[1])
+    }
+
+    /// Exchange the two wires of the sequence.
+    fn swap_wires(&mut self) {
+        for (_, _, qubits) in self.gates.iter_mut() {
+            for qubit in qubits.iter_mut() {
+                *qubit = 1 - *qubit;
+            }
+        }
+    }
+
+    /// Replace every single-qubit gate angle that is within ``atol`` of an angle already used
+    /// earlier in the sequence by that angle, so that the sequence uses fewer distinct angles.
+    /// This changes the unitary of the sequence by an amount controlled by ``atol``.
+    fn snap_angles(&mut self, atol: f64) {
+        let mut angles: Vec<f64> = Vec::new();
+        for gate in self.gates.iter_mut().filter(|gate| gate.2.len() == 1) {
+            for param in gate.1.iter_mut() {
+                match angles.iter().find(|angle| (**angle - *param).abs() <= atol) {
+                    Some(angle) => *param = *angle,
+                    None => angles.push(*param),
+                }
+            }
+        }
+    }
+
+    /// Cancel adjacent single-qubit gates on the same wire that are inverses of each other, and
+    /// merge adjacent ``rz`` gates into one, dropping it if its angle ends up zero. Two gates are
+    /// adjacent if no other gate acts on their wire in between, so the cancellations can cascade
+    /// but never cross a two-qubit gate. This doesn't change the unitary of the sequence.
+    fn optimize_1q(&mut self) {
+        let mut gates = Vec::with_capacity(self.gates.len());
+        // The indices into `gates` of the remaining single-qubit gates on each wire since the
+        // last two-qubit gate.
+        let mut wires: [Vec<usize>; 2] = [Vec::new(), Vec::new()];
+        for gate in self.gates.drain(..) {
+            if gate.2.len() == 2 {
+                wires = [Vec::new(), Vec::new()];
+                gates.push(Some(gate));
+                continue;
+            }
+            let wire = &mut wires[gate.2[0] as usize];
+            if let Some(&last) = wire.last() {
+                let previous = gates[last].as_mut().unwrap();
+                if previous.0 == "rz" && gate.0 == "rz" {
+                    previous.1[0] += gate.1[0];
+                    if previous.1[0].abs() < ANGLE_ZERO_EPSILON {
+                        gates[last] = None;
+                        wire.pop();
+                    }
+                    continue;
+                }
+                if are_inverse_1q_gates(&previous.0, &gate.0) {
+                    gates[last] = None;
+                    wire.pop();
+                    continue;
+                }
+            }
+            wire.push(gates.len());
+            gates.push(Some(gate));
+        }
+        self.gates = gates.into_iter().flatten().collect();
+    }
+}
+
+/// Whether the fixed single-qubit gates ``first`` and ``second`` multiply to the identity.
+fn are_inverse_1q_gates(first: &str, second: &str) -> bool {
+    matches!(
+        (first, second),
+        ("x", "x")
+            | ("y", "y")
+            | ("z", "z")
+            | ("h", "h")
+            | ("s", "sdg")
+            | ("sdg", "s")
+            | ("t", "tdg")
+            | ("tdg", "t")
+            | ("sx", "sxdg")
+            | ("sxdg", "sx")
+    )
+}
+
+/// Single-qubit gates of the Euler ``basis`` whose product is :math:`e^{i\phi} I` for the
+/// ``phase`` :math:`\phi`, if there are any.
+///
+/// The bases with a phase gate use :math:`X P(\phi) X P(\phi) = e^{i\phi} I`, with :math:`X`
+/// built from the basis gates, and ``U`` and ``U3`` use two :math:`\pi` rotations whose phases
+/// multiply to :math:`e^{i\phi}`. The other bases only have rotations, which are special
+/// unitary up to a fixed phase, so only a phase that is a multiple of :math:`\pi` is realized,
+/// as a rotation by :math:`2\pi`.
+fn global_phase_gates(
+    basis: EulerBasis,
+    phase: f64,
+) -> Option<Vec<(&'static str, SmallVec<[f64; 3]>)>> {
+    let gates = match basis {
+        EulerBasis::U | EulerBasis::U3 | EulerBasis::U321 => {
+            let name = if matches!(basis, EulerBasis::U) {
+                "u"
+            } else {
+                "u3"
+            };
+            vec![
+                (name, smallvec![PI, 0., 0.]),
+                (name, smallvec![PI, PI + phase, PI + phase]),
+            ]
+        }
+        EulerBasis::PSX => vec![
+            ("p", smallvec![phase]),
+            ("sx", smallvec![]),
+            ("sx", smallvec![]),
+            ("p", smallvec![phase]),
+            ("sx", smallvec![]),
+            ("sx", smallvec![]),
+        ],
+        // `rx(pi) = -i X`, so the two of them contribute a phase of pi.
+        EulerBasis::U1X => vec![
+            ("u1", smallvec![phase + PI]),
+            ("rx", smallvec![PI]),
+            ("u1", smallvec![phase + PI]),
+            ("rx", smallvec![PI]),
+        ],
+        _ => {
+            if (phase.abs() - PI).abs() > ANGLE_ZERO_EPSILON {
+                return None;
+            }
+            match basis {
+                EulerBasis::XYX | EulerBasis::XZX => vec![("rx", smallvec![TWO_PI])],
+                EulerBasis::RR => vec![("r", smallvec![TWO_PI, 0.])],
+                _ => vec![("rz", smallvec![TWO_PI])],
+            }
+        }
+    };
+    Some(gates)
+}
+
+/// The matrix of a single-qubit gate of a :class:`.TwoQubitGateSequence`.
+fn one_qubit_gate_matrix(name: &str, params: &[f64]) -> PyResult<[[Complex64; 2]; 2]> {
+    Ok(match name {
+        "id" => ONE_QUBIT_IDENTITY,
+        "x" => X_GATE,
+        "y" => Y_GATE,
+        "z" => Z_GATE,
+        "h" => H_GATE,
+        "s" => S_GATE,
+        "sdg" => SDG_GATE,
+        "t" => T_GATE,
+        "tdg" => TDG_GATE,
+        "sx" => SX_GATE,
+        "sxdg" => SXDG_GATE,
+        "rx" => rx_gate(params[0]),
+        "ry" => ry_gate(params[0]),
+        "rz" => rz_gate(params[0]),
+        "p" => phase_gate(params[0]),
+        "u1" => u1_gate(params[0]),
+        "r" => r_gate(params[0], params[1]),
+        "u2" => u2_gate(params[0], params[1]),
+        "u" => u_gate(params[0], params[1], params[2]),
+        "u3" => u3_gate(params[0], params[1], params[2]),
+        _ => {
+            return Err(QiskitError::new_err(format!(
+                "Unknown single-qubit gate {name}"
+            )))
+        }
+    })
+}
+
+/// The matrix of a two-qubit gate of a :class:`.TwoQubitGateSequence` on the qubits ``[0, 1]``.
+fn two_qubit_gate_matrix(name: &str, params: &[f64]) -> PyResult<[[Complex64; 4]; 4]> {
+    let zero = Complex64::new(0., 0.);
+    let one = Complex64::new(1., 0.);
+    let rotation = |theta: f64| {
+        let (sin, cos) = (theta / 2.).sin_cos();
+        (Complex64::new(cos, 0.), Complex64::new(0., -sin))
+    };
+    Ok(match name {
+        "cx" => CX_GATE,
+        "cy" => CY_GATE,
+        "cz" => CZ_GATE,
+        "swap" => SWAP_GATE,
+        "iswap" => ISWAP_GATE,
+        "ecr" => ECR_GATE,
+        "dcx" => DCX_GATE,
+        "rxx" => {
+            let (cos, isin) = rotation(params[0]);
+            [
+                [cos, zero, zero, isin],
+                [zero, cos, isin, zero],
+                [zero, isin, cos, zero],
+                [isin, zero, zero, cos],
+            ]
+        }
+        "ryy" => {
+            let (cos, isin) = rotation(params[0]);
+            [
+                [cos, zero, zero, -isin],
+                [zero, cos, isin, zero],
+                [zero, isin, cos, zero],
+                [-isin, zero, zero, cos],
+            ]
+        }
+        "rzz" => {
+            let phase = Complex64::new(0., params[0] / 2.).exp();
+            [
+                [phase.conj(), zero, zero, zero],
+                [zero, phase, zero, zero],
+                [zero, zero, phase, zero],
+                [zero, zero, zero, phase.conj()],
+            ]
+        }
+        "cp" => {
+            let phase = Complex64::new(0., params[0]).exp();
+            [
+                [one, zero, zero, zero],
+                [zero, one, zero, zero],
+                [zero, zero, one, zero],
+                [zero, zero, zero, phase],
+            ]
+        }
+        "crz" => {
+            let phase = Complex64::new(0., params[0] / 2.).exp();
+            [
+                [one, zero, zero, zero],
+                [zero, phase.conj(), zero, zero],
+                [zero, zero, one, zero],
+                [zero, zero, zero, phase],
+            ]
+        }
+        _ => {
+            return Err(QiskitError::new_err(format!(
+                "Unknown two-qubit gate {name}"
+            )))
+        }
+    })
+}
+
+/// Choose the placement of the basis gates with the smallest depth among ``sequence`` and the
+/// equivalent sequences obtained by synthesizing the target with its qubits swapped, its
+/// adjoint, or both, and undoing the transformation on the result. The target and its swap are
+/// also synthesized with ``pulse_synthesize``, if it gives a sequence.
+///
+/// A candidate is only used if it is made of the same gates as ``sequence``, so inverting the
+/// adjoint must not introduce gates such as ``sxdg``, and, unless the basis gate is
+/// ``symmetric`` under swapping its qubits, if it applies the basis gate in the same
+/// directions as ``sequence``.
+fn depth_optimal_placement(
+    unitary: &Array2<Complex64>,
+    sequence: TwoQubitGateSequence,
+    symmetric: bool,
+    synthesize: impl Fn(&Array2<Complex64>) -> PyResult<TwoQubitGateSequence>,
+    pulse_synthesize: impl Fn(&Array2<Complex64>) -> PyResult<Option<TwoQubitGateSequence>>,
+) -> PyResult<TwoQubitGateSequence> {
+    let directions = |sequence: &TwoQubitGateSequence| -> Vec<SmallVec<[u8; 2]>> {
+        sequence
+            .gates
+            .iter()
+            .filter(|gate| gate.2.len() == 2)
+            .map(|gate| gate.2.clone())
+            .collect()
+    };
+    let gate_names: HashSet<String> = sequence.gates.iter().map(|gate| gate.0.clone()).collect();
+    let basis_directions = directions(&sequence);
+    let perm = [0, 2, 1, 3];
+    let swapped = Array2::from_shape_fn((4, 4), |(i, j)| unitary[[perm[i], perm[j]]]);
+    let mut best = sequence;
+    for (target, swap) in [(unitary.clone(), false), (swapped, true)] {
+        let adjoint = target.t().mapv(|x| x.conj());
+        let mut candidates = vec![synthesize(&target)?];
+        if let Ok(inverted) = synthesize(&adjoint)?.inverse(None) {
+            candidates.push(inverted);
+        }
+        candidates.extend(pulse_synthesize(&target)?);
+        for mut candidate in candidates {
+            if swap {
+                candidate.swap_wires();
+            }
+            if !candidate
+                .gates
+                .iter()
+                .all(|gate| gate_names.contains(&gate.0))
+                || (!symmetric && directions(&candidate) != basis_directions)
+            {
+                continue;
+            }
+            if candidate.depth() < best.depth() {
+                best = candidate;
+            }
+        }
+    }
+    Ok(best)
+}
+
+/// Whether the 2-qubit ``matrix`` is unchanged, up to a global phase, by swapping its qubits.
+fn is_swap_symmetric(matrix: ArrayView2<Complex64>) -> bool {
+    let perm = [0, 2, 1, 3];
+    let swapped = Array2::from_shape_fn((4, 4), |(i, j)| matrix[[perm[i], perm[j]]]);
+    let overlap: Complex64 = transpose_conjugate(swapped.view())
+        .dot(&matrix)
+        .diag()
+        .sum();
+    abs_diff_eq!(overlap.norm(), 4., epsilon = 1e-9)
+}
+
+/// Invert a single-qubit gate of a :class:`.TwoQubitGateSequence`, falling back to the Python
+/// callable ``inverse_1q`` for gates that aren't known here.
+fn invert_1q_gate(
+    gate: &(String, SmallVec<[f64; 3]>, SmallVec<[u8; 2]>),
+    inverse_1q: Option<&Bound<PyAny>>,
+) -> PyResult<(String, SmallVec<[f64; 3]>, SmallVec<[u8; 2]>)> {
+    let (name, params, qubits) = gate;
+    let (inv_name, inv_params): (&str, SmallVec<[f64; 3]>) = match name.as_str() {
+        "id" | "x" | "y" | "z" | "h" => (name.as_str(), smallvec![]),
+        "s" => ("sdg", smallvec![]),
+        "sdg" => ("s", smallvec![]),
+        "t" => ("tdg", smallvec![]),
+        "tdg" => ("t", smallvec![]),
+        "sx" => ("sxdg", smallvec![]),
+        "sxdg" => ("sx", smallvec![]),
+        "rx" | "ry" | "rz" | "p" | "u1" => (name.as_str(), smallvec![-params[0]]),
+        "r" => ("r", smallvec![-params[0], params[1]]),
+        "u" | "u3" => (name.as_str(), smallvec![-params[0], -params[2], -params[1]]),
+        "u2" => ("u3", smallvec![-PI2, -params[1], -params[0]]),
+        _ => {
+            let Some(inverse_1q) = inverse_1q else {
+                return Err(QiskitError::new_err(format!(
+                    "Unable to invert single-qubit gate {name}"
+                )));
+            };
+            let (inv_name, inv_params): (String, Vec<f64>) = inverse_1q
+                .call1((name.as_str(), params.to_vec()))?
+                .extract()?;
+            return Ok((inv_name, SmallVec::from_vec(inv_params), qubits.clone()));
+        }
+    };
+    Ok((inv_name.to_string(), inv_params, qubits.clone()))
+}
+
+/// Invert a two-qubit gate of a :class:`.TwoQubitGateSequence`, returning the gates of its
+/// inverse in circuit order.
+///
+/// ``iswap`` has no inverse among the standard gates, so it's inverted as
+/// :math:`(Z \otimes I) \cdot iSWAP \cdot (Z \otimes I)`, which flips the sign of its
+/// :math:`XX + YY` interaction. ``dcx`` is inverted by reversing its qubits.
+fn invert_2q_gate(
+    gate: &(String, SmallVec<[f64; 3]>, SmallVec<[u8; 2]>),
+) -> PyResult<TwoQubitSequenceVec> {
+    let (name, params, qubits) = gate;
+    Ok(match name.as_str() {
+        "cx" | "cy" | "cz" | "swap" | "ecr" => vec![gate.clone()],
+        "rxx" | "ryy" | "rzz" | "rzx" | "cp" | "crz" => {
+            vec![(name.clone(), smallvec![-params[0]], qubits.clone())]
+        }
+        "dcx" => vec![(name.clone(), smallvec![], smallvec![qubits[1], qubits[0]])],
+        "iswap" => {
+            let z = ("z".to_string(), smallvec![], smallvec![qubits[0]]);
+            vec![z.clone(), gate.clone(), z]
+        }
+        _ => {
+            return Err(QiskitError::new_err(format!(
+                "Unable to invert two-qubit gate {name}"
+            )))
+        }
+    })
+}
+
+/// The number of single-qubit layers needed for a run of single-qubit gates on two wires.
+fn one_qubit_depth(segment: &TwoQubitSequenceVec) -> usize {
+    let on_first = segment.iter().filter(|gate| gate.2[0] == 0).count();
+    on_first.max(segment.len() - on_first)
+}
+
+fn commutes_through_cx(name: &str, is_control: bool) -> bool {
+    if is_control {
+        matches!(name, "rz" | "p" | "u1")
+    } else {
+        matches!(name, "rx" | "sx" | "x")
+    }
+}
+
+/// Insert ``gate`` at the start (if ``front``) or end of ``segment``, merging it into the
+/// neighbouring gate on the same wire if both are rotations of the same kind.
+fn insert_merged(
+    segment: &mut TwoQubitSequenceVec,
+    gate: (String, SmallVec<[f64; 3]>, SmallVec<[u8; 2]>),
+    front: bool,
+) {
+    let wire = gate.2[0];
+    let neighbour = if front {
+        segment.iter().position(|other| other.2[0] == wire)
+    } else {
+        segment.iter().rposition(|other| other.2[0] == wire)
+    };
+    if let Some(idx) = neighbour {
+        let other = &mut segment[idx];
+        if other.0 == gate.0 && matches!(gate.0.as_str(), "rz" | "p" | "u1" | "rx") {
+            other.1[0] += gate.1[0];
+            return;
+        }
+    }
+    if front {
+        segment.insert(0, gate);
+    } else {
+        segment.push(gate);
+    }
+}
+
+/// The fixed single-qubit matrices :math:`K_{ij}` of the decompositions with a supercontrolled
+/// basis gate :math:`\sim U_d(\pi/4, b, 0)`. The decompositions with ``i`` uses of the basis gate
+/// are built from :math:`U_i = K_{i1} \cdot U_\text{basis} \cdot K_{i2}`, with each side split
+/// into its qubit 0 (``r``) and qubit 1 (``l``) parts, and :math:`K_{32l} \cdot K_{21l}` only
+/// appearing as a product.
+struct SupercontrolledBasisMatrices {
+    k11l: Array2<Complex64>,
+    k11r: Array2<Complex64>,
+    k12l: Array2<Complex64>,
+    k12r: Array2<Complex64>,
+    k21r: Array2<Complex64>,
+    k22l: Array2<Complex64>,
+    k22r: Array2<Complex64>,
+    k31l: Array2<Complex64>,
+    k31r: Array2<Complex64>,
+    k32l_k21l: Array2<Complex64>,
+    k32r: Array2<Complex64>,
+}
+
+impl SupercontrolledBasisMatrices {
+    fn new(b: f64) -> Self {
+        let temp = Complex64::new(0.5, -0.5);
+        let k11l = array![
+            [
+                temp * (Complex64::new(0., -1.) * Complex64::new(0., -b).exp()),
+                temp * Complex64::new(0., -b).exp()
+            ],
+            [
+                temp * (Complex64::new(0., -1.) * Complex64::new(0., b).exp()),
+                temp * -(Complex64::new(0., b).exp())
+            ],
+        ];
+        let k11r = array![
+            [
+                FRAC_1_SQRT_2 * (Complex64::new(0., 1.) * Complex64::new(0., -b).exp()),
+                FRAC_1_SQRT_2 * -Complex64::new(0., -b).exp()
+            ],
+            [
+                FRAC_1_SQRT_2 * Complex64::new(0., b).exp(),
+                FRAC_1_SQRT_2 * (Complex64::new(0., -1.) * Complex64::new(0., b).exp())
+            ],
+        ];
+        let k12l = aview2(&K12L_ARR).to_owned();
+        let k12r = aview2(&K12R_ARR).to_owned();
+        let k32l_k21l = array![
+            [
+                FRAC_1_SQRT_2 * Complex64::new(1., (2. * b).cos()),
+                FRAC_1_SQRT_2 * (Complex64::new(0., 1.) * (2. * b).sin())
+            ],
+            [
+                FRAC_1_SQRT_2 * (Complex64::new(0., 1.) * (2. * b).sin()),
+                FRAC_1_SQRT_2 * Complex64::new(1., -(2. * b).cos())
+            ],
+        ];
+        let temp = Complex64::new(0.5, 0.5);
+        let k21r = array![
+            [
+                temp * (Complex64::new(0., -1.) * Complex64::new(0., -2. * b).exp()),
+                temp * Complex64::new(0., -2. * b).exp()
+            ],
+            [
+                temp * (Complex64::new(0., 1.) * Complex64::new(0., 2. * b).exp()),
+                temp * Complex64::new(0., 2. * b).exp()
+            ],
+        ];
+        const K22L_ARR: [[Complex64; 2]; 2] = [
+            [
+                Complex64::new(FRAC_1_SQRT_2, 0.),
+                Complex64::new(-FRAC_1_SQRT_2, 0.),
+            ],
+            [
+                Complex64::new(FRAC_1_SQRT_2, 0.),
+                Complex64::new(FRAC_1_SQRT_2, 0.),
+            ],
+        ];
+        let k22l = aview2(&K22L_ARR).to_owned();
+        let k22r_arr: [[Complex64; 2]; 2] = [
+            [Complex64::zero(), Complex64::new(1., 0.)],
+            [Complex64::new(-1., 0.), Complex64::zero()],
+        ];
+        let k22r = aview2(&k22r_arr).to_owned();
+        let k31l = array![
+            [
+                FRAC_1_SQRT_2 * Complex64::new(0., -b).exp(),
+                FRAC_1_SQRT_2 * Complex64::new(0., -b).exp()
+            ],
+            [
+                FRAC_1_SQRT_2 * -Complex64::new(0., b).exp(),
+                FRAC_1_SQRT_2 * Complex64::new(0., b).exp()
+            ],
+        ];
+        let temp = Complex64::new(0., 1.);
+        let k31r = array![
+            [temp * Complex64::new(0., b).exp(), Complex64::zero()],
+            [Complex64::zero(), temp * -Complex64::new(0., -b).exp()],
+        ];
+        let temp = Complex64::new(0.5, 0.5);
+        let k32r = array![
+            [
+                temp * Complex64::new(0., b).exp(),
+                temp * -Complex64::new(0., -b).exp()
+            ],
+            [
+                temp * (Complex64::new(0., -1.) * Complex64::new(0., b).exp()),
+                temp * (Complex64::new(0., -1.) * Complex64::new(0., -b).exp())
+            ],
+        ];
+        SupercontrolledBasisMatrices {
+            k11l,
+            k11r,
+            k12l,
+            k12r,
+            k21r,
+            k22l,
+            k22r,
+            k31l,
+            k31r,
+            k32l_k21l,
+            k32r,
+        }
+    }
+
+    /// The matrices together with their names, in declaration order.
+    fn into_named(self) -> [(&'static str, Array2<Complex64>); 11] {
+        [
+            ("k11l", self.k11l),
+            ("k11r", self.k11r),
+            ("k12l", self.k12l),
+            ("k12r", self.k12r),
+            ("k21r", self.k21r),
+            ("k22l", self.k22l),
+            ("k22r", self.k22r),
+            ("k31l", self.k31l),
+            ("k31r", self.k31r),
+            ("k32l_k21l", self.k32l_k21l),
+            ("k32r", self.k32r),
+        ]
+    }
+}
+
+/// Return the fixed single-qubit matrices used to decompose a target with a supercontrolled
+/// basis gate :math:`\sim U_d(\pi/4, b, 0)`, as a dictionary from their names to
+/// :math:`2 \times 2` arrays.
+///
+/// The decompositions with ``i`` uses of the basis gate are built from
+/// :math:`U_i = K_{i1} \cdot U_\text{basis} \cdot K_{i2}`, where ``k{i}{j}l`` and ``k{i}{j}r``
+/// are the parts of :math:`K_{ij}` acting on qubits 1 and 0. :math:`K_{32l}` and :math:`K_{21l}`
+/// are only given as their product ``k32l_k21l``.
+#[pyfunction]
+#[pyo3(text_signature = "(b, /)")]
+pub fn supercontrolled_basis_matrices(py: Python, b: f64) -> HashMap<&'static str, PyObject> {
+    SupercontrolledBasisMatrices::new(b)
+        .into_named()
+        .into_iter()
+        .map(|(name, matrix)| (name, matrix.into_pyarray_bound(py).into()))
+        .collect()
+}
+
+/// The exact decompositions of a target with two and three uses of the basis gate of a
+/// [TwoQubitBasisDecomposer].
+///
+/// Both methods return the local gates as ``[qubit 0, qubit 1]`` pairs before each basis gate
+/// and after the last one, like ``decomp2_supercontrolled`` and ``decomp3_supercontrolled``.
+/// Implement this for a basis gate with a known better identity than the supercontrolled one
+/// and install it with [TwoQubitBasisDecomposer::with_strategy].
+pub trait BasisDecompStrategy: Send + Sync {
+    fn decomp2(
+        &self,
+        decomposer: &TwoQubitBasisDecomposer,
+        target: &TwoQubitWeylDecomposition,
+    ) -> SmallVec<[Array2<Complex64>; 8]>;
+
+    fn decomp3(
+        &self,
+        decomposer: &TwoQubitBasisDecomposer,
+        target: &TwoQubitWeylDecomposition,
+    ) -> SmallVec<[Array2<Complex64>; 8]>;
+}
+
+/// The default [BasisDecompStrategy], which is exact for a supercontrolled basis gate.
+pub struct SupercontrolledStrategy;
+
+impl BasisDecompStrategy for SupercontrolledStrategy {
+    fn decomp2(
+        &self,
+        decomposer: &TwoQubitBasisDecomposer,
+        target: &TwoQubitWeylDecomposition,
+    ) -> SmallVec<[Array2<Complex64>; 8]> {
+        decomposer.decomp2_supercontrolled_inner(target)
+    }
+
+    fn decomp3(
+        &self,
+        decomposer: &TwoQubitBasisDecomposer,
+        target: &TwoQubitWeylDecomposition,
+    ) -> SmallVec<[Array2<Complex64>; 8]> {
+        decomposer.decomp3_supercontrolled_inner(target)
+    }
+}
+
+/// A decomposer of 2-qubit unitaries into a fixed basis gate and single-qubit gates.
+///
+/// The decomposer is `Send` and `Sync`, and none of the methods outside of its `#[pymethods]`
+/// block, including [TwoQubitBasisDecomposer::call_inner] and the pulse optimal synthesis, need
+/// the GIL. A single decomposer can therefore be shared between threads, e.g. with rayon, to
+/// synthesize many targets in parallel. The `#[pymethods]` only use the GIL to convert their
+/// arguments and results.
+#[allow(non_snake_case)]
+#[pyclass(module = "qiskit._accelerate.two_qubit_decompose", subclass)]
+pub struct TwoQubitBasisDecomposer {
+    gate: String,
+    basis_fidelity: f64,
+    euler_basis: EulerBasis,
+    euler_basis_candidates: Vec<EulerBasis>,
+    pulse_optimize: Option<bool>,
+    basis_decomposer: TwoQubitWeylDecomposition,
+    #[pyo3(get)]
+    super_controlled: bool,
+    u0l: Array2<Complex64>,
+    u0r: Array2<Complex64>,
+    u1l: Array2<Complex64>,
+    u1ra: Array2<Complex64>,
+    u1rb: Array2<Complex64>,
+    u2la: Array2<Complex64>,
+    u2lb: Array2<Complex64>,
+    u2ra: Array2<Complex64>,
+    u2rb: Array2<Complex64>,
+    u3l: Array2<Complex64>,
+    u3r: Array2<Complex64>,
+    q0l: Array2<Complex64>,
+    q0r: Array2<Complex64>,
+    q1la: Array2<Complex64>,
+    q1lb: Array2<Complex64>,
+    q1ra: Array2<Complex64>,
+    q1rb: Array2<Complex64>,
+    q2l: Array2<Complex64>,
+    q2r: Array2<Complex64>,
+    strategy: Box<dyn BasisDecompStrategy>,
+}
+impl TwoQubitBasisDecomposer {
+    /// Build the decomposer for the basis gate ``gate`` with matrix ``gate_matrix``.
+    ///
+    /// Unless ``unchecked`` is set, an error is returned if ``gate_matrix`` is not unitary, as
+    /// the matrices prebuilt from it would otherwise be silently wrong. Callers that already
+    /// validated the matrix can set ``unchecked`` to skip the check.
+    pub fn new_inner(
+        gate: String,
+        gate_matrix: ArrayView2<Complex64>,
+        basis_fidelity: f64,
+        euler_basis: &str,
+        pulse_optimize: Option<bool>,
+        unchecked: bool,
+    ) -> PyResult<Self> {
+        check_two_qubit_shape(
+            &format!("TwoQubitBasisDecomposer: the matrix of basis gate {gate}"),
+            gate_matrix,
+        )?;
+        if !unchecked {
+            let deviation = (transpose_conjugate(gate_matrix).dot(&gate_matrix)
+                - Array2::<Complex64>::eye(4))
+            .iter()
+            .fold(0., |acc: f64, x| acc.max(x.norm()));
+            if deviation > BASIS_UNITARY_ATOL {
+                return Err(QiskitError::new_err(format!(
+                    "TwoQubitBasisDecomposer: the matrix of basis gate {gate} is not unitary, \
+                    max |U^dagger U - I| = {deviation:e}"
+                )));
+            }
+        }
+        let ipz: ArrayView2<Complex64> = aview2(&IPZ);
+        let mut basis_decomposer =
+            TwoQubitWeylDecomposition::new_inner(gate_matrix, Some(DEFAULT_FIDELITY), None)?;
+        // Depending on the convention and rounding of the provided matrix, a basis gate with
+        // `c == 0` can come out of the Weyl decomposition with a tiny negative `c`. The matrices
+        // below assume the canonical `c == 0`, so snap it there; the error this introduces is of
+        // the order of the snapped value. A basis with a larger `|c|` is not supercontrolled.
+        let c_is_zero = abs_diff_eq!(basis_decomposer.c, 0., epsilon = BASIS_C_ATOL);
+        if c_is_zero {
+            basis_decomposer.c = 0.;
+        }
+        let super_controlled =
+            relative_eq!(basis_decomposer.a, PI4, max_relative = 1e-09) && c_is_zero;
+
+        // Create some useful matrices U1, U2, U3 are equivalent to the basis,
+        // expand as Ui = Ki1.Ubasis.Ki2
+        let SupercontrolledBasisMatrices {
+            k11l,
+            k11r,
+            k12l,
+            k12r,
+            k21r,
+            k22l,
+            k22r,
+            k31l,
+            k31r,
+            k32l_k21l,
+            k32r,
+        } = SupercontrolledBasisMatrices::new(basis_decomposer.b);
+        let k1ld = transpose_conjugate(basis_decomposer.K1l.view());
+        let k1rd = transpose_conjugate(basis_decomposer.K1r.view());
+        let k2ld = transpose_conjugate(basis_decomposer.K2l.view());
+        let k2rd = transpose_conjugate(basis_decomposer.K2r.view());
+        // Pre-build the fixed parts of the matrices used in 3-part decomposition
+        let u0l = k31l.dot(&k1ld);
+        let u0r = k31r.dot(&k1rd);
+        let u1l = k2ld.dot(&k32l_k21l).dot(&k1ld);
+        let u1ra = k2rd.dot(&k32r);
+        let u1rb = k21r.dot(&k1rd);
+        let u2la = k2ld.dot(&k22l);
+        let u2lb = k11l.dot(&k1ld);
+        let u2ra = k2rd.dot(&k22r);
+        let u2rb = k11r.dot(&k1rd);
+        let u3l = k2ld.dot(&k12l);
+        let u3r = k2rd.dot(&k12r);
+        // Pre-build the fixed parts of the matrices used in the 2-part decomposition
+        let q0l = transpose_conjugate(k12l.view()).dot(&k1ld);
+        let q0r = transpose_conjugate(k12r.view()).dot(&ipz).dot(&k1rd);
+        let q1la = k2ld.dot(&transpose_conjugate(k11l.view()));
+        let q1lb = k11l.dot(&k1ld);
+        let q1ra = k2rd.dot(&ipz).dot(&transpose_conjugate(k11r.view()));
+        let q1rb = k11r.dot(&k1rd);
+        let q2l = k2ld.dot(&k12l);
+        let q2r = k2rd.dot(&k12r);
+
+        let euler_basis = parse_euler_basis(euler_basis)?;
+        Ok(TwoQubitBasisDecomposer {
+            gate,
+            basis_fidelity,
+            euler_basis,
+            euler_basis_candidates: vec![euler_basis],
+            pulse_optimize,
+            basis_decomposer,
+            super_controlled,
+            u0l,
+            u0r,
+            u1l,
+            u1ra,
+            u1rb,
+            u2la,
+            u2lb,
+            u2ra,
+            u2rb,
+            u3l,
+            u3r,
+            q0l,
+            q0r,
+            q1la,
+            q1lb,
+            q1ra,
+            q1rb,
+            q2l,
+            q2r,
+            strategy: Box::new(SupercontrolledStrategy),
+        })
+    }
+
+    /// Synthesize each single-qubit block in every basis of ``candidates`` and keep the one with
+    /// the fewest gates, instead of only using the Euler basis of the decomposer. Ties are broken
+    /// in favor of the earlier candidate. The pulse optimal synthesis requires ``ZSX`` or
+    /// ``ZSXX`` among the candidates. An empty list keeps only the Euler basis of the decomposer.
+    pub fn with_euler_basis_candidates(mut self, candidates: Vec<EulerBasis>) -> Self {
+        self.euler_basis_candidates = if candidates.is_empty() {
+            vec![self.euler_basis]
+        } else {
+            candidates
+        };
+        self
+    }
+
+    /// Only synthesize single-qubit blocks in the ``ZSX`` and ``ZSXX`` bases among the Euler
+    /// bases of the decomposer, so that the sequences contain only ``rz``, ``sx`` and ``x``
+    /// besides the basis gate. In particular, the residual ``rx`` rotations of the pulse optimal
+    /// synthesis are then never emitted in another basis, whether or not ``pulse_optimize`` is
+    /// set. Fails if neither basis is among the Euler bases.
+    pub fn with_fixed_angle_sx(mut self) -> PyResult<Self> {
+        self.euler_basis_candidates
+            .retain(|basis| matches!(basis, EulerBasis::ZSX | EulerBasis::ZSXX));
+        match self.euler_basis_candidates.first() {
+            Some(basis) => {
+                self.euler_basis = *basis;
+                Ok(self)
+            }
+            None => Err(QiskitError::new_err(
+                "TwoQubitBasisDecomposer: fixed_angle_sx requires the ZSX or ZSXX Euler basis",
+            )),
+        }
+    }
+
+    /// Whether the pulse optimal synthesis can use the single-qubit bases of the decomposer.
+    fn has_pulse_optimal_basis(&self) -> bool {
+        self.euler_basis_candidates
+            .iter()
+            .any(|basis| matches!(basis, EulerBasis::ZSX | EulerBasis::ZSXX))
+    }
+
+    /// Use ``strategy`` for the decompositions with two and three uses of the basis gate instead
+    /// of the supercontrolled one. The strategy is not preserved when pickling from Python.
+    pub fn with_strategy(mut self, strategy: Box<dyn BasisDecompStrategy>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    fn decomp1_inner(
+        &self,
+        target: &TwoQubitWeylDecomposition,
+    ) -> SmallVec<[Array2<Complex64>; 8]> {
+        // FIXME: fix for z!=0 and c!=0 using closest reflection (not always in the Weyl chamber)
+        smallvec![
+            transpose_conjugate(self.basis_decomposer.K2r.view()).dot(&target.K2r),
+            transpose_conjugate(self.basis_decomposer.K2l.view()).dot(&target.K2l),
+            target
+                .K1r
+                .dot(&transpose_conjugate(self.basis_decomposer.K1r.view())),
+            target
+                .K1l
+                .dot(&transpose_conjugate(self.basis_decomposer.K1l.view())),
+        ]
+    }
+
+    /// Raise a :class:`.QiskitError` naming ``method`` if the basis gate isn't supercontrolled,
+    /// as the supercontrolled decompositions are meaningless otherwise.
+    fn check_super_controlled(&self, method: &str) -> PyResult<()> {
+        if self.super_controlled {
+            return Ok(());
+        }
+        let [a, b, c] = [
+            self.basis_decomposer.a,
+            self.basis_decomposer.b,
+            self.basis_decomposer.c,
+        ];
+        Err(QiskitError::new_err(format!(
+            "TwoQubitBasisDecomposer.{method}: the basis gate ~ Ud({a}, {b}, {c}) is not \
+            supercontrolled, expected ~ Ud(pi/4, b, 0)"
+        )))
+    }
+
+    fn decomp2_supercontrolled_inner(
+        &self,
+        target: &TwoQubitWeylDecomposition,
+    ) -> SmallVec<[Array2<Complex64>; 8]> {
+        smallvec![
+            self.q2r.dot(&target.K2r),
+            self.q2l.dot(&target.K2l),
+            self.q1ra.dot(&rz_matrix(2. * target.b)).dot(&self.q1rb),
+            self.q1la.dot(&rz_matrix(-2. * target.a)).dot(&self.q1lb),
+            target.K1r.dot(&self.q0r),
+            target.K1l.dot(&self.q0l),
+        ]
+    }
+
+    fn decomp3_supercontrolled_inner(
+        &self,
+        target: &TwoQubitWeylDecomposition,
+    ) -> SmallVec<[Array2<Complex64>; 8]> {
+        smallvec![
+            self.u3r.dot(&target.K2r),
+            self.u3l.dot(&target.K2l),
+            self.u2ra.dot(&rz_matrix(2. * target.b)).dot(&self.u2rb),
+            self.u2la.dot(&rz_matrix(-2. * target.a)).dot(&self.u2lb),
+            self.u1ra.dot(&rz_matrix(-2. * target.c)).dot(&self.u1rb),
+            self.u1l.clone(),
+            target.K1r.dot(&self.u0r),
+            target.K1l.dot(&self.u0l),
+        ]
+    }
+
+    /// Compute the candidate decompositions of ``target`` with 0 to 3 uses of the basis gate,
+    /// optionally in parallel.
+    fn all_decomp_candidates_inner(
+        &self,
+        target: &TwoQubitWeylDecomposition,
+        parallel: bool,
+    ) -> Vec<SmallVec<[Array2<Complex64>; 8]>> {
+        let decomp = |nbasis: u8| match nbasis {
+            0 => decomp0_inner(target),
+            1 => self.decomp1_inner(target),
+            2 => self.strategy.decomp2(self, target),
+            3 => self.strategy.decomp3(self, target),
+            _ => unreachable!("Invalid basis to use"),
+        };
+        if parallel {
+            (0..4u8).into_par_iter().map(decomp).collect()
+        } else {
+            (0..4u8).map(decomp).collect()
+        }
+    }
+
+    /// Decomposition of SU(4) gate for device with SX, virtual RZ, and CNOT gates assuming
+    /// two CNOT gates are needed.
+    ///
+    /// This first decomposes each unitary from the KAK decomposition into ZXZ on the source
+    /// qubit of the CNOTs and XZX on the targets in order to commute operators to beginning and
+    /// end of decomposition. The beginning and ending single qubit gates are then
+    /// collapsed and re-decomposed with the single qubit decomposer. This last step could be avoided
+    /// if performance is a concern.
+    fn get_sx_vz_2cx_efficient_euler(
+        &self,
+        decomposition: &SmallVec<[Array2<Complex64>; 8]>,
+        target_decomposed: &TwoQubitWeylDecomposition,
+    ) -> Option<TwoQubitGateSequence> {
+        let mut gates = Vec::new();
+        let mut global_phase = target_decomposed.global_phase;
+        global_phase -= 2. * self.basis_decomposer.global_phase;
+        let euler_q0: Vec<[f64; 3]> = decomposition
+            .iter()
+            .step_by(2)
+            .map(|decomp| {
+                let euler_angles = angles_from_unitary(decomp.view(), EulerBasis::ZXZ);
+                global_phase += euler_angles[3];
+                [euler_angles[2], euler_angles[0], euler_angles[1]]
+            })
+            .collect();
+        let euler_q1: Vec<[f64; 3]> = decomposition
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .map(|decomp| {
+                let euler_angles = angles_from_unitary(decomp.view(), EulerBasis::XZX);
+                global_phase += euler_angles[3];
+                [euler_angles[2], euler_angles[0], euler_angles[1]]
+            })
+            .collect();
+        let mut euler_matrix_q0 = rx_matrix(euler_q0[0][1]).dot(&rz_matrix(euler_q0[0][0]));
+        euler_matrix_q0 = rz_matrix(euler_q0[0][2] + euler_q0[1][0] + PI2).dot(&euler_matrix_q0);
+        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q0.view(), 0);
+        let mut euler_matrix_q1 = rz_matrix(euler_q1[0][1]).dot(&rx_matrix(euler_q1[0][0]));
+        euler_matrix_q1 = rx_matrix(euler_q1[0][2] + euler_q1[1][0]).dot(&euler_matrix_q1);
+        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q1.view(), 1);
+        gates.push(("cx".to_string(), smallvec![], smallvec![0, 1]));
+        gates.push(("sx".to_string(), smallvec![], smallvec![0]));
+        gates.push((
+            "rz".to_string(),
+            smallvec![euler_q0[1][1] - PI],
+            smallvec![0],
+        ));
+        gates.push(("sx".to_string(), smallvec![], smallvec![0]));
+        gates.push(("rz".to_string(), smallvec![euler_q1[1][1]], smallvec![1]));
+        global_phase += PI2;
+        gates.push(("cx".to_string(), smallvec![], smallvec![0, 1]));
+        let mut euler_matrix_q0 =
+            rx_matrix(euler_q0[2][1]).dot(&rz_matrix(euler_q0[1][2] + euler_q0[2][0] + PI2));
+        euler_matrix_q0 = rz_matrix(euler_q0[2][2]).dot(&euler_matrix_q0);
+        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q0.view(), 0);
+        let mut euler_matrix_q1 =
+            rz_matrix(euler_q1[2][1]).dot(&rx_matrix(euler_q1[1][2] + euler_q1[2][0]));
+        euler_matrix_q1 = rx_matrix(euler_q1[2][2]).dot(&euler_matrix_q1);
+        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q1.view(), 1);
+        Some(TwoQubitGateSequence {
+            gates,
+            global_phase,
+        })
+    }
+
+    /// Decomposition of SU(4) gate for device with SX, virtual RZ, and CNOT gates assuming
+    /// three CNOT gates are needed.
+    ///
+    /// This first decomposes each unitary from the KAK decomposition into ZXZ on the source
+    /// qubit of the CNOTs and XZX on the targets in order commute operators to beginning and
+    /// end of decomposition. Inserting Hadamards reverses the direction of the CNOTs and transforms
+    /// a variable Rx -> variable virtual Rz. The beginning and ending single qubit gates are then
+    /// collapsed and re-decomposed with the single qubit decomposer. This last step could be avoided
+    /// if performance is a concern.
+    fn get_sx_vz_3cx_efficient_euler(
+        &self,
+        decomposition: &SmallVec<[Array2<Complex64>; 8]>,
+        target_decomposed: &TwoQubitWeylDecomposition,
+    ) -> Option<TwoQubitGateSequence> {
+        let mut gates = Vec::new();
+        let mut global_phase = target_decomposed.global_phase;
+        global_phase -= 3. * self.basis_decomposer.global_phase;
+        global_phase = global_phase.rem_euclid(TWO_PI);
+        let atol = 1e-10; // absolute tolerance for floats
+                          // Decompose source unitaries to zxz
+        let euler_q0: Vec<[f64; 3]> = decomposition
+            .iter()
+            .step_by(2)
+            .map(|decomp| {
+                let euler_angles = angles_from_unitary(decomp.view(), EulerBasis::ZXZ);
+                global_phase += euler_angles[3];
+                [euler_angles[2], euler_angles[0], euler_angles[1]]
+            })
+            .collect();
+        // Decompose target unitaries to xzx
+        let euler_q1: Vec<[f64; 3]> = decomposition
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .map(|decomp| {
+                let euler_angles = angles_from_unitary(decomp.view(), EulerBasis::XZX);
+                global_phase += euler_angles[3];
+                [euler_angles[2], euler_angles[0], euler_angles[1]]
+            })
+            .collect();
+
+        let x12 = euler_q0[1][2] + euler_q0[2][0];
+        let x12_is_non_zero = !abs_diff_eq!(x12, 0., epsilon = atol);
+        let mut x12_is_old_mult = None;
+        let mut x12_phase = 0.;
+        let x12_is_pi_mult = abs_diff_eq!(x12.sin(), 0., epsilon = atol);
+        if x12_is_pi_mult {
+            x12_is_old_mult = Some(abs_diff_eq!(x12.cos(), -1., epsilon = atol));
+            x12_phase = PI * x12.cos();
+        }
+        let x02_add = x12 - euler_q0[1][0];
+        let x12_is_half_pi = abs_diff_eq!(x12, PI2, epsilon = atol);
+
+        let mut euler_matrix_q0 = rx_matrix(euler_q0[0][1]).dot(&rz_matrix(euler_q0[0][0]));
+        if x12_is_non_zero && x12_is_pi_mult {
+            euler_matrix_q0 = rz_matrix(euler_q0[0][2] - x02_add).dot(&euler_matrix_q0);
+        } else {
+            euler_matrix_q0 = rz_matrix(euler_q0[0][2] + euler_q0[1][0]).dot(&euler_matrix_q0);
+        }
+        euler_matrix_q0 = aview2(&H_GATE).dot(&euler_matrix_q0);
+        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q0.view(), 0);
+
+        let rx_0 = rx_matrix(euler_q1[0][0]);
+        let rz = rz_matrix(euler_q1[0][1]);
+        let rx_1 = rx_matrix(euler_q1[0][2] + euler_q1[1][0]);
+        let mut euler_matrix_q1 = rz.dot(&rx_0);
+        euler_matrix_q1 = rx_1.dot(&euler_matrix_q1);
+        euler_matrix_q1 = aview2(&H_GATE).dot(&euler_matrix_q1);
+        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q1.view(), 1);
+
+        gates.push(("cx".to_string(), smallvec![], smallvec![1, 0]));
+
+        if x12_is_pi_mult {
+            // even or odd multiple
+            if x12_is_non_zero {
+                global_phase += x12_phase;
+            }
+            if x12_is_non_zero && x12_is_old_mult.unwrap() {
+                gates.push(("rz".to_string(), smallvec![-euler_q0[1][1]], smallvec![0]));
+            } else {
+                gates.push(("rz".to_string(), smallvec![euler_q0[1][1]], smallvec![0]));
+                global_phase += PI;
+            }
+        }
+        if x12_is_half_pi {
+            gates.push(("sx".to_string(), smallvec![], smallvec![0]));
+            global_phase -= PI4;
+        } else if x12_is_non_zero && !x12_is_pi_mult {
+            if self.pulse_optimize.is_none() {
+                self.append_1q_sequence(&mut gates, &mut global_phase, rx_matrix(x12).view(), 0);
+            } else {
+                return None;
+            }
+        }
+        if abs_diff_eq!(euler_q1[1][1], PI2, epsilon = atol) {
+            gates.push(("sx".to_string(), smallvec![], smallvec![1]));
+            global_phase -= PI4
+        } else if self.pulse_optimize.is_none() {
+            self.append_1q_sequence(
+                &mut gates,
+                &mut global_phase,
+                rx_matrix(euler_q1[1][1]).view(),
+                1,
+            );
+        } else {
+            return None;
+        }
+        gates.push((
+            "rz".to_string(),
+            smallvec![euler_q1[1][2] + euler_q1[2][0]],
+            smallvec![1],
+        ));
+        gates.push(("cx".to_string(), smallvec![], smallvec![1, 0]));
+        gates.push(("rz".to_string(), smallvec![euler_q0[2][1]], smallvec![0]));
+        if abs_diff_eq!(euler_q1[2][1], PI2, epsilon = atol) {
+            gates.push(("sx".to_string(), smallvec![], smallvec![1]));
+            global_phase -= PI4;
+        } else if self.pulse_optimize.is_none() {
+            self.append_1q_sequence(
+                &mut gates,
+                &mut global_phase,
+                rx_matrix(euler_q1[2][1]).view(),
+                1,
+            );
+        } else {
+            return None;
+        }
+        gates.push(("cx".to_string(), smallvec![], smallvec![1, 0]));
+        let mut euler_matrix = rz_matrix(euler_q0[2][2] + euler_q0[3][0]).dot(&aview2(&H_GATE));
+        euler_matrix = rx_matrix(euler_q0[3][1]).dot(&euler_matrix);
+        euler_matrix = rz_matrix(euler_q0[3][2]).dot(&euler_matrix);
+        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix.view(), 0);
+
+        let mut euler_matrix = rx_matrix(euler_q1[2][2] + euler_q1[3][0]).dot(&aview2(&H_GATE));
+        euler_matrix = rz_matrix(euler_q1[3][1]).dot(&euler_matrix);
+        euler_matrix = rx_matrix(euler_q1[3][2]).dot(&euler_matrix);
+        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix.view(), 1);
+
+        let out_unitary = compute_unitary(&gates, global_phase).ok()?;
+        // TODO: fix the sign problem to avoid correction here
+        if abs_diff_eq!(
+            target_decomposed.unitary_matrix[[0, 0]],
+            -out_unitary[[0, 0]],
+            epsilon = atol
+        ) {
+            global_phase += PI;
+        }
+        Some(TwoQubitGateSequence {
+            gates,
+            global_phase,
+        })
+    }
+
+    /// Decomposition of SU(4) gate for device with SX, virtual RZ, and CZ gates assuming
+    /// two CZ gates are needed.
+    fn get_sx_vz_2cz_efficient_euler(
+        &self,
+        target_decomposed: &TwoQubitWeylDecomposition,
+    ) -> Option<TwoQubitGateSequence> {
+        self.get_sx_vz_ncz_efficient_euler(target_decomposed, 2)
+    }
+
+    /// Decomposition of SU(4) gate for device with SX, virtual RZ, and CZ gates assuming
+    /// three CZ gates are needed.
+    fn get_sx_vz_3cz_efficient_euler(
+        &self,
+        target_decomposed: &TwoQubitWeylDecomposition,
+    ) -> Option<TwoQubitGateSequence> {
+        self.get_sx_vz_ncz_efficient_euler(target_decomposed, 3)
+    }
+
+    /// Shared implementation of the SX, virtual RZ and CZ decompositions.
+    ///
+    /// The KAK local gates are chosen as in the CNOT decompositions: the target is first
+    /// decomposed with the SX, virtual RZ and CNOT routines, which split the locals into ZXZ and
+    /// XZX and fix the angles of the middle gates. Each CNOT is then replaced by a CZ between
+    /// Hadamards on its target, and the single qubit gates between two CZ gates, Hadamards
+    /// included, are collapsed and re-decomposed with the single qubit decomposer. CZ is diagonal,
+    /// so a trailing RZ of each of those is carried through the following CZ and folded into the
+    /// next single qubit gate on the same qubit. This needs fewer SX gates than decomposing each
+    /// of the KAK local gates separately.
+    fn get_sx_vz_ncz_efficient_euler(
+        &self,
+        target_decomposed: &TwoQubitWeylDecomposition,
+        nbasis: u8,
+    ) -> Option<TwoQubitGateSequence> {
+        static CX_DECOMPOSER: OnceLock<TwoQubitBasisDecomposer> = OnceLock::new();
+        let cx_decomposer = CX_DECOMPOSER.get_or_init(|| {
+            TwoQubitBasisDecomposer::new_inner(
+                "cx".to_string(),
+                aview2(&CX_GATE),
+                1.0,
+                "ZSX",
+                None,
+                true,
+            )
+            .unwrap()
+        });
+        let decomposition = cx_decomposer.decomposition_for_nbasis(target_decomposed, nbasis);
+        let cx_sequence = match nbasis {
+            2 => cx_decomposer.get_sx_vz_2cx_efficient_euler(&decomposition, target_decomposed),
+            3 => cx_decomposer.get_sx_vz_3cx_efficient_euler(&decomposition, target_decomposed),
+            _ => None,
+        }?;
+        let mut gates = Vec::new();
+        let mut global_phase = cx_sequence.global_phase;
+        let mut pending = [Array2::<Complex64>::eye(2), Array2::<Complex64>::eye(2)];
+        let h = aview2(&H_GATE);
+        for (name, params, qubits) in cx_sequence.gates {
+            if qubits.len() == 1 {
+                let qubit = qubits[0] as usize;
+                let matrix =
+                    aview2(&one_qubit_gate_matrix(&name, &params).ok()?).dot(&pending[qubit]);
+                pending[qubit] = matrix;
+                continue;
+            }
+            // A CX is a CZ between Hadamards on the target of the CX.
+            let target = qubits[1] as usize;
+            pending[target] = h.dot(&pending[target]);
+            self.flush_1q_runs(&mut pending, &mut gates, &mut global_phase, true)?;
+            gates.push(("cz".to_string(), smallvec![], smallvec![0, 1]));
+            pending[target] = h.dot(&pending[target]);
+        }
+        self.flush_1q_runs(&mut pending, &mut gates, &mut global_phase, false)?;
+        Some(TwoQubitGateSequence {
+            gates,
+            global_phase,
+        })
+    }
+
+    /// Synthesize the pending single qubit gate of each qubit and reset it to the identity. With
+    /// ``carry``, a trailing RZ is not emitted but left pending instead, to be carried through
+    /// a following diagonal gate.
+    fn flush_1q_runs(
+        &self,
+        pending: &mut [Array2<Complex64>; 2],
+        gates: &mut TwoQubitSequenceVec,
+        global_phase: &mut f64,
+        carry: bool,
+    ) -> Option<()> {
+        for (qubit, unitary) in pending.iter_mut().enumerate() {
+            let mut sequence = unitary_to_gate_sequence_inner(
+                unitary.view(),
+                &self.euler_basis_candidates,
+                qubit,
+                None,
+                true,
+                None,
+            )?;
+            *unitary = Array2::eye(2);
+            if carry && sequence.gates.last().is_some_and(|gate| gate.0 == "rz") {
+                let (_, params) = sequence.gates.pop().unwrap();
+                *unitary = rz_matrix(params[0]);
+            }
+            *global_phase += sequence.global_phase;
+            for gate in sequence.gates {
+                gates.push((gate.0, gate.1, smallvec![qubit as u8]));
+            }
+        }
+        Some(())
+    }
+
+    fn append_1q_sequence(
+        &self,
+        gates: &mut TwoQubitSequenceVec,
+        global_phase: &mut f64,
+        unitary: ArrayView2<Complex64>,
+        qubit: u8,
+    ) {
+        let sequence = unitary_to_gate_sequence_inner(
+            unitary,
+            &self.euler_basis_candidates,
+            qubit as usize,
+            None,
+            true,
+            None,
+        );
+        if let Some(sequence) = sequence {
+            *global_phase += sequence.global_phase;
+            for gate in sequence.gates {
+                gates.push((gate.0, gate.1, smallvec![qubit]));
+            }
+        }
+    }
+
+    fn pulse_optimal_chooser(
+        &self,
+        best_nbasis: u8,
+        decomposition: &SmallVec<[Array2<Complex64>; 8]>,
+        target_decomposed: &TwoQubitWeylDecomposition,
+    ) -> PyResult<Option<TwoQubitGateSequence>> {
+        if self.pulse_optimize.is_some()
+            && (best_nbasis == 0 || best_nbasis == 1 || best_nbasis > 3)
+        {
+            return Ok(None);
+        }
+        if !self.has_pulse_optimal_basis() {
+            if self.pulse_optimize.is_some() {
+                let used: Vec<&str> = self
+                    .euler_basis_candidates
+                    .iter()
+                    .map(|basis| basis.as_str())
+                    .collect();
+                return Err(QiskitError::new_err(format!(
+                    "'pulse_optimize' currently only works with ZSX basis ({} used)",
+                    used.join(", ")
+                )));
+            } else {
+                return Ok(None);
+            }
+        }
+        if self.gate != "cx" && self.gate != "cz" {
+            if self.pulse_optimize.is_some() {
+                return Err(QiskitError::new_err(
+                    "pulse_optimizer currently only works with CNOT or CZ entangling gates",
+                ));
+            } else {
+                return Ok(None);
+            }
+        }
+        let res = match (self.gate.as_str(), best_nbasis) {
+            ("cx", 3) => self.get_sx_vz_3cx_efficient_euler(decomposition, target_decomposed),
+            ("cx", 2) => self.get_sx_vz_2cx_efficient_euler(decomposition, target_decomposed),
+            ("cz", 3) => self.get_sx_vz_3cz_efficient_euler(target_decomposed),
+            ("cz", 2) => self.get_sx_vz_2cz_efficient_euler(target_decomposed),
+            _ => None,
+        };
+        if self.pulse_optimize.is_some() && res.is_none() {
+            return Err(QiskitError::new_err(
+                "Failed to compute requested pulse optimal decomposition",
+            ));
+        }
+        Ok(res)
+    }
+
+    /// The Weyl decomposition of ``unitary`` and the number of basis gates to use for it, which
+    /// is ``force_nbasis_best_effort`` if given instead of the best count.
+    fn target_and_forced_nbasis(
+        &self,
+        unitary: ArrayView2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        force_nbasis_best_effort: Option<u8>,
+    ) -> PyResult<(TwoQubitWeylDecomposition, u8)> {
+        match force_nbasis_best_effort {
+            Some(nbasis) if nbasis > 3 => Err(QiskitError::new_err(format!(
+                "TwoQubitBasisDecomposer: force_nbasis_best_effort must be at most 3, got {}",
+                nbasis
+            ))),
+            Some(nbasis) => Ok((
+                TwoQubitWeylDecomposition::new_inner(unitary, Some(DEFAULT_FIDELITY), None)?,
+                nbasis,
+            )),
+            None => self.target_and_nbasis(unitary, basis_fidelity, approximate, None, None),
+        }
+    }
+
+    /// Decompose ``unitary``, which can have any memory layout: the Weyl decomposition of the
+    /// target works on a copy of it in standard layout.
+    fn call_inner(
+        &self,
+        unitary: ArrayView2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        _num_basis_uses: Option<u8>,
+        min_acceptable_fidelity: Option<f64>,
+    ) -> PyResult<TwoQubitGateSequence> {
+        self.call_inner_with_phases(
+            unitary,
+            basis_fidelity,
+            approximate,
+            _num_basis_uses,
+            min_acceptable_fidelity,
+        )
+        .map(|(sequence, _phases)| sequence)
+    }
+
+    /// Like ``call_inner``, but also return the global phase of the sequence split into the
+    /// contributions ``[wire 0, wire 1, entangler]``, where the first two are the phases of the
+    /// single-qubit gates on each wire and the last is that of the Weyl decomposition of the
+    /// target and the basis gates. A pulse optimal sequence only tracks its total phase, which
+    /// is reported as the entangler contribution.
+    fn call_inner_with_phases(
+        &self,
+        unitary: ArrayView2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        _num_basis_uses: Option<u8>,
+        min_acceptable_fidelity: Option<f64>,
+    ) -> PyResult<(TwoQubitGateSequence, [f64; 3])> {
+        let (target_decomposed, best_nbasis) = self.target_and_nbasis(
+            unitary,
+            basis_fidelity,
+            approximate,
+            _num_basis_uses,
+            min_acceptable_fidelity,
+        )?;
+        self.sequence_for_nbasis(&target_decomposed, best_nbasis, self.euler_basis)
+    }
+
+    /// Like ``call_inner``, but synthesize the single-qubit gates in each of the decomposer's
+    /// euler basis and [MINIMIZE_1Q_BASES], and return the sequence with the smallest ``key``.
+    /// Ties are broken in favor of the decomposer's euler basis.
+    fn call_inner_best_euler_basis<K: PartialOrd>(
+        &self,
+        unitary: ArrayView2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        _num_basis_uses: Option<u8>,
+        min_acceptable_fidelity: Option<f64>,
+        key: impl Fn(&TwoQubitGateSequence) -> K,
+    ) -> PyResult<TwoQubitGateSequence> {
+        let (target_decomposed, best_nbasis) = self.target_and_nbasis(
+            unitary,
+            basis_fidelity,
+            approximate,
+            _num_basis_uses,
+            min_acceptable_fidelity,
+        )?;
+        let (mut best, _) =
+            self.sequence_for_nbasis(&target_decomposed, best_nbasis, self.euler_basis)?;
+        for euler_basis in MINIMIZE_1Q_BASES {
+            let (sequence, _) =
+                self.sequence_for_nbasis(&target_decomposed, best_nbasis, euler_basis)?;
+            if key(&sequence) < key(&best) {
+                best = sequence;
+            }
+        }
+        Ok(best)
+    }
+
+    /// The pulse optimal sequence for ``unitary`` if it needs three uses of a ``cx`` or ``cz``
+    /// basis gate and the ``ZSX`` or ``ZSXX`` basis is available, whether or not
+    /// ``pulse_optimize`` is set.
+    fn pulse_optimal_3_basis_sequence(
+        &self,
+        unitary: ArrayView2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        _num_basis_uses: Option<u8>,
+        min_acceptable_fidelity: Option<f64>,
+    ) -> PyResult<Option<TwoQubitGateSequence>> {
+        if !self.has_pulse_optimal_basis() {
+            return Ok(None);
+        }
+        let (target_decomposed, nbasis) = self.target_and_nbasis(
+            unitary,
+            basis_fidelity,
+            approximate,
+            _num_basis_uses,
+            min_acceptable_fidelity,
+        )?;
+        if nbasis != 3 {
+            return Ok(None);
+        }
+        let decomposition = self.decomposition_for_nbasis(&target_decomposed, nbasis);
+        Ok(match self.gate.as_str() {
+            "cx" => self.get_sx_vz_3cx_efficient_euler(&decomposition, &target_decomposed),
+            "cz" => self.get_sx_vz_3cz_efficient_euler(&target_decomposed),
+            _ => None,
+        })
+    }
+
+    /// Synthesize ``unitary`` and return the unitary of the resulting sequence, including its
+    /// global phase. This is mostly useful to check the synthesis from Rust without building a
+    /// circuit.
+    pub fn synthesized_unitary(
+        &self,
+        unitary: ArrayView2<Complex64>,
+        approximate: bool,
+    ) -> PyResult<Array2<Complex64>> {
+        let (target_decomposed, best_nbasis) =
+            self.target_and_nbasis(unitary, None, approximate, None, None)?;
+        self.decomposition_unitary(&target_decomposed, best_nbasis)
+    }
+
+    /// The unitary of the sequence synthesized from the Weyl decomposition ``target_decomposed``
+    /// with ``best_nbasis`` basis gates, as ``call_inner`` does.
+    fn decomposition_unitary(
+        &self,
+        target_decomposed: &TwoQubitWeylDecomposition,
+        best_nbasis: u8,
+    ) -> PyResult<Array2<Complex64>> {
+        let (sequence, _phases) =
+            self.sequence_for_nbasis(target_decomposed, best_nbasis, self.euler_basis)?;
+        self.sequence_unitary(&sequence)
+    }
+
+    /// The unitary of a sequence produced by this decomposer, including its global phase.
+    fn sequence_unitary(&self, sequence: &TwoQubitGateSequence) -> PyResult<Array2<Complex64>> {
+        let basis_matrix = &self.basis_decomposer.unitary_matrix;
+        let basis_matrix: [[Complex64; 4]; 4] =
+            std::array::from_fn(|i| std::array::from_fn(|j| basis_matrix[[i, j]]));
+        sequence_matrix(&sequence.gates, sequence.global_phase, true, |_, _| {
+            Ok(basis_matrix)
+        })
+    }
+
+    /// Append ``next`` to ``prev``, merging the single-qubit gates at the boundary of the two
+    /// blocks. On each wire, the single-qubit gates of ``prev`` after its last two-qubit gate and
+    /// those of ``next`` before its first two-qubit gate are multiplied together and
+    /// re-decomposed once in the Euler basis of the decomposer.
+    pub fn merge_boundary(
+        &self,
+        prev: &mut TwoQubitGateSequence,
+        next: &TwoQubitGateSequence,
+    ) -> PyResult<()> {
+        let prev_tail = prev
+            .gates
+            .iter()
+            .rposition(|gate| gate.2.len() == 2)
+            .map_or(0, |idx| idx + 1);
+        let next_head = next
+            .gates
+            .iter()
+            .position(|gate| gate.2.len() == 2)
+            .unwrap_or(next.gates.len());
+        let mut boundary = [ONE_QUBIT_IDENTITY; 2];
+        for (name, params, qubits) in prev.gates[prev_tail..]
+            .iter()
+            .chain(&next.gates[..next_head])
+        {
+            let gate = one_qubit_gate_matrix(name, params)?;
+            let acc = boundary[qubits[0] as usize];
+            boundary[qubits[0] as usize] = std::array::from_fn(|row| {
+                std::array::from_fn(|col| gate[row][0] * acc[0][col] + gate[row][1] * acc[1][col])
+            });
+        }
+        let mut gates = prev
+            .gates
+            .drain(..prev_tail)
+            .collect::<TwoQubitSequenceVec>();
+        let mut global_phase = prev.global_phase + next.global_phase;
+        for (qubit, matrix) in boundary.iter().enumerate() {
+            let sequence = unitary_to_gate_sequence_inner(
+                aview2(matrix),
+                &self.euler_basis_candidates,
+                qubit,
+                None,
+                true,
+                None,
+            )
+            .unwrap();
+            global_phase += sequence.global_phase;
+            gates.extend(
+                sequence
+                    .gates
+                    .into_iter()
+                    .map(|(name, params)| (name, params, smallvec![qubit as u8])),
+            );
+        }
+        gates.extend(next.gates[next_head..].iter().cloned());
+        prev.gates = gates;
+        prev.global_phase = global_phase;
+        Ok(())
+    }
+
+    /// Weyl-decompose ``unitary`` and choose the number of basis gate uses for it, checking the
+    /// expected fidelity against ``min_acceptable_fidelity``.
+    fn target_and_nbasis(
+        &self,
+        unitary: ArrayView2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        _num_basis_uses: Option<u8>,
+        min_acceptable_fidelity: Option<f64>,
+    ) -> PyResult<(TwoQubitWeylDecomposition, u8)> {
+        let basis_fidelity = if !approximate {
+            1.0
+        } else {
+            basis_fidelity.unwrap_or(self.basis_fidelity)
+        };
+        let target_decomposed =
+            TwoQubitWeylDecomposition::new_inner(unitary, Some(DEFAULT_FIDELITY), None)?;
+        // A target locally equivalent to an ideal basis gate is synthesized exactly by a single
+        // application of it, so there is no need to compare the traces of all the candidates.
+        let basis = &self.basis_decomposer;
+        if basis_fidelity == 1.0
+            && _num_basis_uses.is_none()
+            && !min_acceptable_fidelity.is_some_and(|min_fidelity| min_fidelity > 1.0)
+            && [
+                (target_decomposed.a, basis.a),
+                (target_decomposed.b, basis.b),
+                (target_decomposed.c, basis.c),
+            ]
+            .iter()
+            .all(|(target, basis)| (target - basis).abs() <= BASIS_COORDINATES_ATOL)
+        {
+            return Ok((target_decomposed, 1));
+        }
+        let traces = self.traces(&target_decomposed);
+        let weighted_best = || {
+            traces
+                .into_iter()
+                .enumerate()
+                .map(|(idx, trace)| (idx, trace.trace_to_fid() * basis_fidelity.powi(idx as i32)))
+                .min_by(|(_idx1, fid1), (_idx2, fid2)| fid2.partial_cmp(fid1).unwrap())
+                .unwrap()
+                .0
+        };
+        // With an ideal basis gate the shortest exact decomposition is the best one. Picking it
+        // directly avoids the weighted comparison, where rounding errors can decide between
+        // decompositions that are all exact.
+        let best_nbasis = if basis_fidelity == 1.0 {
+            traces
+                .iter()
+                .position(|trace| trace.trace_to_fid() >= 1. - EXACT_FIDELITY_ATOL)
+                .unwrap_or_else(weighted_best)
+        } else {
+            weighted_best()
+        };
+        let best_nbasis = _num_basis_uses.unwrap_or(best_nbasis as u8);
+        if let Some(min_fidelity) = min_acceptable_fidelity {
+            let achieved_fidelity = traces[best_nbasis as usize].trace_to_fid()
+                * basis_fidelity.powi(best_nbasis as i32);
+            if achieved_fidelity < min_fidelity {
+                return Err(QiskitError::new_err(format!(
+                    "TwoQubitBasisDecomposer: best achievable fidelity {} using {} basis gates \
+                    is below the minimum acceptable fidelity {}",
+                    achieved_fidelity, best_nbasis, min_fidelity
+                )));
+            }
+        }
+        Ok((target_decomposed, best_nbasis))
+    }
+
+    /// If ``unitary`` is :math:`e^{i\phi} B^k` for the basis gate :math:`B` and a nonzero
+    /// integer or half-integer :math:`|k| \le 3`, return the sequence of the single gate
+    /// :math:`B^k` with global phase :math:`\phi`, trying the smallest :math:`|k|` first. The
+    /// powers use the principal branch of the eigenvalues of :math:`B`, like
+    /// :meth:`.Operator.power`.
+    fn basis_power_sequence(&self, unitary: ArrayView2<Complex64>) -> Option<TwoQubitGateSequence> {
+        let eig = self
+            .basis_decomposer
+            .unitary_matrix
+            .view()
+            .into_faer_complex()
+            .complex_eigendecomposition();
+        let u: Array2<Complex64> = eig.u().into_ndarray_complex().to_owned();
+        let u_dag = u.t().mapv(|x| x.conj());
+        let s = eig.s().column_vector();
+        let args: Array1<f64> = Array1::from_shape_fn(4, |i| s[i].to_num_complex().arg());
+        (1..=6)
+            .flat_map(|n| [n as f64 / 2., -(n as f64) / 2.])
+            .find_map(|k| {
+                let diag = args.mapv(|arg| (C1_IM * k * arg).exp());
+                let power = (&u * &diag).dot(&u_dag);
+                let trace: Complex64 = power
+                    .iter()
+                    .zip(unitary.iter())
+                    .map(|(p, t)| p.conj() * t)
+                    .sum();
+                let global_phase = trace.arg();
+                let phased = power.mapv(|x| x * (C1_IM * global_phase).exp());
+                if !abs_diff_eq!(phased, unitary, epsilon = 1e-10) {
+                    return None;
+                }
+                let gate = if k == 1. {
+                    (self.gate.clone(), smallvec![], smallvec![0, 1])
+                } else {
+                    (
+                        "BASIS_GATE_POWER".to_string(),
+                        smallvec![k],
+                        smallvec![0, 1],
+                    )
+                };
+                Some(TwoQubitGateSequence {
+                    gates: vec![gate],
+                    global_phase,
+                })
+            })
+    }
+
+    /// The local gates of the decomposition of ``target_decomposed`` with ``nbasis`` uses of the
+    /// basis gate, as ``[qubit 0, qubit 1]`` pairs before each basis gate and after the last one.
+    fn decomposition_for_nbasis(
+        &self,
+        target_decomposed: &TwoQubitWeylDecomposition,
+        nbasis: u8,
+    ) -> SmallVec<[Array2<Complex64>; 8]> {
+        match nbasis {
+            0 => decomp0_inner(target_decomposed),
+            1 => self.decomp1_inner(target_decomposed),
+            2 => self.strategy.decomp2(self, target_decomposed),
+            3 => self.strategy.decomp3(self, target_decomposed),
+            _ => unreachable!("Invalid basis to use"),
+        }
+    }
+
+    /// Build the sequence for ``target_decomposed`` with ``best_nbasis`` uses of the basis gate
+    /// and single-qubit gates in ``euler_basis``, together with its global phase split as in
+    /// ``call_inner_with_phases``. Pulse optimization is only attempted in the decomposer's own
+    /// euler basis.
+    fn sequence_for_nbasis(
+        &self,
+        target_decomposed: &TwoQubitWeylDecomposition,
+        best_nbasis: u8,
+        euler_basis: EulerBasis,
+    ) -> PyResult<(TwoQubitGateSequence, [f64; 3])> {
+        let decomposition = self.decomposition_for_nbasis(target_decomposed, best_nbasis);
+        let pulse_optimize = self.pulse_optimize.unwrap_or(true);
+        let sequence = if pulse_optimize && euler_basis == self.euler_basis {
+            self.pulse_optimal_chooser(best_nbasis, &decomposition, target_decomposed)?
+        } else {
+            None
+        };
+        if let Some(seq) = sequence {
+            let phases = [0., 0., seq.global_phase];
+            return Ok((seq, phases));
+        }
+        // The decomposer's own basis stands for all of its candidate bases.
+        let target_1q_basis_list = if euler_basis == self.euler_basis {
+            self.euler_basis_candidates.clone()
+        } else {
+            vec![euler_basis]
+        };
+        let euler_decompositions: SmallVec<[Option<OneQubitGateSequence>; 8]> = decomposition
+            .iter()
+            .map(|decomp| {
+                unitary_to_gate_sequence_inner(
+                    decomp.view(),
+                    &target_1q_basis_list,
+                    0,
+                    None,
+                    true,
+                    None,
+                )
+            })
+            .collect();
+        // Worst case length is 5x 1q gates for each 1q decomposition + 1x 2q gate
+        // We might overallocate a bit if the euler basis is different but
+        // the worst case is just 16 extra elements with just a String and 2 smallvecs
+        // each. This is only transient though as the circuit sequences aren't long lived
+        // and are just used to create a QuantumCircuit or DAGCircuit when we return to
+        // Python space.
+        let mut gates = Vec::with_capacity(21);
+        let mut phases = [0., 0., target_decomposed.global_phase];
+        phases[2] -= best_nbasis as f64 * self.basis_decomposer.global_phase;
+        if best_nbasis == 2 {
+            phases[2] += PI;
+        }
+        for i in 0..best_nbasis as usize {
+            if let Some(euler_decomp) = &euler_decompositions[2 * i] {
+                for gate in &euler_decomp.gates {
+                    gates.push((gate.0.clone(), gate.1.clone(), smallvec![0]));
+                }
+                phases[0] += euler_decomp.global_phase
+            }
+            if let Some(euler_decomp) = &euler_decompositions[2 * i + 1] {
+                for gate in &euler_decomp.gates {
+                    gates.push((gate.0.clone(), gate.1.clone(), smallvec![1]));
+                }
+                phases[1] += euler_decomp.global_phase
+            }
+            gates.push((self.gate.clone(), smallvec![], smallvec![0, 1]));
+        }
+        if let Some(euler_decomp) = &euler_decompositions[2 * best_nbasis as usize] {
+            for gate in &euler_decomp.gates {
+                gates.push((gate.0.clone(), gate.1.clone(), smallvec![0]));
+            }
+            phases[0] += euler_decomp.global_phase
+        }
+        if let Some(euler_decomp) = &euler_decompositions[2 * best_nbasis as usize + 1] {
+            for gate in &euler_decomp.gates {
+                gates.push((gate.0.clone(), gate.1.clone(), smallvec![1]));
+            }
+            phases[1] += euler_decomp.global_phase
+        }
+        Ok((
+            TwoQubitGateSequence {
+                gates,
+                global_phase: phases.iter().sum(),
+            },
+            phases,
+        ))
+    }
+}
+
+static K12R_ARR: [[Complex64; 2]; 2] = [
+    [
+        Complex64::new(0., FRAC_1_SQRT_2),
+        Complex64::new(FRAC_1_SQRT_2, 0.),
+    ],
+    [
+        Complex64::new(-FRAC_1_SQRT_2, 0.),
+        Complex64::new(0., -FRAC_1_SQRT_2),
+    ],
+];
+
+static K12L_ARR: [[Complex64; 2]; 2] = [
+    [Complex64::new(0.5, 0.5), Complex64::new(0.5, 0.5)],
+    [Complex64::new(-0.5, 0.5), Complex64::new(0.5, -0.5)],
+];
+
+fn decomp0_inner(target: &TwoQubitWeylDecomposition) -> SmallVec<[Array2<Complex64>; 8]> {
+    smallvec![target.K1r.dot(&target.K2r), target.K1l.dot(&target.K2l),]
+}
+
+#[pymethods]
+impl TwoQubitBasisDecomposer {
+    #[allow(clippy::type_complexity)]
+    fn __getnewargs__(
+        &self,
+        py: Python,
+    ) -> (String, PyObject, f64, &str, Option<bool>, Vec<&str>, bool) {
+        (
+            self.gate.clone(),
+            self.basis_decomposer
+                .unitary_matrix
+                .to_pyarray_bound(py)
+                .into(),
+            self.basis_fidelity,
+            self.euler_basis.as_str(),
+            self.pulse_optimize,
+            self.euler_basis_candidates
+                .iter()
+                .map(|basis| basis.as_str())
+                .collect(),
+            // The matrix was checked when this decomposer was built, or deliberately not.
+            true,
+        )
+    }
+
+    #[new]
+    #[pyo3(signature=(gate, gate_matrix, basis_fidelity=1.0, euler_basis="U", pulse_optimize=None, euler_basis_candidates=None, unchecked=false, fixed_angle_sx=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        gate: String,
+        gate_matrix: PyReadonlyArray2<Complex64>,
+        basis_fidelity: f64,
+        euler_basis: &str,
+        pulse_optimize: Option<bool>,
+        euler_basis_candidates: Option<Vec<PyBackedStr>>,
+        unchecked: bool,
+        fixed_angle_sx: bool,
+    ) -> PyResult<Self> {
+        let mut decomposer = TwoQubitBasisDecomposer::new_inner(
+            gate,
+            gate_matrix.as_array(),
+            basis_fidelity,
+            euler_basis,
+            pulse_optimize,
+            unchecked,
+        )?;
+        if let Some(candidates) = euler_basis_candidates {
+            let candidates = candidates
+                .iter()
+                .map(|basis| parse_euler_basis(basis))
+                .collect::<PyResult<_>>()?;
+            decomposer = decomposer.with_euler_basis_candidates(candidates);
+        }
+        if fixed_angle_sx {
+            decomposer = decomposer.with_fixed_angle_sx()?;
+        }
+        Ok(decomposer)
+    }
+
+    /// Build a decomposer whose basis gate is the canonical gate :math:`U_d(a, b, c)` with
+    /// identity local gates, given only by its Weyl coordinates. The basis gate appears as
+    /// ``USER_GATE`` in the generated sequences.
+    #[staticmethod]
+    #[pyo3(signature=(a, b, c, basis_fidelity=1.0, euler_basis="U", pulse_optimize=None))]
+    fn from_coordinates(
+        a: f64,
+        b: f64,
+        c: f64,
+        basis_fidelity: f64,
+        euler_basis: &str,
+        pulse_optimize: Option<bool>,
+    ) -> PyResult<Self> {
+        TwoQubitBasisDecomposer::new_inner(
+            "USER_GATE".to_string(),
+            ud(a, b, c).view(),
+            basis_fidelity,
+            euler_basis,
+            pulse_optimize,
+            false,
+        )
+    }
+
+    fn traces(&self, target: &TwoQubitWeylDecomposition) -> [Complex64; 4] {
+        kak_traces(target.a, target.b, target.c, self.basis_decomposer.b)
+    }
+
+    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`0` uses of the basis gate.
+    /// Result :math:`U_r` has trace:
+    ///
+    /// .. math::
+    ///
+    ///     \Big\vert\text{Tr}(U_r\cdot U_\text{target}^{\dag})\Big\vert =
+    ///     4\Big\vert (\cos(x)\cos(y)\cos(z)+ j \sin(x)\sin(y)\sin(z)\Big\vert
+    ///
+    /// which is optimal for all targets and bases
+    #[staticmethod]
+    fn decomp0(py: Python, target: &TwoQubitWeylDecomposition) -> SmallVec<[PyObject; 2]> {
+        decomp0_inner(target)
+            .into_iter()
+            .map(|x| x.into_pyarray_bound(py).into())
+            .collect()
+    }
+
+    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`1` use of the basis gate
+    /// math:`\sim U_d(a, b, c)`.
+    /// Result :math:`U_r` has trace:
+    ///
+    /// .. math::
+    ///
+    ///     \Big\vert\text{Tr}(U_r \cdot U_\text{target}^{\dag})\Big\vert =
+    ///     4\Big\vert \cos(x-a)\cos(y-b)\cos(z-c) + j \sin(x-a)\sin(y-b)\sin(z-c)\Big\vert
+    ///
+    /// which is optimal for all targets and bases with ``z==0`` or ``c==0``.
+    fn decomp1(&self, py: Python, target: &TwoQubitWeylDecomposition) -> SmallVec<[PyObject; 4]> {
+        self.decomp1_inner(target)
+            .into_iter()
+            .map(|x| x.into_pyarray_bound(py).into())
+            .collect()
+    }
+
+    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`2` uses of the basis gate.
+    ///
+    /// For supercontrolled basis :math:`\sim U_d(\pi/4, b, 0)`, all b, result :math:`U_r` has trace
+    ///
+    /// .. math::
+    ///
+    ///     \Big\vert\text{Tr}(U_r \cdot U_\text{target}^\dag) \Big\vert = 4\cos(z)
+    ///
+    /// which is the optimal approximation for basis of CNOT-class :math:`\sim U_d(\pi/4, 0, 0)`
+    /// or DCNOT-class :math:`\sim U_d(\pi/4, \pi/4, 0)` and any target. It may
+    /// be sub-optimal for :math:`b \neq 0` (i.e. there exists an exact decomposition for any target
+    /// using :math:`B \sim U_d(\pi/4, \pi/8, 0)`, but it may not be this decomposition).
+    /// This is an exact decomposition for supercontrolled basis and target :math:`\sim U_d(x, y, 0)`.
+    /// Raises a :class:`.QiskitError` for a non-supercontrolled basis.
+    fn decomp2_supercontrolled(
         &self,
+        py: Python,
         target: &TwoQubitWeylDecomposition,
-    ) -> SmallVec<[Array2<Complex64>; 8]> {
-        smallvec![
-            self.q2r.dot(&target.K2r),
-            self.q2l.dot(&target.K2l),
-            self.q1ra.dot(&rz_matrix(2. * target.b)).dot(&self.q1rb),
-            self.q1la.dot(&rz_matrix(-2. * target.a)).dot(&self.q1lb),
-            target.K1r.dot(&self.q0r),
-            target.K1l.dot(&self.q0l),
-        ]
+    ) -> PyResult<SmallVec<[PyObject; 6]>> {
+        self.check_super_controlled("decomp2_supercontrolled")?;
+        Ok(self
+            .decomp2_supercontrolled_inner(target)
+            .into_iter()
+            .map(|x| x.into_pyarray_bound(py).into())
+            .collect())
     }
 
-    fn decomp3_supercontrolled_inner(
+    /// Decompose target with :math:`3` uses of the basis.
+    ///
+    /// This is an exact decomposition for supercontrolled basis :math:`\sim U_d(\pi/4, b, 0)`, all b,
+    /// and any target. Raises a :class:`.QiskitError` for a non-supercontrolled basis.
+    fn decomp3_supercontrolled(
         &self,
+        py: Python,
         target: &TwoQubitWeylDecomposition,
-    ) -> SmallVec<[Array2<Complex64>; 8]> {
-        smallvec![
-            self.u3r.dot(&target.K2r),
-            self.u3l.dot(&target.K2l),
-            self.u2ra.dot(&rz_matrix(2. * target.b)).dot(&self.u2rb),
-            self.u2la.dot(&rz_matrix(-2. * target.a)).dot(&self.u2lb),
-            self.u1ra.dot(&rz_matrix(-2. * target.c)).dot(&self.u1rb),
-            self.u1l.clone(),
-            target.K1r.dot(&self.u0r),
-            target.K1l.dot(&self.u0l),
-        ]
+    ) -> PyResult<SmallVec<[PyObject; 8]>> {
+        self.check_super_controlled("decomp3_supercontrolled")?;
+        Ok(self
+            .decomp3_supercontrolled_inner(target)
+            .into_iter()
+            .map(|x| x.into_pyarray_bound(py).into())
+            .collect())
     }
 
-    /// Decomposition of SU(4) gate for device with SX, virtual RZ, and CNOT gates assuming
-    /// two CNOT gates are needed.
+    /// Return the single-qubit matrices of the decomposition of ``unitary``, for the number of
+    /// basis gate uses that would be chosen when synthesizing it.
     ///
-    /// This first decomposes each unitary from the KAK decomposition into ZXZ on the source
-    /// qubit of the CNOTs and XZX on the targets in order to commute operators to beginning and
-    /// end of decomposition. The beginning and ending single qubit gates are then
-    /// collapsed and re-decomposed with the single qubit decomposer. This last step could be avoided
-    /// if performance is a concern.
-    fn get_sx_vz_2cx_efficient_euler(
+    /// For ``n`` basis gate uses the ``2 n + 2`` matrices are ordered as ``[qubit 0, qubit 1]``
+    /// pairs, the ``i``-th pair being applied before the ``i``-th basis gate and the last pair
+    /// after the last basis gate. This is the input to the single-qubit synthesis, before any
+    /// pulse optimization.
+    #[pyo3(signature = (unitary, approximate=true))]
+    fn decomposition_locals(
         &self,
-        decomposition: &SmallVec<[Array2<Complex64>; 8]>,
-        target_decomposed: &TwoQubitWeylDecomposition,
-    ) -> Option<TwoQubitGateSequence> {
-        let mut gates = Vec::new();
-        let mut global_phase = target_decomposed.global_phase;
-        global_phase -= 2. * self.basis_decomposer.global_phase;
-        let euler_q0: Vec<[f64; 3]> = decomposition
-            .iter()
-            .step_by(2)
-            .map(|decomp| {
-                let euler_angles = angles_from_unitary(decomp.view(), EulerBasis::ZXZ);
-                global_phase += euler_angles[3];
-                [euler_angles[2], euler_angles[0], euler_angles[1]]
+        py: Python,
+        unitary: PyReadonlyArray2<Complex64>,
+        approximate: bool,
+    ) -> PyResult<Vec<Py<PyArray2<Complex64>>>> {
+        let (target_decomposed, best_nbasis) =
+            self.target_and_nbasis(unitary.as_array(), None, approximate, None, None)?;
+        Ok(self
+            .decomposition_for_nbasis(&target_decomposed, best_nbasis)
+            .into_iter()
+            .map(|x| x.into_pyarray_bound(py).into())
+            .collect())
+    }
+
+    /// Return the decompositions of ``target`` with 0, 1, 2 and 3 uses of the basis gate, as
+    /// given by :meth:`decomp0` to :meth:`decomp3_supercontrolled`.
+    ///
+    /// The candidates are computed in parallel if ``parallel`` is true; it defaults to whether
+    /// Qiskit is allowed to use multiple threads.
+    #[pyo3(signature = (target, parallel=None))]
+    fn all_decomp_candidates(
+        &self,
+        py: Python,
+        target: &TwoQubitWeylDecomposition,
+        parallel: Option<bool>,
+    ) -> Vec<SmallVec<[PyObject; 8]>> {
+        let parallel = parallel.unwrap_or_else(getenv_use_multiple_threads);
+        self.all_decomp_candidates_inner(target, parallel)
+            .into_iter()
+            .map(|decomposition| {
+                decomposition
+                    .into_iter()
+                    .map(|x| x.into_pyarray_bound(py).into())
+                    .collect()
             })
-            .collect();
-        let euler_q1: Vec<[f64; 3]> = decomposition
+            .collect()
+    }
+
+    /// Decompose a two-qubit ``unitary`` over fixed basis and :math:`SU(2)` using the best
+    /// approximation given that each basis application has a finite ``basis_fidelity``.
+    #[pyo3(signature = (unitary, basis_fidelity=None, approximate=true, _num_basis_uses=None))]
+    fn __call__(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        _num_basis_uses: Option<u8>,
+    ) -> PyResult<TwoQubitGateSequence> {
+        self.call_inner(
+            unitary.as_array(),
+            basis_fidelity,
+            approximate,
+            _num_basis_uses,
+            None,
+        )
+    }
+
+    /// Decompose a two-qubit ``unitary`` like :meth:`__call__`, with the additional options
+    /// given by the attributes of ``options``, a :class:`.SequenceOptions`.
+    ///
+    /// If ``min_acceptable_fidelity`` is set and the expected fidelity of the best
+    /// decomposition (including the ``basis_fidelity`` of every basis gate used) is below it,
+    /// a :class:`.QiskitError` is raised instead of returning a sequence.
+    ///
+    /// If ``balance_single_qubit`` is true, single-qubit gates that commute with an adjacent
+    /// ``cx`` are moved across it to even out the single-qubit gates on the two wires between
+    /// consecutive basis gates.
+    ///
+    /// If ``schedule`` is true, the single-qubit gates between consecutive basis gates are
+    /// reordered so that gates which can run in parallel on the two wires are adjacent.
+    ///
+    /// If ``snap_angles`` is set, single-qubit gate angles within that tolerance of an angle
+    /// used earlier in the sequence are replaced by it, reducing the number of distinct angles
+    /// at the cost of a small approximation error.
+    ///
+    /// If ``absorb_global_phase`` is true, the global phase of the sequence is realized by
+    /// single-qubit gates of the decomposer's euler basis on the first wire and the returned
+    /// global phase is zero. Only the ``U``, ``U3``, ``U321``, ``PSX`` and ``U1X`` bases can
+    /// realize any phase; the bases made of rotations only realize multiples of :math:`\pi`, and
+    /// a ``QiskitError`` is raised for any other phase.
+    ///
+    /// If ``minimize_1q`` is true, the single-qubit gates are synthesized in each of the
+    /// decomposer's euler basis, ``ZYZ``, ``ZXZ`` and ``ZSX``, and the sequence with the fewest
+    /// single-qubit gates is returned.
+    ///
+    /// If ``euler_basis_q1_error`` is set to the single-qubit gate error rates of the two qubits,
+    /// the euler basis is chosen by error instead: the same bases are tried, and the sequence
+    /// whose single-qubit gates have the smallest total error, weighted by the error rate of
+    /// their qubit, is returned. This takes precedence over ``minimize_1q``. No gate is moved to
+    /// the other qubit, only the euler basis of the single-qubit gates is chosen.
+    ///
+    /// If ``depth_optimal`` is true and the target needs three uses of the basis gate, the target
+    /// is also synthesized with its qubits swapped and as the inverse of its adjoint, and, for a
+    /// ``cx`` or ``cz`` basis with the ``ZSX`` or ``ZSXX`` basis available, with the pulse optimal
+    /// placement. The sequence with the smallest depth is returned among those using the same
+    /// gates as the default one and, unless the basis gate is symmetric under swapping its
+    /// qubits, applying it in the same directions.
+    ///
+    /// If ``use_power`` is true and ``unitary`` is, up to a global phase, a power :math:`B^k` of
+    /// the basis gate :math:`B` for a nonzero integer or half-integer :math:`|k| \le 3`, the
+    /// sequence is the single gate ``BASIS_GATE_POWER`` with parameter :math:`k` (or the basis
+    /// gate itself for :math:`k = 1`). Powers are taken on the principal branch of the
+    /// eigenvalues of :math:`B`.
+    ///
+    /// If ``prefer_specialized`` and ``approximate`` are true, the target is first replaced by its
+    /// most specialized Weyl decomposition with a fidelity of at least ``1 - 1e-3`` to it, such
+    /// as a locally-controlled form for a target close to one. This trades a small approximation
+    /// error for a decomposition with fewer free parameters, and possibly fewer basis gates.
+    /// ``min_acceptable_fidelity`` is then checked against the specialized target.
+    ///
+    /// If ``input_endianness`` is ``Endianness.Big``, ``unitary`` is taken to be in the
+    /// big-endian qubit ordering and its qubits are swapped before it is decomposed, so the
+    /// returned sequence implements it in Qiskit's little-endian ordering.
+    ///
+    /// If ``pad_identities`` is true, the sequence is scheduled as for ``schedule`` and the idle
+    /// positions of each wire between consecutive basis gates are filled with ``id`` gates, so
+    /// both wires have the same number of gates in each such layer.
+    ///
+    /// If ``convention`` is ``MultiplicationOrder.LeftToRight``, the gates are returned in
+    /// reverse order, so that multiplying them from left to right gives ``unitary``. This is
+    /// applied last, and the result is not a valid sequence in Qiskit's convention, so it is only
+    /// available here and not when the Python decomposer builds a circuit.
+    ///
+    /// If ``optimize_1q`` is true, adjacent single-qubit gates on the same wire that are inverses
+    /// of each other are cancelled and adjacent ``rz`` gates are merged. This is applied after
+    /// ``balance_single_qubit``, ``snap_angles`` and ``absorb_global_phase``, which can all leave
+    /// such gates next to each other.
+    ///
+    /// If ``verify_atol`` is given, the unitary of the sequence is reconstructed, including its
+    /// global phase, and a ``QiskitError`` is raised if any of its elements differs from
+    /// ``unitary`` by more than ``verify_atol``. This is checked before ``pad_identities`` and
+    /// ``convention`` are applied, and not for a power of the basis gate from ``use_power``,
+    /// which is matched against the target while it is synthesized.
+    #[pyo3(signature = (unitary, basis_fidelity=None, approximate=true, _num_basis_uses=None, options=None))]
+    fn generate_sequence(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        _num_basis_uses: Option<u8>,
+        options: Option<SequenceOptions>,
+    ) -> PyResult<TwoQubitGateSequence> {
+        let SequenceOptions {
+            min_acceptable_fidelity,
+            balance_single_qubit,
+            schedule,
+            snap_angles,
+            absorb_global_phase,
+            minimize_1q,
+            euler_basis_q1_error,
+            depth_optimal,
+            use_power,
+            prefer_specialized,
+            input_endianness,
+            pad_identities,
+            convention,
+            verify_atol,
+            optimize_1q,
+        } = options.unwrap_or_default();
+        let synthesize = |unitary: &Array2<Complex64>| {
+            if let Some((error0, error1)) = euler_basis_q1_error {
+                self.call_inner_best_euler_basis(
+                    unitary.view(),
+                    basis_fidelity,
+                    approximate,
+                    _num_basis_uses,
+                    min_acceptable_fidelity,
+                    |sequence| {
+                        sequence
+                            .gates
+                            .iter()
+                            .filter(|gate| gate.2.len() == 1)
+                            .map(|gate| if gate.2[0] == 0 { error0 } else { error1 })
+                            .sum::<f64>()
+                    },
+                )
+            } else if minimize_1q {
+                self.call_inner_best_euler_basis(
+                    unitary.view(),
+                    basis_fidelity,
+                    approximate,
+                    _num_basis_uses,
+                    min_acceptable_fidelity,
+                    |sequence| {
+                        let num_1q = sequence
+                            .gates
+                            .iter()
+                            .filter(|gate| gate.2.len() == 1)
+                            .count();
+                        (num_1q, sequence.gates.len() - num_1q)
+                    },
+                )
+            } else {
+                self.call_inner(
+                    unitary.view(),
+                    basis_fidelity,
+                    approximate,
+                    _num_basis_uses,
+                    min_acceptable_fidelity,
+                )
+            }
+        };
+        let target = input_endianness.to_little_endian(unitary.as_array());
+        let mut unitary = target.clone();
+        if prefer_specialized && approximate {
+            unitary = TwoQubitWeylDecomposition::new_inner(
+                unitary.view(),
+                Some(PREFER_SPECIALIZED_FIDELITY),
+                None,
+            )?
+            .specialized_unitary();
+        }
+        let power_sequence = if use_power {
+            self.basis_power_sequence(unitary.view())
+        } else {
+            None
+        };
+        let is_power = power_sequence.is_some();
+        let mut sequence = match power_sequence {
+            Some(sequence) => sequence,
+            None => synthesize(&unitary)?,
+        };
+        let num_basis = sequence
+            .gates
             .iter()
-            .skip(1)
-            .step_by(2)
-            .map(|decomp| {
-                let euler_angles = angles_from_unitary(decomp.view(), EulerBasis::XZX);
-                global_phase += euler_angles[3];
-                [euler_angles[2], euler_angles[0], euler_angles[1]]
-            })
-            .collect();
-        let mut euler_matrix_q0 = rx_matrix(euler_q0[0][1]).dot(&rz_matrix(euler_q0[0][0]));
-        euler_matrix_q0 = rz_matrix(euler_q0[0][2] + euler_q0[1][0] + PI2).dot(&euler_matrix_q0);
-        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q0.view(), 0);
-        let mut euler_matrix_q1 = rz_matrix(euler_q1[0][1]).dot(&rx_matrix(euler_q1[0][0]));
-        euler_matrix_q1 = rx_matrix(euler_q1[0][2] + euler_q1[1][0]).dot(&euler_matrix_q1);
-        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q1.view(), 1);
-        gates.push(("cx".to_string(), smallvec![], smallvec![0, 1]));
-        gates.push(("sx".to_string(), smallvec![], smallvec![0]));
-        gates.push((
-            "rz".to_string(),
-            smallvec![euler_q0[1][1] - PI],
-            smallvec![0],
-        ));
-        gates.push(("sx".to_string(), smallvec![], smallvec![0]));
-        gates.push(("rz".to_string(), smallvec![euler_q1[1][1]], smallvec![1]));
-        global_phase += PI2;
-        gates.push(("cx".to_string(), smallvec![], smallvec![0, 1]));
-        let mut euler_matrix_q0 =
-            rx_matrix(euler_q0[2][1]).dot(&rz_matrix(euler_q0[1][2] + euler_q0[2][0] + PI2));
-        euler_matrix_q0 = rz_matrix(euler_q0[2][2]).dot(&euler_matrix_q0);
-        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q0.view(), 0);
-        let mut euler_matrix_q1 =
-            rz_matrix(euler_q1[2][1]).dot(&rx_matrix(euler_q1[1][2] + euler_q1[2][0]));
-        euler_matrix_q1 = rx_matrix(euler_q1[2][2]).dot(&euler_matrix_q1);
-        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q1.view(), 1);
-        Some(TwoQubitGateSequence {
-            gates,
-            global_phase,
-        })
+            .filter(|gate| gate.2.len() == 2)
+            .count();
+        if depth_optimal && num_basis == 3 {
+            let pulse_synthesize = |unitary: &Array2<Complex64>| {
+                self.pulse_optimal_3_basis_sequence(
+                    unitary.view(),
+                    basis_fidelity,
+                    approximate,
+                    _num_basis_uses,
+                    min_acceptable_fidelity,
+                )
+            };
+            let symmetric = is_swap_symmetric(self.basis_decomposer.unitary_matrix.view());
+            sequence = depth_optimal_placement(
+                &unitary,
+                sequence,
+                symmetric,
+                synthesize,
+                pulse_synthesize,
+            )?;
+        }
+        if balance_single_qubit {
+            sequence.balance_single_qubit();
+        }
+        if schedule {
+            sequence.schedule();
+        }
+        if let Some(atol) = snap_angles {
+            sequence.snap_angles(atol);
+        }
+        if absorb_global_phase {
+            sequence.absorb_global_phase(&self.euler_basis_candidates)?;
+        }
+        if optimize_1q {
+            sequence.optimize_1q();
+        }
+        if let Some(atol) = verify_atol.filter(|_| !is_power) {
+            let deviation = (&self.sequence_unitary(&sequence)? - &target)
+                .iter()
+                .map(|x| x.norm())
+                .fold(0., f64::max);
+            if deviation > atol {
+                return Err(QiskitError::new_err(format!(
+                    "TwoQubitBasisDecomposer: the synthesized sequence differs from the target \
+                     by {deviation}, which exceeds verify_atol {atol}"
+                )));
+            }
+        }
+        if pad_identities {
+            sequence.pad_identities();
+        }
+        if convention == MultiplicationOrder::LeftToRight {
+            sequence.gates.reverse();
+        }
+        Ok(sequence)
+    }
+
+    /// Decompose ``unitary`` like :meth:`generate_sequence` with its default options, and also
+    /// return the :class:`GateSource` of each gate of the sequence, which helps to track down
+    /// which part of the decomposition a wrong gate comes from.
+    #[pyo3(signature = (unitary, basis_fidelity=None, approximate=true))]
+    fn generate_sequence_with_sources(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+    ) -> PyResult<(TwoQubitGateSequence, Vec<GateSource>)> {
+        let sequence =
+            self.call_inner(unitary.as_array(), basis_fidelity, approximate, None, None)?;
+        let sources = gate_sources(&sequence.gates);
+        Ok((sequence, sources))
+    }
+
+    /// Decompose ``unitary`` like :meth:`generate_sequence` with its default options, and also
+    /// return the Weyl coordinates ``[a, b, c]`` of the target the sequence was synthesized
+    /// for, taken from the same Weyl decomposition.
+    #[pyo3(signature = (unitary, basis_fidelity=None, approximate=true))]
+    fn generate_sequence_with_coords(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+    ) -> PyResult<(TwoQubitGateSequence, [f64; 3])> {
+        let (target_decomposed, best_nbasis) =
+            self.target_and_nbasis(unitary.as_array(), basis_fidelity, approximate, None, None)?;
+        let (sequence, _) =
+            self.sequence_for_nbasis(&target_decomposed, best_nbasis, self.euler_basis)?;
+        let coords = [
+            target_decomposed.a,
+            target_decomposed.b,
+            target_decomposed.c,
+        ];
+        Ok((sequence, coords))
+    }
+
+    /// Decompose ``diag^{-1} @ unitary``, so that applying the diagonal ``diag`` after the
+    /// returned sequence realizes ``unitary``. This lets a diagonal that is applied anyway be
+    /// folded into the synthesis for free. Every entry of ``diag`` must have modulus 1, as
+    /// otherwise ``diag^{-1} @ unitary`` isn't unitary.
+    #[pyo3(signature = (unitary, diag, approximate=true))]
+    fn generate_sequence_absorbing_diagonal(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        diag: [Complex64; 4],
+        approximate: bool,
+    ) -> PyResult<TwoQubitGateSequence> {
+        if diag
+            .iter()
+            .any(|x| (x.norm() - 1.).abs() > DIAGONAL_UNIT_MODULUS_ATOL)
+        {
+            return Err(QiskitError::new_err(format!(
+                "TwoQubitBasisDecomposer: the diagonal to absorb must be unitary, but its entries \
+                 have moduli {:?}",
+                diag.map(|x| x.norm())
+            )));
+        }
+        let mut target = unitary.as_array().to_owned();
+        for (mut row, d) in target.outer_iter_mut().zip(diag) {
+            row.mapv_inplace(|x| x / d);
+        }
+        self.call_inner(target.view(), None, approximate, None, None)
+    }
+
+    /// Decompose ``unitary`` and return the sequence together with the duration of its critical
+    /// path, given the duration of each gate in ``durations`` keyed by gate name.
+    #[pyo3(signature = (unitary, durations, approximate=true))]
+    fn generate_sequence_with_durations(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        durations: HashMap<String, f64>,
+        approximate: bool,
+    ) -> PyResult<(TwoQubitGateSequence, f64)> {
+        let sequence = self.call_inner(unitary.as_array(), None, approximate, None, None)?;
+        let duration = sequence.critical_path_duration(&durations)?;
+        Ok((sequence, duration))
+    }
+
+    /// Decompose ``unitary`` like :meth:`__call__` and also return the fidelity of the sequence
+    /// to ``unitary``, assuming ideal basis gates.
+    ///
+    /// If ``force_nbasis_best_effort`` is set, exactly that many basis gates are used regardless
+    /// of ``basis_fidelity`` and ``approximate``, and the sequence is the highest-fidelity
+    /// approximation of ``unitary`` with that many of them. Unlike ``_num_basis_uses``, which is
+    /// meant for counts at which the decomposition is exact, the returned fidelity tells how good
+    /// the approximation is.
+    #[pyo3(signature = (unitary, basis_fidelity=None, approximate=true, *, force_nbasis_best_effort=None))]
+    fn generate_sequence_with_fidelity(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        force_nbasis_best_effort: Option<u8>,
+    ) -> PyResult<(TwoQubitGateSequence, f64)> {
+        let (target_decomposed, nbasis) = self.target_and_forced_nbasis(
+            unitary.as_array(),
+            basis_fidelity,
+            approximate,
+            force_nbasis_best_effort,
+        )?;
+        let fidelity = self.traces(&target_decomposed)[nbasis as usize].trace_to_fid();
+        let (sequence, _) =
+            self.sequence_for_nbasis(&target_decomposed, nbasis, self.euler_basis)?;
+        Ok((sequence, fidelity))
+    }
+
+    /// Like :meth:`generate_sequence`, but also return the :class:`.Specialization` of the Weyl
+    /// decomposition of ``unitary`` the sequence was built from.
+    #[pyo3(signature = (unitary, basis_fidelity=None, approximate=true))]
+    fn generate_sequence_with_specialization(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+    ) -> PyResult<(TwoQubitGateSequence, Specialization)> {
+        let (target_decomposed, best_nbasis) =
+            self.target_and_nbasis(unitary.as_array(), basis_fidelity, approximate, None, None)?;
+        let (sequence, _) =
+            self.sequence_for_nbasis(&target_decomposed, best_nbasis, self.euler_basis)?;
+        Ok((sequence, target_decomposed.specialization))
+    }
+
+    /// Decompose ``unitary`` with the number of basis gates that minimizes the total error, for
+    /// a basis gate with error rate ``cx_error``, and return the sequence with that number.
+    ///
+    /// Each additional basis gate lowers the approximation error but adds its own, so for
+    /// example the 2-gate approximation of a target needing 3 gates is chosen when its
+    /// infidelity is lower than the error of the extra gate. The total fidelity with :math:`k`
+    /// gates is estimated as :math:`F_k (1 - \epsilon)^k`.
+    #[pyo3(signature = (unitary, cx_error))]
+    fn noise_optimal_sequence(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        cx_error: f64,
+    ) -> PyResult<(TwoQubitGateSequence, u8)> {
+        if !(0. ..1.).contains(&cx_error) {
+            return Err(QiskitError::new_err(format!(
+                "TwoQubitBasisDecomposer: cx_error must be in [0, 1), got {cx_error}"
+            )));
+        }
+        let (target_decomposed, best_nbasis) =
+            self.target_and_nbasis(unitary.as_array(), Some(1. - cx_error), true, None, None)?;
+        let (sequence, _) =
+            self.sequence_for_nbasis(&target_decomposed, best_nbasis, self.euler_basis)?;
+        Ok((sequence, best_nbasis))
+    }
+
+    /// Decompose ``unitary`` exactly with at most ``max_basis`` basis gates, all of them applied
+    /// to the qubits in the order ``allowed_direction``, or return ``None`` if that isn't
+    /// possible.
+    ///
+    /// When the basis gates of the sequence act in the other direction, the target is
+    /// synthesized with its qubits swapped and the wires of the result are exchanged.
+    #[pyo3(signature = (unitary, allowed_direction, max_basis))]
+    fn try_directional(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        allowed_direction: [u8; 2],
+        max_basis: u8,
+    ) -> PyResult<Option<TwoQubitGateSequence>> {
+        if allowed_direction != [0, 1] && allowed_direction != [1, 0] {
+            return Err(QiskitError::new_err(format!(
+                "TwoQubitBasisDecomposer: allowed_direction must be [0, 1] or [1, 0], got \
+                 {allowed_direction:?}"
+            )));
+        }
+        let unitary = unitary.as_array().to_owned();
+        let perm = [0, 2, 1, 3];
+        let swapped = Array2::from_shape_fn((4, 4), |(i, j)| unitary[[perm[i], perm[j]]]);
+        for (target, swap) in [(unitary, false), (swapped, true)] {
+            let (target_decomposed, nbasis) =
+                self.target_and_nbasis(target.view(), None, false, None, None)?;
+            if nbasis > max_basis {
+                return Ok(None);
+            }
+            let (mut sequence, _) =
+                self.sequence_for_nbasis(&target_decomposed, nbasis, self.euler_basis)?;
+            if swap {
+                sequence.swap_wires();
+            }
+            if sequence
+                .gates
+                .iter()
+                .filter(|gate| gate.2.len() == 2)
+                .all(|gate| gate.2[..] == allowed_direction)
+            {
+                return Ok(Some(sequence));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Decompose the canonical gate :math:`U_d(a, b, c)` alone, for callers that only need a
+    /// circuit locally equivalent to it and handle the single-qubit gates themselves. With
+    /// ``approximate``, the number of basis gates is chosen for the decomposer's basis fidelity.
+    #[pyo3(signature = (a, b, c, approximate=true))]
+    fn synthesize_weyl_only(
+        &self,
+        a: f64,
+        b: f64,
+        c: f64,
+        approximate: bool,
+    ) -> PyResult<TwoQubitGateSequence> {
+        let target = ud(a, b, c);
+        self.call_inner(target.view(), None, approximate, None, None)
+    }
+
+    /// Return the parameter :math:`p` of the depolarizing channel
+    /// :math:`\rho \mapsto (1 - p) \rho + p I / 4` with the same average gate fidelity as the
+    /// synthesis of ``unitary``, assuming ideal basis gates.
+    ///
+    /// This is :math:`p = \frac{4}{3} (1 - F)` for the fidelity :math:`F` reported by
+    /// :meth:`generate_sequence_with_fidelity` with the same arguments, so it is 0 for an exact
+    /// synthesis and only accounts for the approximation error, not for errors of the basis
+    /// gates.
+    #[pyo3(signature = (unitary, basis_fidelity=None, approximate=true, *, force_nbasis_best_effort=None))]
+    fn approximation_error_channel(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        force_nbasis_best_effort: Option<u8>,
+    ) -> PyResult<f64> {
+        let (target_decomposed, nbasis) = self.target_and_forced_nbasis(
+            unitary.as_array(),
+            basis_fidelity,
+            approximate,
+            force_nbasis_best_effort,
+        )?;
+        let fidelity = self.traces(&target_decomposed)[nbasis as usize].trace_to_fid();
+        Ok((4. / 3. * (1. - fidelity)).max(0.))
+    }
+
+    /// Like :meth:`generate_sequence`, but with the target given as a flat row-major array of
+    /// length 16. Contiguous input is reshaped in place without copying.
+    #[pyo3(signature = (flat, basis_fidelity=None, approximate=true))]
+    fn generate_sequence_flat(
+        &self,
+        flat: PyReadonlyArray1<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+    ) -> PyResult<TwoQubitGateSequence> {
+        let flat = flat.as_array();
+        if flat.len() != 16 {
+            return Err(QiskitError::new_err(format!(
+                "TwoQubitBasisDecomposer: expected a flat unitary of length 16, got {}",
+                flat.len()
+            )));
+        }
+        let unitary: CowArray<Complex64, Ix2> = match flat.into_shape((4, 4)) {
+            Ok(unitary) => unitary.into(),
+            Err(_) => Array2::from_shape_fn((4, 4), |(i, j)| flat[4 * i + j]).into(),
+        };
+        self.call_inner(unitary.view(), basis_fidelity, approximate, None, None)
     }
 
-    /// Decomposition of SU(4) gate for device with SX, virtual RZ, and CNOT gates assuming
-    /// three CNOT gates are needed.
+    /// Decompose a two-qubit ``unitary`` like :meth:`__call__`, and also return the global
+    /// phase of the sequence split into the ``[wire 0, wire 1, entangler]`` contributions.
+    #[pyo3(signature = (unitary, approximate=true))]
+    fn phase_breakdown(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        approximate: bool,
+    ) -> PyResult<(TwoQubitGateSequence, [f64; 3])> {
+        self.call_inner_with_phases(unitary.as_array(), None, approximate, None, None)
+    }
+
+    /// Decompose ``unitary`` with each of 0, 1, 2 and 3 uses of the basis gate, sharing a single
+    /// Weyl decomposition of the target.
     ///
-    /// This first decomposes each unitary from the KAK decomposition into ZXZ on the source
-    /// qubit of the CNOTs and XZX on the targets in order commute operators to beginning and
-    /// end of decomposition. Inserting Hadamards reverses the direction of the CNOTs and transforms
-    /// a variable Rx -> variable virtual Rz. The beginning and ending single qubit gates are then
-    /// collapsed and re-decomposed with the single qubit decomposer. This last step could be avoided
-    /// if performance is a concern.
-    fn get_sx_vz_3cx_efficient_euler(
+    /// Returns the four sequences, indexed by the number of basis gates, and their fidelities to
+    /// ``unitary`` assuming ideal basis gates.
+    fn all_budget_circuits(
         &self,
-        decomposition: &SmallVec<[Array2<Complex64>; 8]>,
-        target_decomposed: &TwoQubitWeylDecomposition,
-    ) -> Option<TwoQubitGateSequence> {
-        let mut gates = Vec::new();
-        let mut global_phase = target_decomposed.global_phase;
-        global_phase -= 3. * self.basis_decomposer.global_phase;
-        global_phase = global_phase.rem_euclid(TWO_PI);
-        let atol = 1e-10; // absolute tolerance for floats
-                          // Decompose source unitaries to zxz
-        let euler_q0: Vec<[f64; 3]> = decomposition
-            .iter()
-            .step_by(2)
-            .map(|decomp| {
-                let euler_angles = angles_from_unitary(decomp.view(), EulerBasis::ZXZ);
-                global_phase += euler_angles[3];
-                [euler_angles[2], euler_angles[0], euler_angles[1]]
-            })
-            .collect();
-        // Decompose target unitaries to xzx
-        let euler_q1: Vec<[f64; 3]> = decomposition
-            .iter()
-            .skip(1)
-            .step_by(2)
-            .map(|decomp| {
-                let euler_angles = angles_from_unitary(decomp.view(), EulerBasis::XZX);
-                global_phase += euler_angles[3];
-                [euler_angles[2], euler_angles[0], euler_angles[1]]
+        unitary: PyReadonlyArray2<Complex64>,
+    ) -> PyResult<(Vec<TwoQubitGateSequence>, [f64; 4])> {
+        let target_decomposed =
+            TwoQubitWeylDecomposition::new_inner(unitary.as_array(), Some(DEFAULT_FIDELITY), None)?;
+        let fidelities = self
+            .traces(&target_decomposed)
+            .map(|trace| trace.trace_to_fid());
+        let sequences = (0..4u8)
+            .map(|nbasis| {
+                self.sequence_for_nbasis(&target_decomposed, nbasis, self.euler_basis)
+                    .map(|(sequence, _)| sequence)
             })
-            .collect();
+            .collect::<PyResult<_>>()?;
+        Ok((sequences, fidelities))
+    }
 
-        let x12 = euler_q0[1][2] + euler_q0[2][0];
-        let x12_is_non_zero = !abs_diff_eq!(x12, 0., epsilon = atol);
-        let mut x12_is_old_mult = None;
-        let mut x12_phase = 0.;
-        let x12_is_pi_mult = abs_diff_eq!(x12.sin(), 0., epsilon = atol);
-        if x12_is_pi_mult {
-            x12_is_old_mult = Some(abs_diff_eq!(x12.cos(), -1., epsilon = atol));
-            x12_phase = PI * x12.cos();
+    /// Decompose ``unitary`` both exactly and with at most ``cap`` uses of the basis gate,
+    /// sharing a single Weyl decomposition of the target.
+    ///
+    /// Returns the exact sequence, the capped sequence and their fidelities to ``unitary``
+    /// assuming ideal basis gates. If the exact decomposition needs no more than ``cap`` basis
+    /// gates, both sequences use the same number of them.
+    fn exact_and_approx(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        cap: u8,
+    ) -> PyResult<(TwoQubitGateSequence, TwoQubitGateSequence, f64, f64)> {
+        if cap > 3 {
+            return Err(QiskitError::new_err(format!(
+                "TwoQubitBasisDecomposer: cap must be at most 3, got {}",
+                cap
+            )));
         }
-        let x02_add = x12 - euler_q0[1][0];
-        let x12_is_half_pi = abs_diff_eq!(x12, PI2, epsilon = atol);
+        let target_decomposed =
+            TwoQubitWeylDecomposition::new_inner(unitary.as_array(), Some(DEFAULT_FIDELITY), None)?;
+        let fidelities = self
+            .traces(&target_decomposed)
+            .map(|trace| trace.trace_to_fid());
+        let exact_nbasis = fidelities
+            .iter()
+            .enumerate()
+            .min_by(|(_idx1, fid1), (_idx2, fid2)| fid2.partial_cmp(fid1).unwrap())
+            .unwrap()
+            .0 as u8;
+        let capped_nbasis = exact_nbasis.min(cap);
+        let (exact, _) =
+            self.sequence_for_nbasis(&target_decomposed, exact_nbasis, self.euler_basis)?;
+        let (capped, _) =
+            self.sequence_for_nbasis(&target_decomposed, capped_nbasis, self.euler_basis)?;
+        Ok((
+            exact,
+            capped,
+            fidelities[exact_nbasis as usize],
+            fidelities[capped_nbasis as usize],
+        ))
+    }
 
-        let mut euler_matrix_q0 = rx_matrix(euler_q0[0][1]).dot(&rz_matrix(euler_q0[0][0]));
-        if x12_is_non_zero && x12_is_pi_mult {
-            euler_matrix_q0 = rz_matrix(euler_q0[0][2] - x02_add).dot(&euler_matrix_q0);
-        } else {
-            euler_matrix_q0 = rz_matrix(euler_q0[0][2] + euler_q0[1][0]).dot(&euler_matrix_q0);
-        }
-        euler_matrix_q0 = aview2(&H_GATE).dot(&euler_matrix_q0);
-        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q0.view(), 0);
+    fn num_basis_gates(&self, unitary: PyReadonlyArray2<Complex64>) -> usize {
+        _num_basis_gates(self.basis_decomposer.b, self.basis_fidelity, unitary)
+    }
 
-        let rx_0 = rx_matrix(euler_q1[0][0]);
-        let rz = rz_matrix(euler_q1[0][1]);
-        let rx_1 = rx_matrix(euler_q1[0][2] + euler_q1[1][0]);
-        let mut euler_matrix_q1 = rz.dot(&rx_0);
-        euler_matrix_q1 = rx_1.dot(&euler_matrix_q1);
-        euler_matrix_q1 = aview2(&H_GATE).dot(&euler_matrix_q1);
-        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q1.view(), 1);
+    /// Return :meth:`num_basis_gates` for every 2-qubit unitary in ``unitaries``, given as an
+    /// ``(N, 4, 4)`` array. The counts are computed in parallel if Qiskit is allowed to use
+    /// multiple threads.
+    fn num_basis_gates_batch(
+        &self,
+        py: Python,
+        unitaries: PyReadonlyArray3<Complex64>,
+    ) -> Py<PyArray1<usize>> {
+        let unitaries = unitaries.as_array();
+        let (basis_b, basis_fidelity) = (self.basis_decomposer.b, self.basis_fidelity);
+        let num_basis = |unitary: ArrayView2<Complex64>| {
+            __num_basis_gates(basis_b, basis_fidelity, unitary.into_faer_complex())
+        };
+        let counts: Vec<usize> = if getenv_use_multiple_threads() {
+            unitaries
+                .outer_iter()
+                .into_par_iter()
+                .map(num_basis)
+                .collect()
+        } else {
+            unitaries.outer_iter().map(num_basis).collect()
+        };
+        counts.into_pyarray_bound(py).unbind()
+    }
 
-        gates.push(("cx".to_string(), smallvec![], smallvec![1, 0]));
+    /// Return the number of basis gates and the expected fidelity of the decomposition chosen
+    /// for every 2-qubit unitary in ``unitaries``, given as an ``(N, 4, 4)`` array.
+    ///
+    /// ``basis_fidelity`` and ``approximate`` are used like in :meth:`__call__`, and the
+    /// fidelities include the ``basis_fidelity`` of each basis gate. The targets are processed in
+    /// parallel if Qiskit is allowed to use multiple threads.
+    #[pyo3(signature = (unitaries, basis_fidelity=None, approximate=true))]
+    fn batch_report(
+        &self,
+        unitaries: PyReadonlyArray3<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+    ) -> PyResult<(Vec<u8>, Vec<f64>)> {
+        let unitaries = unitaries.as_array();
+        let gate_fidelity = if approximate {
+            basis_fidelity.unwrap_or(self.basis_fidelity)
+        } else {
+            1.0
+        };
+        let report = |unitary: ArrayView2<Complex64>| -> PyResult<(u8, f64)> {
+            let (target_decomposed, nbasis) =
+                self.target_and_nbasis(unitary, basis_fidelity, approximate, None, None)?;
+            let fidelity = self.traces(&target_decomposed)[nbasis as usize].trace_to_fid()
+                * gate_fidelity.powi(nbasis as i32);
+            Ok((nbasis, fidelity))
+        };
+        let reports: Vec<(u8, f64)> = if getenv_use_multiple_threads() {
+            unitaries
+                .outer_iter()
+                .into_par_iter()
+                .map(report)
+                .collect::<PyResult<_>>()?
+        } else {
+            unitaries
+                .outer_iter()
+                .map(report)
+                .collect::<PyResult<_>>()?
+        };
+        Ok(reports.into_iter().unzip())
+    }
 
-        if x12_is_pi_mult {
-            // even or odd multiple
-            if x12_is_non_zero {
-                global_phase += x12_phase;
-            }
-            if x12_is_non_zero && x12_is_old_mult.unwrap() {
-                gates.push(("rz".to_string(), smallvec![-euler_q0[1][1]], smallvec![0]));
-            } else {
-                gates.push(("rz".to_string(), smallvec![euler_q0[1][1]], smallvec![0]));
-                global_phase += PI;
-            }
-        }
-        if x12_is_half_pi {
-            gates.push(("sx".to_string(), smallvec![], smallvec![0]));
-            global_phase -= PI4;
-        } else if x12_is_non_zero && !x12_is_pi_mult {
-            if self.pulse_optimize.is_none() {
-                self.append_1q_sequence(&mut gates, &mut global_phase, rx_matrix(x12).view(), 0);
+    /// Return :meth:`generate_sequence` for every 2-qubit unitary in ``unitaries``, given as an
+    /// ``(N, 4, 4)`` array, with ``basis_fidelity`` and ``approximate`` used like there.
+    ///
+    /// The sequences are purely numeric, so they are all computed without holding the GIL, and in
+    /// parallel if Qiskit is allowed to use multiple threads. Building circuits from them is left
+    /// to the caller, which only needs the GIL for that final step.
+    #[pyo3(signature = (unitaries, basis_fidelity=None, approximate=true))]
+    fn generate_sequence_batch(
+        &self,
+        py: Python,
+        unitaries: PyReadonlyArray3<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+    ) -> PyResult<Vec<TwoQubitGateSequence>> {
+        let unitaries = unitaries.as_array();
+        py.allow_threads(|| {
+            let synthesize = |unitary: ArrayView2<Complex64>| {
+                self.call_inner(unitary, basis_fidelity, approximate, None, None)
+            };
+            if getenv_use_multiple_threads() {
+                unitaries
+                    .outer_iter()
+                    .into_par_iter()
+                    .map(synthesize)
+                    .collect()
             } else {
-                return None;
+                unitaries.outer_iter().map(synthesize).collect()
             }
+        })
+    }
+
+    /// Return a smooth estimate of the number of basis gates needed to synthesize ``unitary``.
+    ///
+    /// With :math:`F_k` the expected fidelity of the best decomposition using :math:`k` basis
+    /// gates, including the ``basis_fidelity`` :math:`f` of each of them, the estimate is
+    ///
+    /// .. math::
+    ///
+    ///     \sum_{k=0}^3 k\,w_k \Big/ \sum_{k=0}^3 w_k, \qquad
+    ///     w_k = \exp\big((F_k - \max_j F_j) / (1 - f)\big),
+    ///
+    /// so a competing decomposition contributes when its fidelity is within about one basis gate
+    /// infidelity of the best one. For :math:`f = 1` this is the count of
+    /// :meth:`num_basis_gates`.
+    #[pyo3(signature = (unitary, basis_fidelity=None))]
+    fn expected_basis_cost(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        basis_fidelity: Option<f64>,
+    ) -> f64 {
+        let basis_fidelity = basis_fidelity.unwrap_or(self.basis_fidelity);
+        let [a, b, c] = __weyl_coordinates(unitary.as_array().into_faer_complex());
+        let fidelities: Vec<f64> = kak_traces(a, b, c, self.basis_decomposer.b)
+            .into_iter()
+            .enumerate()
+            .map(|(k, trace)| trace.trace_to_fid() * basis_fidelity.powi(k as i32))
+            .collect();
+        let best = fidelities.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let temperature = 1. - basis_fidelity;
+        if temperature <= 0. {
+            return fidelities.iter().position(|fid| *fid == best).unwrap() as f64;
         }
-        if abs_diff_eq!(euler_q1[1][1], PI2, epsilon = atol) {
-            gates.push(("sx".to_string(), smallvec![], smallvec![1]));
-            global_phase -= PI4
-        } else if self.pulse_optimize.is_none() {
-            self.append_1q_sequence(
-                &mut gates,
-                &mut global_phase,
-                rx_matrix(euler_q1[1][1]).view(),
-                1,
-            );
+        let weights: Vec<f64> = fidelities
+            .iter()
+            .map(|fid| ((fid - best) / temperature).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+        weights
+            .iter()
+            .enumerate()
+            .map(|(k, weight)| k as f64 * weight)
+            .sum::<f64>()
+            / total
+    }
+
+    /// Return the worst-case number of basis gate applications in a synthesized sequence.
+    ///
+    /// For a supercontrolled basis any two-qubit unitary is synthesized exactly with at most 3
+    /// applications. For other bases the decomposer still never uses more than 3 applications,
+    /// but the decompositions with 2 or 3 applications are then not exact in general.
+    fn max_basis_gates_for_basis(&self) -> u8 {
+        3
+    }
+
+    /// Return the Weyl coordinates ``[a, b, c]`` of the basis gate.
+    fn basis_weyl_coordinates(&self) -> [f64; 3] {
+        [
+            self.basis_decomposer.a,
+            self.basis_decomposer.b,
+            self.basis_decomposer.c,
+        ]
+    }
+
+    /// Return the number of basis gates needed for ``unitary`` together with the basis gate
+    /// direction (as ``[control, target]``) with the better fidelity.
+    ///
+    /// The decomposer's ``basis_fidelity`` is taken as the fidelity of the ``[0, 1]`` direction,
+    /// and ``reverse_fidelity`` (defaulting to the same value) as that of ``[1, 0]``. The direction
+    /// only matters when two or three basis gates are needed; otherwise ``[0, 1]`` is returned.
+    #[pyo3(signature = (unitary, reverse_fidelity=None))]
+    fn best_direction_and_count(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        reverse_fidelity: Option<f64>,
+    ) -> (u8, [u8; 2]) {
+        let reverse_fidelity = reverse_fidelity.unwrap_or(self.basis_fidelity);
+        let (fidelity, direction) = if reverse_fidelity > self.basis_fidelity {
+            (reverse_fidelity, [1, 0])
         } else {
-            return None;
-        }
-        gates.push((
-            "rz".to_string(),
-            smallvec![euler_q1[1][2] + euler_q1[2][0]],
-            smallvec![1],
-        ));
-        gates.push(("cx".to_string(), smallvec![], smallvec![1, 0]));
-        gates.push(("rz".to_string(), smallvec![euler_q0[2][1]], smallvec![0]));
-        if abs_diff_eq!(euler_q1[2][1], PI2, epsilon = atol) {
-            gates.push(("sx".to_string(), smallvec![], smallvec![1]));
-            global_phase -= PI4;
-        } else if self.pulse_optimize.is_none() {
-            self.append_1q_sequence(
-                &mut gates,
-                &mut global_phase,
-                rx_matrix(euler_q1[2][1]).view(),
-                1,
-            );
+            (self.basis_fidelity, [0, 1])
+        };
+        let count = __num_basis_gates(
+            self.basis_decomposer.b,
+            fidelity,
+            unitary.as_array().into_faer_complex(),
+        ) as u8;
+        if count >= 2 {
+            (count, direction)
         } else {
-            return None;
+            (count, [0, 1])
         }
-        gates.push(("cx".to_string(), smallvec![], smallvec![1, 0]));
-        let mut euler_matrix = rz_matrix(euler_q0[2][2] + euler_q0[3][0]).dot(&aview2(&H_GATE));
-        euler_matrix = rx_matrix(euler_q0[3][1]).dot(&euler_matrix);
-        euler_matrix = rz_matrix(euler_q0[3][2]).dot(&euler_matrix);
-        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix.view(), 0);
-
-        let mut euler_matrix = rx_matrix(euler_q1[2][2] + euler_q1[3][0]).dot(&aview2(&H_GATE));
-        euler_matrix = rz_matrix(euler_q1[3][1]).dot(&euler_matrix);
-        euler_matrix = rx_matrix(euler_q1[3][2]).dot(&euler_matrix);
-        self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix.view(), 1);
+    }
 
-        let out_unitary = compute_unitary(&gates, global_phase);
-        // TODO: fix the sign problem to avoid correction here
-        if abs_diff_eq!(
-            target_decomposed.unitary_matrix[[0, 0]],
-            -out_unitary[[0, 0]],
-            epsilon = atol
-        ) {
-            global_phase += PI;
+    /// Return whether the pulse optimal sequence will be attempted for ``unitary``.
+    ///
+    /// This checks the same conditions as the synthesis does: ``pulse_optimize`` is not
+    /// ``False``, the Euler basis is ``ZSX`` or ``ZSXX``, the basis gate is ``cx`` or ``cz``
+    /// and the target needs two or three basis gates.
+    fn pulse_optimal_applicable(&self, unitary: PyReadonlyArray2<Complex64>) -> PyResult<bool> {
+        if !self.pulse_optimize.unwrap_or(true)
+            || !self.has_pulse_optimal_basis()
+            || (self.gate != "cx" && self.gate != "cz")
+        {
+            return Ok(false);
         }
-        Some(TwoQubitGateSequence {
-            gates,
-            global_phase,
+        let unitary = unitary.as_array();
+        check_two_qubit_shape("TwoQubitBasisDecomposer", unitary)?;
+        let best_nbasis = __num_basis_gates(
+            self.basis_decomposer.b,
+            self.basis_fidelity,
+            unitary.into_faer_complex(),
+        );
+        Ok(best_nbasis == 2 || best_nbasis == 3)
+    }
+}
+
+/// A decomposer of 2-qubit unitaries into two different basis gates :math:`A` and :math:`B`
+/// and single-qubit gates, for basis gates where :math:`B` is locally equivalent to
+/// :math:`\text{SWAP} \cdot A`, such as ``cx`` and ``iswap``.
+///
+/// A target :math:`U` for which :math:`\text{SWAP} \cdot U` needs two uses of :math:`A` is
+/// synthesized as :math:`A`, single-qubit gates and :math:`B`, since
+/// :math:`U = \text{SWAP} \cdot (\text{SWAP} \cdot U)` and the product of the ``SWAP`` with the
+/// last :math:`A` can be replaced by :math:`B` and single-qubit gates. This mixed sequence is
+/// used if it needs fewer basis gates than either basis gate on its own, otherwise the target is
+/// synthesized with whichever of the two needs fewer uses, preferring :math:`A`.
+#[pyclass(module = "qiskit._accelerate.two_qubit_decompose", subclass)]
+pub struct TwoQubitTwoBasisDecomposer {
+    decomposer_a: TwoQubitBasisDecomposer,
+    decomposer_b: TwoQubitBasisDecomposer,
+    // The single-qubit gates `[la, lb, ra, rb]` with `SWAP A = (la x lb) B (ra x rb)`.
+    swap_a_to_b: [Array2<Complex64>; 4],
+}
+
+impl TwoQubitTwoBasisDecomposer {
+    pub fn new_inner(
+        gate_a: String,
+        gate_matrix_a: ArrayView2<Complex64>,
+        gate_b: String,
+        gate_matrix_b: ArrayView2<Complex64>,
+        basis_fidelity: f64,
+        euler_basis: &str,
+    ) -> PyResult<Self> {
+        let decomposer_a = TwoQubitBasisDecomposer::new_inner(
+            gate_a,
+            gate_matrix_a,
+            basis_fidelity,
+            euler_basis,
+            None,
+            false,
+        )?;
+        let decomposer_b = TwoQubitBasisDecomposer::new_inner(
+            gate_b,
+            gate_matrix_b,
+            basis_fidelity,
+            euler_basis,
+            None,
+            false,
+        )?;
+        let swap_a = aview2(&SWAP_GATE).dot(&gate_matrix_a);
+        let swap_a_to_b = local_equivalence_maps_inner(gate_matrix_b, swap_a.view(), 1e-9)
+            .map_err(|_| {
+                QiskitError::new_err(
+                    "TwoQubitTwoBasisDecomposer: gate_b is not locally equivalent to swap times \
+                    gate_a",
+                )
+            })?;
+        Ok(TwoQubitTwoBasisDecomposer {
+            decomposer_a,
+            decomposer_b,
+            swap_a_to_b,
         })
     }
 
-    fn append_1q_sequence(
+    pub fn call_inner(
         &self,
-        gates: &mut TwoQubitSequenceVec,
-        global_phase: &mut f64,
         unitary: ArrayView2<Complex64>,
-        qubit: u8,
-    ) {
-        let target_1q_basis_list = vec![self.euler_basis];
-        let sequence = unitary_to_gate_sequence_inner(
-            unitary,
-            &target_1q_basis_list,
-            qubit as usize,
-            None,
-            true,
-            None,
-        );
-        if let Some(sequence) = sequence {
-            *global_phase += sequence.global_phase;
-            for gate in sequence.gates {
-                gates.push((gate.0, gate.1, smallvec![qubit]));
-            }
+        approximate: bool,
+    ) -> PyResult<TwoQubitGateSequence> {
+        let num_basis = |sequence: &TwoQubitGateSequence| {
+            sequence
+                .gates
+                .iter()
+                .filter(|gate| gate.2.len() == 2)
+                .count()
+        };
+        let sequence_a = self
+            .decomposer_a
+            .call_inner(unitary, None, approximate, None, None)?;
+        let sequence_b = self
+            .decomposer_b
+            .call_inner(unitary, None, approximate, None, None)?;
+        let single = if num_basis(&sequence_b) < num_basis(&sequence_a) {
+            sequence_b
+        } else {
+            sequence_a
+        };
+        if num_basis(&single) <= 2 {
+            return Ok(single);
+        }
+        let swapped = aview2(&SWAP_GATE).dot(&unitary);
+        let (swapped_decomposed, nbasis) =
+            self.decomposer_a
+                .target_and_nbasis(swapped.view(), None, approximate, None, None)?;
+        if nbasis > 2 {
+            return Ok(single);
         }
+        Ok(self.mixed_sequence(unitary, &swapped_decomposed))
     }
 
-    fn pulse_optimal_chooser(
+    /// The sequence ``A``, single-qubit gates, ``B`` for ``unitary``, given the Weyl
+    /// decomposition of ``SWAP unitary``.
+    fn mixed_sequence(
         &self,
-        best_nbasis: u8,
-        decomposition: &SmallVec<[Array2<Complex64>; 8]>,
-        target_decomposed: &TwoQubitWeylDecomposition,
-    ) -> PyResult<Option<TwoQubitGateSequence>> {
-        if self.pulse_optimize.is_some()
-            && (best_nbasis == 0 || best_nbasis == 1 || best_nbasis > 3)
-        {
-            return Ok(None);
-        }
-        match self.euler_basis {
-            EulerBasis::ZSX => (),
-            EulerBasis::ZSXX => (),
-            _ => {
-                if self.pulse_optimize.is_some() {
-                    return Err(QiskitError::new_err(format!(
-                        "'pulse_optimize' currently only works with ZSX basis ({} used)",
-                        self.euler_basis.as_str()
-                    )));
-                } else {
-                    return Ok(None);
-                }
+        unitary: ArrayView2<Complex64>,
+        swapped_decomposed: &TwoQubitWeylDecomposition,
+    ) -> TwoQubitGateSequence {
+        // Up to a global phase, SWAP U = (d5 x d4) A (d3 x d2) A (d1 x d0), so
+        // U = (d4 x d5) (SWAP A) (d3 x d2) A (d1 x d0)
+        //   = (d4 la x d5 lb) B (ra d3 x rb d2) A (d1 x d0).
+        let d = self
+            .decomposer_a
+            .decomposition_for_nbasis(swapped_decomposed, 2);
+        let [la, lb, ra, rb] = &self.swap_a_to_b;
+        let locals = [
+            d[0].clone(),
+            d[1].clone(),
+            rb.dot(&d[2]),
+            ra.dot(&d[3]),
+            d[5].dot(lb),
+            d[4].dot(la),
+        ];
+        let matrix_a = &self.decomposer_a.basis_decomposer.unitary_matrix;
+        let matrix_b = &self.decomposer_b.basis_decomposer.unitary_matrix;
+        let mixed = kron(&locals[5], &locals[4])
+            .dot(matrix_b)
+            .dot(&kron(&locals[3], &locals[2]))
+            .dot(matrix_a)
+            .dot(&kron(&locals[1], &locals[0]));
+        let trace: Complex64 = transpose_conjugate(mixed.view()).dot(&unitary).diag().sum();
+
+        let mut gates = Vec::with_capacity(21);
+        let mut global_phase = trace.arg();
+        for (i, decomposer) in [&self.decomposer_a, &self.decomposer_b].iter().enumerate() {
+            for qubit in 0..2 {
+                self.decomposer_a.append_1q_sequence(
+                    &mut gates,
+                    &mut global_phase,
+                    locals[2 * i + qubit].view(),
+                    qubit as u8,
+                );
             }
+            gates.push((decomposer.gate.clone(), smallvec![], smallvec![0, 1]));
         }
-        if self.gate != "cx" {
-            if self.pulse_optimize.is_some() {
-                return Err(QiskitError::new_err(
-                    "pulse_optimizer currently only works with CNOT entangling gate",
-                ));
-            } else {
-                return Ok(None);
-            }
+        for qubit in 0..2 {
+            self.decomposer_a.append_1q_sequence(
+                &mut gates,
+                &mut global_phase,
+                locals[4 + qubit].view(),
+                qubit as u8,
+            );
         }
-        let res = if best_nbasis == 3 {
-            self.get_sx_vz_3cx_efficient_euler(decomposition, target_decomposed)
-        } else if best_nbasis == 2 {
-            self.get_sx_vz_2cx_efficient_euler(decomposition, target_decomposed)
-        } else {
-            None
-        };
-        if self.pulse_optimize.is_some() && res.is_none() {
-            return Err(QiskitError::new_err(
-                "Failed to compute requested pulse optimal decomposition",
-            ));
+        TwoQubitGateSequence {
+            gates,
+            global_phase,
         }
-        Ok(res)
     }
 }
 
-static K12R_ARR: [[Complex64; 2]; 2] = [
-    [
-        Complex64::new(0., FRAC_1_SQRT_2),
-        Complex64::new(FRAC_1_SQRT_2, 0.),
-    ],
-    [
-        Complex64::new(-FRAC_1_SQRT_2, 0.),
-        Complex64::new(0., -FRAC_1_SQRT_2),
-    ],
-];
-
-static K12L_ARR: [[Complex64; 2]; 2] = [
-    [Complex64::new(0.5, 0.5), Complex64::new(0.5, 0.5)],
-    [Complex64::new(-0.5, 0.5), Complex64::new(0.5, -0.5)],
-];
-
-fn decomp0_inner(target: &TwoQubitWeylDecomposition) -> SmallVec<[Array2<Complex64>; 8]> {
-    smallvec![target.K1r.dot(&target.K2r), target.K1l.dot(&target.K2l),]
-}
-
 #[pymethods]
-impl TwoQubitBasisDecomposer {
-    fn __getnewargs__(&self, py: Python) -> (String, PyObject, f64, &str, Option<bool>) {
+impl TwoQubitTwoBasisDecomposer {
+    fn __getnewargs__(&self, py: Python) -> (String, PyObject, String, PyObject, f64, &str) {
+        let (a, b) = (&self.decomposer_a, &self.decomposer_b);
         (
-            self.gate.clone(),
-            self.basis_decomposer
+            a.gate.clone(),
+            a.basis_decomposer
                 .unitary_matrix
                 .to_pyarray_bound(py)
                 .into(),
-            self.basis_fidelity,
-            self.euler_basis.as_str(),
-            self.pulse_optimize,
+            b.gate.clone(),
+            b.basis_decomposer
+                .unitary_matrix
+                .to_pyarray_bound(py)
+                .into(),
+            a.basis_fidelity,
+            a.euler_basis.as_str(),
         )
     }
 
     #[new]
-    #[pyo3(signature=(gate, gate_matrix, basis_fidelity=1.0, euler_basis="U", pulse_optimize=None))]
+    #[pyo3(signature=(gate_a, gate_matrix_a, gate_b, gate_matrix_b, basis_fidelity=1.0, euler_basis="U"))]
     fn new(
-        gate: String,
-        gate_matrix: PyReadonlyArray2<Complex64>,
+        gate_a: String,
+        gate_matrix_a: PyReadonlyArray2<Complex64>,
+        gate_b: String,
+        gate_matrix_b: PyReadonlyArray2<Complex64>,
         basis_fidelity: f64,
         euler_basis: &str,
-        pulse_optimize: Option<bool>,
     ) -> PyResult<Self> {
-        let ipz: ArrayView2<Complex64> = aview2(&IPZ);
-        let basis_decomposer =
-            TwoQubitWeylDecomposition::new(gate_matrix, Some(DEFAULT_FIDELITY), None)?;
-        let super_controlled = relative_eq!(basis_decomposer.a, PI4, max_relative = 1e-09)
-            && relative_eq!(basis_decomposer.c, 0.0, max_relative = 1e-09);
+        TwoQubitTwoBasisDecomposer::new_inner(
+            gate_a,
+            gate_matrix_a.as_array(),
+            gate_b,
+            gate_matrix_b.as_array(),
+            basis_fidelity,
+            euler_basis,
+        )
+    }
 
-        // Create some useful matrices U1, U2, U3 are equivalent to the basis,
-        // expand as Ui = Ki1.Ubasis.Ki2
-        let b = basis_decomposer.b;
-        let temp = Complex64::new(0.5, -0.5);
-        let k11l = array![
-            [
-                temp * (Complex64::new(0., -1.) * Complex64::new(0., -b).exp()),
-                temp * Complex64::new(0., -b).exp()
-            ],
-            [
-                temp * (Complex64::new(0., -1.) * Complex64::new(0., b).exp()),
-                temp * -(Complex64::new(0., b).exp())
-            ],
-        ];
-        let k11r = array![
-            [
-                FRAC_1_SQRT_2 * (Complex64::new(0., 1.) * Complex64::new(0., -b).exp()),
-                FRAC_1_SQRT_2 * -Complex64::new(0., -b).exp()
-            ],
-            [
-                FRAC_1_SQRT_2 * Complex64::new(0., b).exp(),
-                FRAC_1_SQRT_2 * (Complex64::new(0., -1.) * Complex64::new(0., b).exp())
-            ],
-        ];
-        let k12l = aview2(&K12L_ARR);
-        let k12r = aview2(&K12R_ARR);
-        let k32l_k21l = array![
-            [
-                FRAC_1_SQRT_2 * Complex64::new(1., (2. * b).cos()),
-                FRAC_1_SQRT_2 * (Complex64::new(0., 1.) * (2. * b).sin())
-            ],
-            [
-                FRAC_1_SQRT_2 * (Complex64::new(0., 1.) * (2. * b).sin()),
-                FRAC_1_SQRT_2 * Complex64::new(1., -(2. * b).cos())
-            ],
-        ];
-        let temp = Complex64::new(0.5, 0.5);
-        let k21r = array![
-            [
-                temp * (Complex64::new(0., -1.) * Complex64::new(0., -2. * b).exp()),
-                temp * Complex64::new(0., -2. * b).exp()
-            ],
-            [
-                temp * (Complex64::new(0., 1.) * Complex64::new(0., 2. * b).exp()),
-                temp * Complex64::new(0., 2. * b).exp()
-            ],
-        ];
-        const K22L_ARR: [[Complex64; 2]; 2] = [
-            [
-                Complex64::new(FRAC_1_SQRT_2, 0.),
-                Complex64::new(-FRAC_1_SQRT_2, 0.),
-            ],
-            [
-                Complex64::new(FRAC_1_SQRT_2, 0.),
-                Complex64::new(FRAC_1_SQRT_2, 0.),
-            ],
-        ];
-        let k22l = aview2(&K22L_ARR);
-        let k22r_arr: [[Complex64; 2]; 2] = [
-            [Complex64::zero(), Complex64::new(1., 0.)],
-            [Complex64::new(-1., 0.), Complex64::zero()],
-        ];
-        let k22r = aview2(&k22r_arr);
-        let k31l = array![
-            [
-                FRAC_1_SQRT_2 * Complex64::new(0., -b).exp(),
-                FRAC_1_SQRT_2 * Complex64::new(0., -b).exp()
-            ],
-            [
-                FRAC_1_SQRT_2 * -Complex64::new(0., b).exp(),
-                FRAC_1_SQRT_2 * Complex64::new(0., b).exp()
-            ],
-        ];
-        let temp = Complex64::new(0., 1.);
-        let k31r = array![
-            [temp * Complex64::new(0., b).exp(), Complex64::zero()],
-            [Complex64::zero(), temp * -Complex64::new(0., -b).exp()],
-        ];
-        let temp = Complex64::new(0.5, 0.5);
-        let k32r = array![
-            [
-                temp * Complex64::new(0., b).exp(),
-                temp * -Complex64::new(0., -b).exp()
-            ],
-            [
-                temp * (Complex64::new(0., -1.) * Complex64::new(0., b).exp()),
-                temp * (Complex64::new(0., -1.) * Complex64::new(0., -b).exp())
-            ],
-        ];
-        let k1ld = transpose_conjugate(basis_decomposer.K1l.view());
-        let k1rd = transpose_conjugate(basis_decomposer.K1r.view());
-        let k2ld = transpose_conjugate(basis_decomposer.K2l.view());
-        let k2rd = transpose_conjugate(basis_decomposer.K2r.view());
-        // Pre-build the fixed parts of the matrices used in 3-part decomposition
-        let u0l = k31l.dot(&k1ld);
-        let u0r = k31r.dot(&k1rd);
-        let u1l = k2ld.dot(&k32l_k21l).dot(&k1ld);
-        let u1ra = k2rd.dot(&k32r);
-        let u1rb = k21r.dot(&k1rd);
-        let u2la = k2ld.dot(&k22l);
-        let u2lb = k11l.dot(&k1ld);
-        let u2ra = k2rd.dot(&k22r);
-        let u2rb = k11r.dot(&k1rd);
-        let u3l = k2ld.dot(&k12l);
-        let u3r = k2rd.dot(&k12r);
-        // Pre-build the fixed parts of the matrices used in the 2-part decomposition
-        let q0l = transpose_conjugate(k12l.view()).dot(&k1ld);
-        let q0r = transpose_conjugate(k12r.view()).dot(&ipz).dot(&k1rd);
-        let q1la = k2ld.dot(&transpose_conjugate(k11l.view()));
-        let q1lb = k11l.dot(&k1ld);
-        let q1ra = k2rd.dot(&ipz).dot(&transpose_conjugate(k11r.view()));
-        let q1rb = k11r.dot(&k1rd);
-        let q2l = k2ld.dot(&k12l);
-        let q2r = k2rd.dot(&k12r);
+    /// Decompose a two-qubit ``unitary`` into the two basis gates and single-qubit gates.
+    #[pyo3(signature = (unitary, approximate=true))]
+    fn __call__(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        approximate: bool,
+    ) -> PyResult<TwoQubitGateSequence> {
+        self.call_inner(unitary.as_array(), approximate)
+    }
+}
 
-        Ok(TwoQubitBasisDecomposer {
-            gate,
-            basis_fidelity,
-            euler_basis: EulerBasis::__new__(euler_basis)?,
-            pulse_optimize,
-            basis_decomposer,
-            super_controlled,
-            u0l,
-            u0r,
-            u1l,
-            u1ra,
-            u1rb,
-            u2la,
-            u2lb,
-            u2ra,
-            u2rb,
-            u3l,
-            u3r,
-            q0l,
-            q0r,
-            q1la,
-            q1lb,
-            q1ra,
-            q1rb,
-            q2l,
-            q2r,
-        })
+#[pymodule]
+pub fn two_qubit_decompose(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(_num_basis_gates))?;
+    m.add_wrapped(wrap_pyfunction!(kak_traces))?;
+    m.add_wrapped(wrap_pyfunction!(makhlin_from_weyl))?;
+    m.add_wrapped(wrap_pyfunction!(closest_partial_swap))?;
+    m.add_wrapped(wrap_pyfunction!(supercontrolled_basis_matrices))?;
+    m.add_wrapped(wrap_pyfunction!(weyl_interpolate))?;
+    m.add_wrapped(wrap_pyfunction!(locally_equivalent))?;
+    m.add_wrapped(wrap_pyfunction!(local_equivalence_maps))?;
+    m.add_wrapped(wrap_pyfunction!(distance_to_perfect_entangler))?;
+    m.add_wrapped(wrap_pyfunction!(gate_class))?;
+    m.add_wrapped(wrap_pyfunction!(two_qubit_clifford_circuit))?;
+    m.add_wrapped(wrap_pyfunction!(average_gate_infidelity))?;
+    m.add_wrapped(wrap_pyfunction!(synthesis_fidelity))?;
+    m.add_wrapped(wrap_pyfunction!(gate_sequence_unitary))?;
+    m.add_wrapped(wrap_pyfunction!(cluster_by_weyl))?;
+    m.add_wrapped(wrap_pyfunction!(best_single_rzz_angle))?;
+    m.add_wrapped(wrap_pyfunction!(best_fixed_angle_rzz))?;
+    m.add_wrapped(wrap_pyfunction!(weyl_coordinates_and_specialization))?;
+    m.add_wrapped(wrap_pyfunction!(ud_batch))?;
+    m.add_wrapped(wrap_pyfunction!(total_cx_for_blocks))?;
+    m.add_wrapped(wrap_pyfunction!(effective_cx_count))?;
+    m.add_wrapped(wrap_pyfunction!(fidelity_curve))?;
+    m.add_class::<TwoQubitGateSequence>()?;
+    m.add_class::<TwoQubitWeylDecomposition>()?;
+    m.add_class::<Specialization>()?;
+    m.add_class::<WeylBasis>()?;
+    m.add_class::<Endianness>()?;
+    m.add_class::<MultiplicationOrder>()?;
+    m.add_class::<SequenceOptions>()?;
+    m.add_class::<WeylCircuitOptions>()?;
+    m.add_class::<GateSource>()?;
+    m.add_class::<TwoQubitBasisDecomposer>()?;
+    m.add_class::<TwoQubitTwoBasisDecomposer>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert_2q_block_matrix::change_basis;
+    use approx::assert_abs_diff_eq;
+
+    /// Reference implementation of ``compute_unitary`` built from explicit Kronecker products.
+    fn compute_unitary_kron(
+        sequence: &TwoQubitSequenceVec,
+        global_phase: f64,
+    ) -> Array2<Complex64> {
+        let identity = aview2(&ONE_QUBIT_IDENTITY);
+        let mut matrix = Array2::<Complex64>::eye(4) * Complex64::new(0., global_phase).exp();
+        for (name, params, qubits) in sequence {
+            let op_matrix = match name.as_str() {
+                "sx" => aview2(&SX_GATE).to_owned(),
+                "x" => aview2(&X_GATE).to_owned(),
+                "rz" => rz_matrix(params[0]),
+                _ => aview2(&CX_GATE).to_owned(),
+            };
+            let op_matrix = match qubits.as_slice() {
+                [0] => kron(&identity, &op_matrix),
+                [1] => kron(&op_matrix, &identity),
+                [1, 0] => change_basis(op_matrix.view()),
+                _ => op_matrix,
+            };
+            matrix = op_matrix.dot(&matrix);
+        }
+        matrix
+    }
+
+    fn random_1q(rng: &mut Pcg64Mcg) -> Array2<Complex64> {
+        aview2(&u3_gate(
+            rng.gen_range(0. ..PI),
+            rng.gen_range(-PI..PI),
+            rng.gen_range(-PI..PI),
+        ))
+        .to_owned()
+    }
+
+    /// A random 2-qubit unitary :math:`K_1 U_d(a, b, c) K_2` with random local gates, whose
+    /// Weyl coordinates are drawn uniformly between 0 and the entries of ``max_coords``.
+    fn random_target(rng: &mut Pcg64Mcg, max_coords: [f64; 3]) -> Array2<Complex64> {
+        let k1 = kron(&random_1q(rng), &random_1q(rng));
+        let k2 = kron(&random_1q(rng), &random_1q(rng));
+        let [a, b, c] = max_coords.map(|max| rng.gen_range(0. ..=max));
+        k1.dot(&ud(a, b, c)).dot(&k2)
+    }
+
+    #[test]
+    fn synthesized_unitary_reproduces_target() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        for euler_basis in ["U", "ZSX", "ZYZ"] {
+            let decomposer = TwoQubitBasisDecomposer::new_inner(
+                "cx".to_string(),
+                aview2(&CX_GATE),
+                1.0,
+                euler_basis,
+                None,
+                false,
+            )
+            .unwrap();
+            for _ in 0..20 {
+                let target = random_target(&mut rng, [PI4; 3]);
+                let synthesized = decomposer
+                    .synthesized_unitary(target.view(), false)
+                    .unwrap();
+                assert_abs_diff_eq!(synthesized, target, epsilon = 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn call_inner_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<TwoQubitBasisDecomposer>();
+        assert_send_sync::<TwoQubitWeylDecomposition>();
+
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            "cx".to_string(),
+            aview2(&CX_GATE),
+            1.0,
+            "ZSX",
+            None,
+            false,
+        )
+        .unwrap();
+        let targets: Vec<Array2<Complex64>> =
+            (0..16).map(|_| random_target(&mut rng, [PI4; 3])).collect();
+        let synthesize = |target: &Array2<Complex64>| {
+            let sequence = decomposer
+                .call_inner(target.view(), None, false, None, None)
+                .unwrap();
+            (sequence.gates, sequence.global_phase)
+        };
+        let expected: Vec<_> = targets.iter().map(synthesize).collect();
+        let parallel: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = targets
+                .chunks(4)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(synthesize).collect::<Vec<_>>()))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        assert_eq!(parallel, expected);
+    }
+
+    #[test]
+    fn cz_pulse_optimal_reproduces_target() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            "cz".to_string(),
+            aview2(&CZ_GATE),
+            1.0,
+            "ZSX",
+            Some(true),
+            false,
+        )
+        .unwrap();
+        for b_max in [0., PI4] {
+            for _ in 0..10 {
+                let target = random_target(&mut rng, [PI4, b_max, b_max]);
+                let sequence = decomposer
+                    .call_inner(target.view(), None, true, None, None)
+                    .unwrap();
+                assert!(sequence
+                    .gates
+                    .iter()
+                    .all(|gate| ["sx", "x", "rz", "cz"].contains(&gate.0.as_str())));
+                assert_abs_diff_eq!(
+                    compute_unitary(&sequence.gates, sequence.global_phase).unwrap(),
+                    target,
+                    epsilon = 1e-10
+                );
+            }
+        }
     }
 
-    fn traces(&self, target: &TwoQubitWeylDecomposition) -> [Complex64; 4] {
-        [
-            4. * Complex64::new(
-                target.a.cos() * target.b.cos() * target.c.cos(),
-                target.a.sin() * target.b.sin() * target.c.sin(),
-            ),
-            4. * Complex64::new(
-                (PI4 - target.a).cos()
-                    * (self.basis_decomposer.b - target.b).cos()
-                    * target.c.cos(),
-                (PI4 - target.a).sin()
-                    * (self.basis_decomposer.b - target.b).sin()
-                    * target.c.sin(),
-            ),
-            Complex64::new(4. * target.c.cos(), 0.),
-            Complex64::new(4., 0.),
-        ]
+    #[test]
+    fn product_target_skips_diagonalization() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            "cx".to_string(),
+            aview2(&CX_GATE),
+            1.0,
+            "ZSX",
+            None,
+            false,
+        )
+        .unwrap();
+        for _ in 0..8 {
+            let phase = Complex64::new(0., rng.gen_range(-PI..PI)).exp();
+            let target = random_target(&mut rng, [0.; 3]) * phase;
+            let decomposed =
+                TwoQubitWeylDecomposition::new_inner(target.view(), Some(DEFAULT_FIDELITY), None)
+                    .unwrap();
+            assert_eq!(decomposed.diagonalization_iterations, 0);
+            let sequence = decomposer
+                .call_inner(target.view(), None, true, None, None)
+                .unwrap();
+            assert!(sequence.gates.iter().all(|gate| gate.2.len() == 1));
+            assert!(abs_diff_eq!(
+                compute_unitary(&sequence.gates, sequence.global_phase)
+                    .unwrap()
+                    .view(),
+                target.view(),
+                epsilon = 1e-10
+            ));
+        }
     }
 
-    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`0` uses of the basis gate.
-    /// Result :math:`U_r` has trace:
-    ///
-    /// .. math::
-    ///
-    ///     \Big\vert\text{Tr}(U_r\cdot U_\text{target}^{\dag})\Big\vert =
-    ///     4\Big\vert (\cos(x)\cos(y)\cos(z)+ j \sin(x)\sin(y)\sin(z)\Big\vert
-    ///
-    /// which is optimal for all targets and bases
-    #[staticmethod]
-    fn decomp0(py: Python, target: &TwoQubitWeylDecomposition) -> SmallVec<[PyObject; 2]> {
-        decomp0_inner(target)
-            .into_iter()
-            .map(|x| x.into_pyarray_bound(py).into())
-            .collect()
+    #[test]
+    fn weyl_gate_matrix_between_local_gates_reconstructs_target() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        for _ in 0..8 {
+            let target = random_target(&mut rng, [PI4; 3]);
+            let decomposed =
+                TwoQubitWeylDecomposition::new_inner(target.view(), Some(DEFAULT_FIDELITY), None)
+                    .unwrap();
+            let reconstructed = kron(&decomposed.K1l, &decomposed.K1r)
+                .dot(&decomposed.weyl_gate_matrix())
+                .dot(&kron(&decomposed.K2l, &decomposed.K2r));
+            assert_abs_diff_eq!(
+                reconstructed.view(),
+                decomposed.unitary_matrix.view(),
+                epsilon = 1e-10
+            );
+        }
     }
 
-    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`1` use of the basis gate
-    /// math:`\sim U_d(a, b, c)`.
-    /// Result :math:`U_r` has trace:
-    ///
-    /// .. math::
-    ///
-    ///     \Big\vert\text{Tr}(U_r \cdot U_\text{target}^{\dag})\Big\vert =
-    ///     4\Big\vert \cos(x-a)\cos(y-b)\cos(z-c) + j \sin(x-a)\sin(y-b)\sin(z-c)\Big\vert
-    ///
-    /// which is optimal for all targets and bases with ``z==0`` or ``c==0``.
-    fn decomp1(&self, py: Python, target: &TwoQubitWeylDecomposition) -> SmallVec<[PyObject; 4]> {
-        self.decomp1_inner(target)
-            .into_iter()
-            .map(|x| x.into_pyarray_bound(py).into())
-            .collect()
+    #[test]
+    fn compensated_phase_sum_keeps_small_terms() {
+        // Each term is below half an ulp of the initial phase, so plain summation drops them all.
+        let terms = vec![1.0e-16; 10_000];
+        let mut plain = PhaseSum::new(PI, false);
+        let mut compensated = PhaseSum::new(PI, true);
+        for term in &terms {
+            plain.add(*term);
+            compensated.add(*term);
+        }
+        let exact = PI + 1.0e-12;
+        assert_eq!(plain.value(), PI);
+        assert!((compensated.value() - exact).abs() < (plain.value() - exact).abs() / 100.);
     }
 
-    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`2` uses of the basis gate.
-    ///
-    /// For supercontrolled basis :math:`\sim U_d(\pi/4, b, 0)`, all b, result :math:`U_r` has trace
-    ///
-    /// .. math::
-    ///
-    ///     \Big\vert\text{Tr}(U_r \cdot U_\text{target}^\dag) \Big\vert = 4\cos(z)
-    ///
-    /// which is the optimal approximation for basis of CNOT-class :math:`\sim U_d(\pi/4, 0, 0)`
-    /// or DCNOT-class :math:`\sim U_d(\pi/4, \pi/4, 0)` and any target. It may
-    /// be sub-optimal for :math:`b \neq 0` (i.e. there exists an exact decomposition for any target
-    /// using :math:`B \sim U_d(\pi/4, \pi/8, 0)`, but it may not be this decomposition).
-    /// This is an exact decomposition for supercontrolled basis and target :math:`\sim U_d(x, y, 0)`.
-    /// No guarantees for non-supercontrolled basis.
-    fn decomp2_supercontrolled(
-        &self,
-        py: Python,
-        target: &TwoQubitWeylDecomposition,
-    ) -> SmallVec<[PyObject; 6]> {
-        self.decomp2_supercontrolled_inner(target)
-            .into_iter()
-            .map(|x| x.into_pyarray_bound(py).into())
-            .collect()
+    #[test]
+    fn swap_target_skips_diagonalization() {
+        let target = aview2(&SWAP_GATE).mapv(|x| x * Complex64::new(0., 0.7).exp());
+        for fidelity in [None, Some(DEFAULT_FIDELITY)] {
+            let decomposed = TwoQubitWeylDecomposition::new_inner_with_max_iterations(
+                target.view(),
+                fidelity,
+                None,
+                0,
+            )
+            .unwrap();
+            assert_eq!([decomposed.a, decomposed.b, decomposed.c], [PI4; 3]);
+            assert_eq!(decomposed.diagonalization_iterations, 0);
+            assert!(match fidelity {
+                Some(_) => matches!(decomposed.specialization, Specialization::SWAPEquiv),
+                None => matches!(decomposed.specialization, Specialization::General),
+            });
+            assert_abs_diff_eq!(
+                decomposed.weyl_gate_matrix().view(),
+                target.view(),
+                epsilon = 1e-12
+            );
+        }
+        // A requested fidelity low enough to select the identity takes the general path.
+        let decomposed =
+            TwoQubitWeylDecomposition::new_inner(target.view(), Some(0.3), None).unwrap();
+        assert!(matches!(decomposed.specialization, Specialization::IdEquiv));
     }
 
-    /// Decompose target with :math:`3` uses of the basis.
-    ///
-    /// This is an exact decomposition for supercontrolled basis :math:`\sim U_d(\pi/4, b, 0)`, all b,
-    /// and any target. No guarantees for non-supercontrolled basis.
-    fn decomp3_supercontrolled(
-        &self,
-        py: Python,
-        target: &TwoQubitWeylDecomposition,
-    ) -> SmallVec<[PyObject; 8]> {
-        self.decomp3_supercontrolled_inner(target)
-            .into_iter()
-            .map(|x| x.into_pyarray_bound(py).into())
-            .collect()
+    #[test]
+    fn tensor_factors_reconstruct_target() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        for _ in 0..8 {
+            let target = random_target(&mut rng, [PI4; 3]);
+            let decomposed =
+                TwoQubitWeylDecomposition::new_inner(target.view(), None, None).unwrap();
+            let (k1l, k1r, k2l, k2r, d) = decomposed.to_tensor_factors();
+            let core = magic_basis_transform(
+                Array2::from_diag(&arr1(&d)).view(),
+                MagicBasisTransform::Into,
+            );
+            let reconstructed = kron(&k1l, &k1r).dot(&core).dot(&kron(&k2l, &k2r));
+            assert_abs_diff_eq!(reconstructed.view(), target.view(), epsilon = 1e-10);
+        }
     }
 
-    /// Decompose a two-qubit ``unitary`` over fixed basis and :math:`SU(2)` using the best
-    /// approximation given that each basis application has a finite ``basis_fidelity``.
-    #[pyo3(signature = (unitary, basis_fidelity=None, approximate=true, _num_basis_uses=None))]
-    fn __call__(
-        &self,
-        unitary: PyReadonlyArray2<Complex64>,
-        basis_fidelity: Option<f64>,
-        approximate: bool,
-        _num_basis_uses: Option<u8>,
-    ) -> PyResult<TwoQubitGateSequence> {
-        let basis_fidelity = if !approximate {
-            1.0
-        } else {
-            basis_fidelity.unwrap_or(self.basis_fidelity)
-        };
-        let target_decomposed =
-            TwoQubitWeylDecomposition::new(unitary, Some(DEFAULT_FIDELITY), None)?;
-        let traces = self.traces(&target_decomposed);
-        let best_nbasis = traces
-            .into_iter()
-            .enumerate()
-            .map(|(idx, trace)| (idx, trace.trace_to_fid() * basis_fidelity.powi(idx as i32)))
-            .min_by(|(_idx1, fid1), (_idx2, fid2)| fid2.partial_cmp(fid1).unwrap())
-            .unwrap()
-            .0;
-        let best_nbasis = _num_basis_uses.unwrap_or(best_nbasis as u8);
-        let decomposition = match best_nbasis {
-            0 => decomp0_inner(&target_decomposed),
-            1 => self.decomp1_inner(&target_decomposed),
-            2 => self.decomp2_supercontrolled_inner(&target_decomposed),
-            3 => self.decomp3_supercontrolled_inner(&target_decomposed),
-            _ => unreachable!("Invalid basis to use"),
-        };
-        let pulse_optimize = self.pulse_optimize.unwrap_or(true);
-        let sequence = if pulse_optimize {
-            self.pulse_optimal_chooser(best_nbasis, &decomposition, &target_decomposed)?
-        } else {
-            None
-        };
-        if let Some(seq) = sequence {
-            return Ok(seq);
+    #[test]
+    fn parts_of_cx() {
+        let decomp = TwoQubitWeylDecomposition::new_inner(aview2(&CX_GATE), None, None).unwrap();
+        let ([a, b, c, _global_phase], [k1l, k1r, k2l, k2r]) = decomp.parts();
+        assert_abs_diff_eq!(a, PI4, epsilon = 1e-12);
+        assert_abs_diff_eq!(b, 0., epsilon = 1e-12);
+        assert_abs_diff_eq!(c, 0., epsilon = 1e-12);
+        assert!([k1l, k1r, k2l, k2r].iter().all(|k| k.shape() == [2, 2]));
+    }
+
+    #[test]
+    fn parts_reconstruct_target() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        for _ in 0..8 {
+            let target = random_target(&mut rng, [PI4, 0., 0.]);
+            let decomposed =
+                TwoQubitWeylDecomposition::new_inner(target.view(), None, None).unwrap();
+            let ([a, b, c, global_phase], [k1l, k1r, k2l, k2r]) = decomposed.parts();
+            let reconstructed = kron(&k1l, &k1r).dot(&ud(a, b, c)).dot(&kron(&k2l, &k2r))
+                * Complex64::new(0., global_phase).exp();
+            assert_abs_diff_eq!(reconstructed.view(), target.view(), epsilon = 1e-10);
         }
-        let target_1q_basis_list = vec![self.euler_basis];
-        let euler_decompositions: SmallVec<[Option<OneQubitGateSequence>; 8]> = decomposition
-            .iter()
-            .map(|decomp| {
-                unitary_to_gate_sequence_inner(
-                    decomp.view(),
-                    &target_1q_basis_list,
-                    0,
-                    None,
-                    true,
-                    None,
-                )
-            })
-            .collect();
-        // Worst case length is 5x 1q gates for each 1q decomposition + 1x 2q gate
-        // We might overallocate a bit if the euler basis is different but
-        // the worst case is just 16 extra elements with just a String and 2 smallvecs
-        // each. This is only transient though as the circuit sequences aren't long lived
-        // and are just used to create a QuantumCircuit or DAGCircuit when we return to
-        // Python space.
-        let mut gates = Vec::with_capacity(21);
-        let mut global_phase = target_decomposed.global_phase;
-        global_phase -= best_nbasis as f64 * self.basis_decomposer.global_phase;
-        if best_nbasis == 2 {
-            global_phase += PI;
+    }
+
+    #[test]
+    fn clifford_circuits_cover_the_clifford_group() {
+        let paulis: Vec<_> = (0..16).map(two_qubit_pauli).collect();
+        let circuits = two_qubit_clifford_circuits();
+        // The two-qubit Clifford group has 11520 elements up to a global phase.
+        assert_eq!(circuits.len(), 11520);
+        for (key, sequence) in circuits {
+            let matrix = compute_unitary(sequence, 0.).unwrap();
+            assert_eq!(
+                clifford_tableau_key(matrix.view(), &paulis, 1e-9),
+                Some(*key)
+            );
+            assert!(sequence.iter().filter(|gate| gate.2.len() == 2).count() <= 3);
         }
-        for i in 0..best_nbasis as usize {
-            if let Some(euler_decomp) = &euler_decompositions[2 * i] {
-                for gate in &euler_decomp.gates {
-                    gates.push((gate.0.clone(), gate.1.clone(), smallvec![0]));
+    }
+
+    #[test]
+    fn gate_sources_follow_emission_structure() {
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            "cx".to_string(),
+            aview2(&CX_GATE),
+            1.0,
+            "ZSX",
+            Some(false),
+            false,
+        )
+        .unwrap();
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let local = random_target(&mut rng, [0.; 3]);
+        let targets = [
+            local.clone(),
+            local.dot(&aview2(&CX_GATE)),
+            local.dot(&ud(0.5, 0.2, 0.)),
+            local.dot(&ud(0.6, 0.3, -0.1)),
+        ];
+        for (num_basis, target) in targets.iter().enumerate() {
+            let sequence = decomposer
+                .call_inner(target.view(), None, true, None, None)
+                .unwrap();
+            let sources = gate_sources(&sequence.gates);
+            assert_eq!(sources.len(), sequence.gates.len());
+            let mut seen_basis = 0;
+            for (gate, source) in sequence.gates.iter().zip(&sources) {
+                let expected = match (gate.2.as_slice(), seen_basis) {
+                    ([_, _], _) => GateSource::Basis,
+                    ([0], n) if n == num_basis => GateSource::K1r,
+                    ([1], n) if n == num_basis => GateSource::K1l,
+                    ([0], 0) => GateSource::K2r,
+                    ([1], 0) => GateSource::K2l,
+                    _ => GateSource::Weyl,
+                };
+                assert_eq!(*source, expected);
+                if gate.2.len() == 2 {
+                    seen_basis += 1;
                 }
-                global_phase += euler_decomp.global_phase
             }
-            if let Some(euler_decomp) = &euler_decompositions[2 * i + 1] {
-                for gate in &euler_decomp.gates {
-                    gates.push((gate.0.clone(), gate.1.clone(), smallvec![1]));
+            assert_eq!(seen_basis, num_basis);
+            if num_basis > 1 {
+                assert!(sources.contains(&GateSource::Weyl));
+            }
+        }
+    }
+
+    #[test]
+    fn segments_of_two_cx_sequence() {
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            "cx".to_string(),
+            aview2(&CX_GATE),
+            1.0,
+            "ZSX",
+            None,
+            false,
+        )
+        .unwrap();
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let target = random_target(&mut rng, [0.; 3]).dot(&ud(0.6, 0.3, 0.));
+        let sequence = decomposer
+            .call_inner(target.view(), None, false, None, None)
+            .unwrap();
+        let segments = sequence.segments();
+        let entanglers: Vec<usize> = segments
+            .iter()
+            .filter(|(kind, _)| *kind == SegmentKind::Entangler)
+            .map(|(_, range)| range.start)
+            .collect();
+        assert_eq!(entanglers.len(), 2);
+        // The segments tile the gates in order, and agree with the qubits of their gates.
+        let mut next = 0;
+        for (kind, range) in &segments {
+            assert_eq!(range.start, next);
+            assert!(range.end > range.start);
+            next = range.end;
+            for (_, _, qubits) in &sequence.gates[range.clone()] {
+                match kind {
+                    SegmentKind::Entangler => assert_eq!(qubits.len(), 2),
+                    SegmentKind::OneQubit(wire) => assert_eq!(qubits.as_slice(), &[*wire]),
                 }
-                global_phase += euler_decomp.global_phase
             }
-            gates.push((self.gate.clone(), smallvec![], smallvec![0, 1]));
         }
-        if let Some(euler_decomp) = &euler_decompositions[2 * best_nbasis as usize] {
-            for gate in &euler_decomp.gates {
-                gates.push((gate.0.clone(), gate.1.clone(), smallvec![0]));
+        assert_eq!(next, sequence.gates.len());
+        for pair in segments.windows(2) {
+            assert!(pair[0].0 != pair[1].0 || pair[0].0 == SegmentKind::Entangler);
+        }
+    }
+
+    #[test]
+    fn param_gates_round_trip() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            "cx".to_string(),
+            aview2(&CX_GATE),
+            1.0,
+            "ZSX",
+            None,
+            false,
+        )
+        .unwrap();
+        for _ in 0..10 {
+            let target = random_target(&mut rng, [PI4; 3]);
+            let sequence = decomposer
+                .call_inner(target.view(), None, false, None, None)
+                .unwrap();
+            let gates = sequence.gates_with_params();
+            assert!(gates
+                .iter()
+                .flat_map(|gate| gate.1.iter())
+                .all(|param| matches!(param, Param::Float(_))));
+            let round_trip =
+                TwoQubitGateSequence::from_gates_with_params(gates, sequence.global_phase).unwrap();
+            assert_eq!(round_trip.gates, sequence.gates);
+            assert_eq!(round_trip.global_phase, sequence.global_phase);
+        }
+    }
+
+    #[test]
+    fn supercontrolled_basis_matrices_match_decomposer() {
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            "cx".to_string(),
+            aview2(&CX_GATE),
+            1.0,
+            "U",
+            None,
+            false,
+        )
+        .unwrap();
+        let basis = &decomposer.basis_decomposer;
+        let k1ld = transpose_conjugate(basis.K1l.view());
+        let k1rd = transpose_conjugate(basis.K1r.view());
+        let k2ld = transpose_conjugate(basis.K2l.view());
+        let k2rd = transpose_conjugate(basis.K2r.view());
+        let m = SupercontrolledBasisMatrices::new(basis.b);
+        for (expected, actual) in [
+            (m.k31l.dot(&k1ld), &decomposer.u0l),
+            (m.k31r.dot(&k1rd), &decomposer.u0r),
+            (k2ld.dot(&m.k32l_k21l).dot(&k1ld), &decomposer.u1l),
+            (k2rd.dot(&m.k32r), &decomposer.u1ra),
+            (m.k21r.dot(&k1rd), &decomposer.u1rb),
+            (k2ld.dot(&m.k22l), &decomposer.u2la),
+            (m.k11l.dot(&k1ld), &decomposer.u2lb),
+            (k2rd.dot(&m.k22r), &decomposer.u2ra),
+            (m.k11r.dot(&k1rd), &decomposer.u2rb),
+            (k2ld.dot(&m.k12l), &decomposer.u3l),
+            (k2rd.dot(&m.k12r), &decomposer.u3r),
+        ] {
+            assert_abs_diff_eq!(&expected, actual, epsilon = 1e-14);
+        }
+        for (_, matrix) in SupercontrolledBasisMatrices::new(0.3).into_named() {
+            let product = transpose_conjugate(matrix.view()).dot(&matrix);
+            assert_abs_diff_eq!(product, Array2::<Complex64>::eye(2), epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn custom_basis_strategy_is_used() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        /// Delegates to the supercontrolled formulas, counting how often each is requested.
+        struct CountingStrategy(Arc<[AtomicUsize; 2]>);
+
+        impl BasisDecompStrategy for CountingStrategy {
+            fn decomp2(
+                &self,
+                decomposer: &TwoQubitBasisDecomposer,
+                target: &TwoQubitWeylDecomposition,
+            ) -> SmallVec<[Array2<Complex64>; 8]> {
+                self.0[0].fetch_add(1, Ordering::Relaxed);
+                SupercontrolledStrategy.decomp2(decomposer, target)
+            }
+
+            fn decomp3(
+                &self,
+                decomposer: &TwoQubitBasisDecomposer,
+                target: &TwoQubitWeylDecomposition,
+            ) -> SmallVec<[Array2<Complex64>; 8]> {
+                self.0[1].fetch_add(1, Ordering::Relaxed);
+                SupercontrolledStrategy.decomp3(decomposer, target)
             }
-            global_phase += euler_decomp.global_phase
         }
-        if let Some(euler_decomp) = &euler_decompositions[2 * best_nbasis as usize + 1] {
-            for gate in &euler_decomp.gates {
-                gates.push((gate.0.clone(), gate.1.clone(), smallvec![1]));
+
+        let calls = Arc::new([AtomicUsize::new(0), AtomicUsize::new(0)]);
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            "cx".to_string(),
+            aview2(&CX_GATE),
+            1.0,
+            "U",
+            None,
+            false,
+        )
+        .unwrap()
+        .with_strategy(Box::new(CountingStrategy(calls.clone())));
+        for (target, expected) in [(ud(0.6, 0.4, 0.), [1, 0]), (ud(0.6, 0.4, 0.2), [1, 1])] {
+            let synthesized = decomposer
+                .synthesized_unitary(target.view(), false)
+                .unwrap();
+            assert_abs_diff_eq!(synthesized, target, epsilon = 1e-10);
+            assert_eq!([0, 1].map(|i| calls[i].load(Ordering::Relaxed)), expected);
+        }
+    }
+
+    #[test]
+    fn merge_boundary_reduces_1q_gates() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            "cx".to_string(),
+            aview2(&CX_GATE),
+            1.0,
+            "ZSX",
+            None,
+            false,
+        )
+        .unwrap();
+        let count_1q = |sequence: &TwoQubitGateSequence| {
+            sequence
+                .gates
+                .iter()
+                .filter(|gate| gate.2.len() == 1)
+                .count()
+        };
+        for _ in 0..20 {
+            let [first, second] = [(); 2].map(|_| random_target(&mut rng, [PI4; 3]));
+            let mut merged = decomposer
+                .call_inner(first.view(), None, false, None, None)
+                .unwrap();
+            let next = decomposer
+                .call_inner(second.view(), None, false, None, None)
+                .unwrap();
+            let concatenated = count_1q(&merged) + count_1q(&next);
+            decomposer.merge_boundary(&mut merged, &next).unwrap();
+            assert!(count_1q(&merged) < concatenated);
+            assert_abs_diff_eq!(
+                decomposer.sequence_unitary(&merged).unwrap(),
+                second.dot(&first),
+                epsilon = 1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn synthesis_fidelity_catches_k1r_phase_errors() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            "cx".to_string(),
+            aview2(&CX_GATE),
+            1.0,
+            "U",
+            None,
+            false,
+        )
+        .unwrap();
+        for _ in 0..5 {
+            let target = random_target(&mut rng, [PI4; 3]);
+            let (mut target_decomposed, best_nbasis) = decomposer
+                .target_and_nbasis(target.view(), None, true, None, None)
+                .unwrap();
+            let fidelity = |target_decomposed: &TwoQubitWeylDecomposition| {
+                let synthesized = decomposer
+                    .decomposition_unitary(target_decomposed, best_nbasis)
+                    .unwrap();
+                average_gate_fidelity(synthesized.view(), target.view())
+            };
+            assert_abs_diff_eq!(fidelity(&target_decomposed), 1., epsilon = 1e-12);
+            // A wrong global phase alone leaves the fidelity unchanged...
+            target_decomposed.global_phase += 0.2;
+            assert_abs_diff_eq!(fidelity(&target_decomposed), 1., epsilon = 1e-12);
+            // ... but a flip into the Weyl chamber that multiplies K1r by `IPZ` without the
+            // matching change of the coordinates lowers it.
+            target_decomposed.K1r = target_decomposed.K1r.dot(&aview2(&IPZ));
+            assert!(fidelity(&target_decomposed) < 1. - 1e-3);
+        }
+    }
+
+    #[test]
+    fn optimize_1q_cancels_adjacent_gates() {
+        let gate = |name: &str, params: &[f64], qubits: &[u8]| {
+            (
+                name.to_string(),
+                SmallVec::from_slice(params),
+                SmallVec::from_slice(qubits),
+            )
+        };
+        let gates: TwoQubitSequenceVec = vec![
+            gate("sx", &[], &[0]),
+            gate("rz", &[0.3], &[1]),
+            gate("sxdg", &[], &[0]),
+            gate("rz", &[0.4], &[1]),
+            gate("x", &[], &[0]),
+            gate("cx", &[], &[0, 1]),
+            gate("x", &[], &[0]),
+            gate("s", &[], &[1]),
+            gate("t", &[], &[1]),
+            gate("tdg", &[], &[1]),
+            gate("sdg", &[], &[1]),
+            gate("rz", &[0.5], &[0]),
+            gate("rz", &[-0.5], &[0]),
+            gate("sx", &[], &[0]),
+        ];
+        let mut sequence = TwoQubitGateSequence {
+            gates: gates.clone(),
+            global_phase: 0.2,
+        };
+        sequence.optimize_1q();
+        // The `x` gates on either side of the `cx` don't cancel.
+        let names: Vec<&str> = sequence.gates.iter().map(|gate| gate.0.as_str()).collect();
+        assert_eq!(names, ["rz", "x", "cx", "x", "sx"]);
+        assert_abs_diff_eq!(sequence.gates[0].1[0], 0.7, epsilon = 1e-15);
+        assert_abs_diff_eq!(
+            sequence_to_unitary(&sequence).unwrap(),
+            compute_unitary(&gates, 0.2).unwrap(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn cached_gate_matrices_match_uncached() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let mut sequence: TwoQubitSequenceVec = Vec::new();
+        for i in 0..3 * GATE_MATRIX_CACHE_CAPACITY {
+            let angle = rng.gen_range(-PI..PI);
+            let gate = match i % 4 {
+                0 | 1 => ("rz".to_string(), smallvec![angle], smallvec![0]),
+                2 => ("u3".to_string(), smallvec![angle, 0.5, -0.], smallvec![1]),
+                _ => ("rzz".to_string(), smallvec![angle], smallvec![0, 1]),
+            };
+            // Every gate is repeated, so half of the lookups are hits.
+            sequence.push(gate.clone());
+            sequence.push(gate);
+        }
+        let mut expected = [[Complex64::zero(); 4]; 4];
+        for (i, row) in expected.iter_mut().enumerate() {
+            row[i] = Complex64::new(0., 0.2).exp();
+        }
+        for (name, params, qubits) in &sequence {
+            if qubits.len() == 2 {
+                let gate = two_qubit_gate_matrix(name, params).unwrap();
+                apply_2q_gate(&mut expected, &gate, qubits);
+            } else {
+                let gate = one_qubit_gate_matrix(name, params).unwrap();
+                apply_1q_gate(&mut expected, &gate, qubits[0]);
             }
-            global_phase += euler_decomp.global_phase
         }
-        Ok(TwoQubitGateSequence {
-            gates,
-            global_phase,
-        })
+        // The second time, the caches are already filled.
+        for _ in 0..2 {
+            assert_eq!(compute_unitary(&sequence, 0.2).unwrap(), aview2(&expected));
+        }
+        assert_eq!(
+            compute_unitary_inner(&sequence, 0.2, false).unwrap(),
+            aview2(&expected)
+        );
+        let one_qubit_len = || ONE_QUBIT_MATRIX_CACHE.with(|cache| cache.borrow().len());
+        let two_qubit_len = || TWO_QUBIT_MATRIX_CACHE.with(|cache| cache.borrow().len());
+        assert!(one_qubit_len() <= GATE_MATRIX_CACHE_CAPACITY);
+        assert!(two_qubit_len() <= GATE_MATRIX_CACHE_CAPACITY);
+        // A zero of either sign is looked up in the same entry.
+        let rx = |zero: f64| {
+            cached_gate_matrix(
+                &ONE_QUBIT_MATRIX_CACHE,
+                &CACHED_ONE_QUBIT_GATES,
+                "rx",
+                &[zero],
+                one_qubit_gate_matrix,
+            )
+            .unwrap()
+        };
+        rx(0.);
+        let len = one_qubit_len();
+        rx(-0.);
+        assert_eq!(one_qubit_len(), len);
     }
 
-    fn num_basis_gates(&self, unitary: PyReadonlyArray2<Complex64>) -> usize {
-        _num_basis_gates(self.basis_decomposer.b, self.basis_fidelity, unitary)
+    #[test]
+    fn compute_unitary_matches_kron() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        for _ in 0..50 {
+            let sequence: TwoQubitSequenceVec = (0..rng.gen_range(0..40))
+                .map(|_| {
+                    let qubit: u8 = rng.gen_range(0..2);
+                    match rng.gen_range(0..4) {
+                        0 => ("sx".to_string(), smallvec![], smallvec![qubit]),
+                        1 => ("x".to_string(), smallvec![], smallvec![qubit]),
+                        2 => (
+                            "rz".to_string(),
+                            smallvec![rng.gen_range(-PI..PI)],
+                            smallvec![qubit],
+                        ),
+                        _ => ("cx".to_string(), smallvec![], smallvec![qubit, 1 - qubit]),
+                    }
+                })
+                .collect();
+            let global_phase = rng.gen_range(-PI..PI);
+            assert_abs_diff_eq!(
+                compute_unitary(&sequence, global_phase).unwrap(),
+                compute_unitary_kron(&sequence, global_phase),
+                epsilon = 1e-12
+            );
+        }
     }
-}
 
-#[pymodule]
-pub fn two_qubit_decompose(m: &Bound<PyModule>) -> PyResult<()> {
-    m.add_wrapped(wrap_pyfunction!(_num_basis_gates))?;
-    m.add_class::<TwoQubitGateSequence>()?;
-    m.add_class::<TwoQubitWeylDecomposition>()?;
-    m.add_class::<Specialization>()?;
-    m.add_class::<TwoQubitBasisDecomposer>()?;
-    Ok(())
+    #[test]
+    fn sequence_to_unitary_reconstructs_decompositions() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let targets: Vec<Array2<Complex64>> =
+            (0..5).map(|_| random_target(&mut rng, [PI4; 3])).collect();
+        let bases: [(&str, &[[Complex64; 4]; 4], &str, Option<bool>); 5] = [
+            ("cx", &CX_GATE, "U", None),
+            ("cx", &CX_GATE, "ZSX", Some(true)),
+            ("cz", &CZ_GATE, "ZSX", Some(true)),
+            ("iswap", &ISWAP_GATE, "ZYZ", None),
+            ("ecr", &ECR_GATE, "XYX", None),
+        ];
+        for (gate, matrix, euler_basis, pulse_optimize) in bases {
+            let decomposer = TwoQubitBasisDecomposer::new_inner(
+                gate.to_string(),
+                aview2(matrix),
+                1.0,
+                euler_basis,
+                pulse_optimize,
+                false,
+            )
+            .unwrap();
+            for target in &targets {
+                let sequence = decomposer
+                    .call_inner(target.view(), None, true, None, None)
+                    .unwrap();
+                assert_abs_diff_eq!(
+                    sequence_to_unitary(&sequence).unwrap(),
+                    target,
+                    epsilon = 1e-10
+                );
+            }
+        }
+        let decomposer = TwoQubitTwoBasisDecomposer::new_inner(
+            "cx".to_string(),
+            aview2(&CX_GATE),
+            "iswap".to_string(),
+            aview2(&ISWAP_GATE),
+            1.0,
+            "ZYZ",
+        )
+        .unwrap();
+        let local = random_target(&mut rng, [0.; 3]);
+        let mixed = aview2(&ISWAP_GATE).dot(&local).dot(&aview2(&CX_GATE));
+        for target in targets.iter().chain([&mixed]) {
+            let sequence = decomposer.call_inner(target.view(), true).unwrap();
+            assert_abs_diff_eq!(
+                sequence_to_unitary(&sequence).unwrap(),
+                target,
+                epsilon = 1e-10
+            );
+        }
+        let unknown = TwoQubitGateSequence {
+            gates: vec![("USER_GATE".to_string(), smallvec![], smallvec![0, 1])],
+            global_phase: 0.,
+        };
+        assert!(sequence_to_unitary(&unknown).is_err());
+    }
 }
@@ -29,15 +29,18 @@ import math
 import io
 import base64
 import warnings
-from typing import Optional, Type, TYPE_CHECKING
+from collections import OrderedDict
+from typing import NamedTuple, Optional, Sequence, Type, TYPE_CHECKING
 
 import logging
 
 import numpy as np
 
-from qiskit.circuit import QuantumRegister, QuantumCircuit, Gate
+from qiskit.circuit import QuantumRegister, QuantumCircuit, Gate, Barrier
 from qiskit.circuit.library.standard_gates import (
     CXGate,
+    CZGate,
+    IGate,
     U3Gate,
     U2Gate,
     U1Gate,
@@ -46,20 +49,26 @@ from qiskit.circuit.library.standard_gates import (
     RXGate,
     RYGate,
     RZGate,
+    RZZGate,
     SXGate,
+    SXdgGate,
     XGate,
     RGate,
 )
 from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.operators import Operator
+from qiskit.quantum_info.operators.predicates import is_unitary_matrix
 from qiskit.synthesis.one_qubit.one_qubit_decompose import (
     OneQubitEulerDecomposer,
     DEFAULT_ATOL,
 )
 from qiskit.utils.deprecation import deprecate_func
 from qiskit._accelerate import two_qubit_decompose
+from qiskit._accelerate.euler_one_qubit_decomposer import EulerBasis
+from qiskit._accelerate.two_qubit_decompose import Endianness, WeylBasis
 
 if TYPE_CHECKING:
+    from qiskit.circuit import Qubit
     from qiskit.dagcircuit.dagcircuit import DAGCircuit
 
 logger = logging.getLogger(__name__)
@@ -67,8 +76,11 @@ logger = logging.getLogger(__name__)
 
 GATE_NAME_MAP = {
     "cx": CXGate,
+    "cz": CZGate,
+    "id": IGate,
     "rx": RXGate,
     "sx": SXGate,
+    "sxdg": SXdgGate,
     "x": XGate,
     "rz": RZGate,
     "u": UGate,
@@ -81,15 +93,82 @@ GATE_NAME_MAP = {
 }
 
 
-def decompose_two_qubit_product_gate(special_unitary_matrix: np.ndarray):
+class DecompositionCacheInfo(NamedTuple):
+    """Statistics of the decomposition cache of a :class:`.TwoQubitBasisDecomposer`."""
+
+    hits: int
+    misses: int
+    evictions: int
+    maxsize: int
+    currsize: int
+
+
+class _SequenceCache:
+    """A least-recently-used cache of synthesized gate sequences with at most ``maxsize``
+    entries."""
+
+    def __init__(self, maxsize):
+        self.maxsize = maxsize
+        self._entries = OrderedDict()
+        self.hits = 0
+        self.misses = 0
+        self.evictions = 0
+
+    def get(self, key):
+        """Return the sequence stored for ``key`` and mark it as recently used, or ``None``."""
+        sequence = self._entries.get(key)
+        if sequence is None:
+            self.misses += 1
+        else:
+            self.hits += 1
+            self._entries.move_to_end(key)
+        return sequence
+
+    def put(self, key, sequence):
+        """Store ``sequence`` for ``key``, evicting the least recently used entries if full."""
+        self._entries[key] = sequence
+        self._entries.move_to_end(key)
+        while len(self._entries) > self.maxsize:
+            self._entries.popitem(last=False)
+            self.evictions += 1
+
+    def clear(self):
+        """Remove all entries. The statistics are kept."""
+        self._entries.clear()
+
+    def info(self):
+        """Return the statistics of the cache."""
+        return DecompositionCacheInfo(
+            self.hits, self.misses, self.evictions, self.maxsize, len(self._entries)
+        )
+
+
+def _with_label(operation, label):
+    """Return a copy of ``operation`` with its label set to ``label``."""
+    operation = operation.to_mutable()
+    operation.label = label
+    return operation
+
+
+def decompose_two_qubit_product_gate(special_unitary_matrix: np.ndarray, gauge: str = "phase"):
     r"""Decompose :math:`U = U_l \otimes U_r` where :math:`U \in SU(4)`,
     and :math:`U_l,~U_r \in SU(2)`.
 
+    The split of the global phase between :math:`U_l`, :math:`U_r` and the returned phase is
+    arbitrary; ``gauge`` selects where it goes. With the default ``"phase"``, both factors are
+    special unitary and the phase is returned separately. With ``"left"`` or ``"right"`` it is
+    absorbed entirely into :math:`U_l` or :math:`U_r`, and with ``"split"`` it is divided evenly
+    between the two. In all cases but ``"phase"`` the returned phase is ``0``, and in all cases
+    :math:`U = e^{i \phi} U_l \otimes U_r`.
+
     Args:
         special_unitary_matrix: special unitary matrix to decompose
+        gauge: one of ``"phase"``, ``"left"``, ``"right"`` or ``"split"``.
     Raises:
-        QiskitError: if decomposition isn't possible.
+        QiskitError: if decomposition isn't possible, or ``gauge`` is not recognized.
     """
+    if gauge not in ("phase", "left", "right", "split"):
+        raise QiskitError(f"decompose_two_qubit_product_gate: unknown gauge '{gauge}'")
     special_unitary_matrix = np.asarray(special_unitary_matrix, dtype=complex)
     # extract the right component
     R = special_unitary_matrix[:2, :2].copy()
@@ -119,6 +198,13 @@ def decompose_two_qubit_product_gate(special_unitary_matrix: np.ndarray):
             f"deviation too large: {deviation}"
         )
 
+    if gauge == "left":
+        L, phase = L * cmath.exp(1j * phase), 0.0
+    elif gauge == "right":
+        R, phase = R * cmath.exp(1j * phase), 0.0
+    elif gauge == "split":
+        half = cmath.exp(0.5j * phase)
+        L, R, phase = L * half, R * half, 0.0
     return L, R, phase
 
 
@@ -154,6 +240,17 @@ class TwoQubitWeylDecomposition:
     chamber. If there is a high-symmetry gate "nearby" (in terms of the requested average gate fidelity),
     then it return a canonicalized decomposition of that high-symmetry gate.
 
+    If ``input_endianness`` is ``Endianness.Big``, ``unitary_matrix`` is taken to be in the
+    big-endian qubit ordering of most textbooks and many other frameworks, in which qubit 0 is the
+    most significant bit, and its qubits are swapped before it is decomposed. The decomposition
+    and :attr:`unitary_matrix` are then in Qiskit's little-endian ordering.
+
+    Unless the target is a local gate or its matrix :math:`M_2` is purely real or imaginary, the
+    decomposition diagonalizes random real combinations of :math:`M_2` until one succeeds, which
+    almost always takes a single attempt (see :attr:`diagonalization_iterations`). A
+    :class:`.QiskitError` is raised after ``max_diagonalization_iterations`` failed attempts;
+    lower it to fail fast in latency-sensitive contexts, or raise it for pathological inputs.
+
     References:
         1. Cross, A. W., Bishop, L. S., Sheldon, S., Nation, P. D. & Gambetta, J. M.,
            *Validating quantum computers using randomized model circuits*,
@@ -178,6 +275,7 @@ class TwoQubitWeylDecomposition:
     unitary_matrix: np.ndarray  # The unitary that was input
     requested_fidelity: Optional[float]  # None means no automatic specialization
     calculated_fidelity: float  # Fidelity after specialization
+    diagonalization_iterations: int  # Attempts to diagonalize M2, >1 near degeneracies, 0 if local
 
     _specializations = two_qubit_decompose.Specialization
 
@@ -187,11 +285,19 @@ class TwoQubitWeylDecomposition:
         fidelity: float | None = 1.0 - 1.0e-9,
         *,
         _specialization: two_qubit_decompose.Specialization | None = None,
+        input_endianness: Endianness = Endianness.Little,
+        max_diagonalization_iterations: int = 100,
     ):
         unitary_matrix = np.asarray(unitary_matrix, dtype=complex)
         self._inner_decomposition = two_qubit_decompose.TwoQubitWeylDecomposition(
-            unitary_matrix, fidelity=fidelity, _specialization=_specialization
+            unitary_matrix,
+            fidelity=fidelity,
+            _specialization=_specialization,
+            input_endianness=input_endianness,
+            max_diagonalization_iterations=max_diagonalization_iterations,
         )
+        if input_endianness == Endianness.Big:
+            unitary_matrix = self._inner_decomposition.unitary_matrix
         self.a = self._inner_decomposition.a
         self.b = self._inner_decomposition.b
         self.c = self._inner_decomposition.c
@@ -203,6 +309,7 @@ class TwoQubitWeylDecomposition:
         self.unitary_matrix = unitary_matrix
         self.requested_fidelity = fidelity
         self.calculated_fidelity = self._inner_decomposition.calculated_fidelity
+        self.diagonalization_iterations = self._inner_decomposition.diagonalization_iterations
         if logger.isEnabledFor(logging.DEBUG):
             actual_fidelity = self.actual_fidelity()
             logger.debug(
@@ -226,24 +333,161 @@ class TwoQubitWeylDecomposition:
         """
         raise NotImplementedError
 
+    @staticmethod
+    def classify(
+        unitary_matrix: np.ndarray, fidelity: float | None = 1.0 - 1.0e-9
+    ) -> two_qubit_decompose.Specialization:
+        r"""Return the specialization that would be chosen for ``unitary_matrix``.
+
+        Only the Weyl coordinates of ``unitary_matrix`` are computed and the specialization is
+        selected from them in the same way as :class:`.TwoQubitWeylDecomposition` does, without
+        building the decomposition. This is useful to inspect which specialization a gate falls
+        into at a given ``fidelity``.
+
+        Args:
+            unitary_matrix: The :math:`4 \times 4` unitary to classify.
+            fidelity: The fidelity a specialization must reach to be chosen. If ``None`` the
+                general case is always chosen.
+
+        Returns:
+            Specialization: The chosen specialization.
+        """
+        return two_qubit_decompose.TwoQubitWeylDecomposition.classify(
+            np.asarray(unitary_matrix, dtype=complex), fidelity
+        )
+
     def circuit(
-        self, *, euler_basis: str | None = None, simplify: bool = False, atol: float = DEFAULT_ATOL
+        self,
+        *,
+        euler_basis: str | None = None,
+        simplify: bool = False,
+        atol: float = DEFAULT_ATOL,
+        clifford: bool = False,
+        avoid_ryy: bool = False,
+        drop_atol: float | None = None,
+        basis: WeylBasis = WeylBasis.Rotations,
+        absorb_swap: bool = False,
+        high_precision: bool = False,
     ) -> QuantumCircuit:
-        """Returns Weyl decomposition in circuit form."""
-        circuit_sequence = self._inner_decomposition.circuit(
-            euler_basis=euler_basis, simplify=simplify, atol=atol
+        r"""Returns Weyl decomposition in circuit form.
+
+        If ``simplify`` is ``True``, the single-qubit gates are simplified to within ``atol``
+        and the interaction terms whose Weyl coordinate is at most ``drop_atol`` in magnitude are
+        left out.  ``drop_atol`` defaults to ``atol``.  Choosing a ``drop_atol`` that sits away
+        from the coordinates of the targets of interest keeps the number of two-qubit gates
+        stable when the targets are slightly perturbed, independently of the single-qubit
+        tolerance.
+
+        If ``clifford`` is ``True``, interaction terms with a Clifford angle of :math:`\pi/2`
+        are emitted as a :class:`.CZGate` dressed with :class:`.HGate`, :class:`.SGate` and
+        :class:`.SdgGate` instead of a continuous :class:`.RXXGate`, :class:`.RYYGate` or
+        :class:`.RZZGate`.
+
+        If ``avoid_ryy`` is ``True``, the :math:`YY` interaction is emitted as an
+        :class:`.RXXGate` conjugated by :class:`.SdgGate` and :class:`.SGate` on both qubits, so
+        that only :class:`.RXXGate` and :class:`.RZZGate` entanglers appear in the circuit.
+
+        If ``basis`` is ``WeylBasis.CXRZ``, every interaction is emitted as a :class:`.CXGate`,
+        :class:`.RZGate`, :class:`.CXGate` sequence (conjugated by :class:`.HGate`,
+        :class:`.SGate` and :class:`.SdgGate` for the :math:`XX` and :math:`YY` terms) and every
+        swap as three :class:`.CXGate`, so that no :class:`.RXXGate`, :class:`.RYYGate`,
+        :class:`.RZZGate` or :class:`.SwapGate` appears in the circuit. The default,
+        ``WeylBasis.Rotations``, emits the rotation gates.
+
+        If ``absorb_swap`` is ``True`` and the specialization is ``MirrorControlledEquiv`` or
+        ``SWAPEquiv``, the SWAP of the interaction part is left out and the final single-qubit
+        gates are applied to the exchanged qubits instead, so that the returned circuit followed
+        by a SWAP equals the circuit without ``absorb_swap``.  See :meth:`circuit_absorbing_swap`,
+        which also reports the implied permutation of the qubits.
+
+        If ``high_precision`` is ``True``, the phases of the single-qubit gates and of the
+        interaction part are added up to the global phase of the circuit with compensated (Kahan)
+        summation, which can be used to validate the global phase of the default summation.
+        """
+        options = two_qubit_decompose.WeylCircuitOptions(
+            simplify=simplify,
+            atol=atol,
+            clifford=clifford,
+            avoid_ryy=avoid_ryy,
+            drop_atol=drop_atol,
+            basis=basis,
+            absorb_swap=absorb_swap,
+            high_precision=high_precision,
         )
+        circuit_sequence = self._inner_decomposition.circuit(euler_basis, options)
         circ = QuantumCircuit(2, global_phase=circuit_sequence.global_phase)
         for name, params, qubits in circuit_sequence:
             getattr(circ, name)(*params, *qubits)
         return circ
 
+    def circuit_absorbing_swap(self, **kwargs) -> tuple[QuantumCircuit, list[int]]:
+        """Returns the Weyl decomposition in circuit form without the SWAP of its interaction
+        part, along with the permutation of the qubits the SWAP implied.
+
+        This lets a router absorb the SWAP of a ``MirrorControlledEquiv`` or ``SWAPEquiv``
+        decomposition by relabeling the qubits instead of executing it.
+
+        Args:
+            kwargs: Passed to :meth:`circuit`, with ``absorb_swap=True``.
+
+        Returns:
+            The circuit and the permutation ``[1, 0]`` if a SWAP was left out, or ``[0, 1]``
+            otherwise.  The circuit followed by a :class:`.PermutationGate` with this pattern
+            equals :meth:`circuit` with the same arguments.
+        """
+        circ = self.circuit(absorb_swap=True, **kwargs)
+        permutation = [1, 0] if self._inner_decomposition.has_swap else [0, 1]
+        return circ, permutation
+
+    def to_canonical_qasm_lines(self) -> list[str]:
+        """Returns the OpenQASM 3 statements of the canonical part of the decomposition.
+
+        The statements act on the qubits ``q[0]`` and ``q[1]``, using the ``rxx``, ``ryy`` and
+        ``rzz`` interactions (or ``swap`` for the specializations that use it).  The single-qubit
+        components and the global phase are not included.
+        """
+        return self._inner_decomposition.to_canonical_qasm_lines()
+
+    def euler_angles(self, euler_basis: str = "ZYZ") -> list[list[float]]:
+        """Returns the single-qubit components as Euler angles.
+
+        Args:
+            euler_basis: The single-qubit Euler basis to express the components in.
+
+        Returns:
+            The ``[theta, phi, lambda, phase]`` angles of ``K1l``, ``K1r``, ``K2l`` and ``K2r``,
+            in that order.
+        """
+        return self._inner_decomposition.euler_angles(EulerBasis(euler_basis))
+
     def actual_fidelity(self, **kwargs) -> float:
         """Calculates the actual fidelity of the decomposed circuit to the input unitary."""
         circ = self.circuit(**kwargs)
         trace = np.trace(Operator(circ).data.T.conj() @ self.unitary_matrix)
         return trace_to_fid(trace)
 
+    @property
+    def reconstructed_matrix(self) -> np.ndarray:
+        r"""The unitary :math:`e^{i\phi} ({K_1}^l \otimes {K_1}^r) U_d(a, b, c)
+        ({K_2}^l \otimes {K_2}^r)` realized by the decomposition.
+
+        This equals :attr:`unitary_matrix` up to the error of any specialization that was
+        applied, and is computed on each access.
+        """
+        k1 = np.kron(self.K1l, self.K1r)
+        k2 = np.kron(self.K2l, self.K2r)
+        return cmath.exp(1j * self.global_phase) * k1 @ Ud(self.a, self.b, self.c) @ k2
+
+    @property
+    def weyl_gate_matrix(self) -> np.ndarray:
+        r"""The entangling core :math:`e^{i\phi} U_d(a, b, c)` of the decomposition, without
+        the local gates.
+
+        Multiplying it by :math:`{K_1}^l \otimes {K_1}^r` on the left and
+        :math:`{K_2}^l \otimes {K_2}^r` on the right gives :attr:`reconstructed_matrix`.
+        """
+        return cmath.exp(1j * self.global_phase) * Ud(self.a, self.b, self.c)
+
     def __repr__(self):
         """Represent with enough precision to allow copy-paste debugging of all corner cases"""
         prefix = f"{type(self).__qualname__}.from_bytes("
@@ -306,7 +550,8 @@ class TwoQubitControlledUDecomposer:
             rxx_equivalent_gate: Gate that is locally equivalent to an :class:`.RXXGate`:
             :math:`U \sim U_d(\alpha, 0, 0) \sim \text{Ctrl-U}` gate.
         Raises:
-            QiskitError: If the gate is not locally equivalent to an :class:`.RXXGate`.
+            QiskitError: If the gate is not locally equivalent to an :class:`.RXXGate`, or its
+                matrix is not :math:`4 \times 4`.
         """
         atol = DEFAULT_ATOL
 
@@ -318,7 +563,13 @@ class TwoQubitControlledUDecomposer:
                 rxx_equivalent_gate(test_angle, label="foo")
             except TypeError as _:
                 raise QiskitError("Equivalent gate needs to take exactly 1 angle parameter.") from _
-            decomp = TwoQubitWeylDecomposition(rxx_equivalent_gate(test_angle))
+            matrix = np.asarray(rxx_equivalent_gate(test_angle), dtype=complex)
+            if matrix.shape != (4, 4):
+                raise QiskitError(
+                    f"{rxx_equivalent_gate.__name__} must have a 4x4 matrix, "
+                    f"got shape {matrix.shape}."
+                )
+            decomp = TwoQubitWeylDecomposition(matrix)
 
             circ = QuantumCircuit(2)
             circ.rxx(test_angle, 0, 1)
@@ -356,10 +607,21 @@ class TwoQubitControlledUDecomposer:
 
         self.rxx_equivalent_gate = rxx_equivalent_gate
 
-    def __call__(self, unitary, *, atol=DEFAULT_ATOL) -> QuantumCircuit:
-        """Returns the Weyl decomposition in circuit form.
+    def __call__(self, unitary, *, atol=DEFAULT_ATOL, verify: bool = False) -> QuantumCircuit:
+        r"""Returns the Weyl decomposition in circuit form.
 
         Note: atol ist passed to OneQubitEulerDecomposer.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            atol (float): Absolute tolerance passed to the single-qubit decomposer.
+            verify (bool): If ``True``, reconstruct the operator of the synthesized circuit and
+                compare it (including the global phase) against ``unitary``.  This is a debugging
+                aid for the sign and global-phase bookkeeping of :meth:`_weyl_gate`.
+
+        Raises:
+            QiskitError: if ``verify`` is ``True`` and the synthesized circuit does not reproduce
+                ``unitary``.
         """
 
         # pylint: disable=attribute-defined-outside-init
@@ -381,8 +643,48 @@ class TwoQubitControlledUDecomposer:
         self._weyl_gate(circ)
         circ.compose(c1r, [0], inplace=True)
         circ.compose(c1l, [1], inplace=True)
+        if verify:
+            self._verify(circ)
         return circ
 
+    def emitted_basis_angles(self, unitary, *, atol: float = 1.0e-13) -> list[float]:
+        r"""Return the angles the ``rxx_equivalent_gate`` is applied with to synthesize ``unitary``.
+
+        The angles are those of the equivalent gates for the :math:`XX`, :math:`YY` and
+        :math:`ZZ` interactions of the Weyl decomposition of ``unitary``, in the order they are
+        emitted by :meth:`__call__` and including :attr:`scale`. The :math:`YY` and :math:`ZZ`
+        interactions are omitted if their Weyl coordinate is within ``atol`` of zero, and the
+        gate for the :math:`ZZ` interaction is emitted inverted if :math:`c < 0`.  This allows
+        checking the angles against hardware limits before synthesizing ``unitary``.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            atol (float): Absolute tolerance below which the :math:`b` and :math:`c` Weyl
+                coordinates are treated as zero, as in the synthesis.
+
+        Returns:
+            The angles of each application of the ``rxx_equivalent_gate``.
+        """
+        decomposer = TwoQubitWeylDecomposition(unitary)
+        angles = [-2 * decomposer.a]
+        if abs(decomposer.b) > atol:
+            angles.append(-2 * decomposer.b)
+        if abs(decomposer.c) > atol:
+            angles.append(-2 * abs(decomposer.c))
+        return [self.scale * angle for angle in angles]
+
+    def _verify(self, circ: QuantumCircuit, atol: float = 1.0e-10):
+        """Raise if ``circ`` does not reproduce the unitary of the last decomposition."""
+        target = self.decomposer.unitary_matrix
+        deviation = np.max(np.abs(Operator(circ).data - target))
+        if deviation > atol:
+            raise QiskitError(
+                f"{self.__class__.__name__}: synthesized circuit deviates from the target by "
+                f"{deviation} for Weyl coordinates "
+                f"(a, b, c) = ({self.decomposer.a}, {self.decomposer.b}, {self.decomposer.c}) "
+                f"using {self.rxx_equivalent_gate.__name__}."
+            )
+
     def _to_rxx_gate(self, angle: float) -> QuantumCircuit:
         """
         Takes an angle and returns the circuit equivalent to an RXXGate with the
@@ -509,9 +811,32 @@ class TwoQubitBasisDecomposer:
             ``'PSX'``, ``'ZSX'``, ``'RR'``].
         pulse_optimize: If ``True``, try to do decomposition which minimizes
             local unitaries in between entangling gates. This will raise an exception if an
-            optimal decomposition is not implemented. Currently, only [{CX, SX, RZ}] is known.
+            optimal decomposition is not implemented. Currently, only [{CX, SX, RZ}] and
+            [{CZ, SX, RZ}] are known.
             If ``False``, don't attempt optimization. If ``None``, attempt optimization but don't raise
             if unknown.
+        euler_basis_candidates: An ordered list of Euler bases, with the same options as
+            ``euler_basis``. If given, every single-qubit block is synthesized in each of them
+            and the one with the fewest gates is kept, with ties going to the earlier basis, so
+            that the output can mix bases. ``pulse_optimize`` then requires ``'ZSX'`` or
+            ``'ZSXX'`` in the list.
+        cache_size: The maximum number of synthesized targets kept in a least-recently-used
+            cache, so that calling the decomposer again on a unitary with the same arguments
+            skips the synthesis. Only the gate sequences are stored, not the Weyl decompositions
+            of the targets, so each entry is small. ``0`` disables the cache. See
+            :meth:`cache_info` and :meth:`clear_cache`.
+        fixed_angle_sx: If ``True``, single-qubit blocks are only synthesized in the ``'ZSX'``
+            and ``'ZSXX'`` bases among ``euler_basis`` and ``euler_basis_candidates``, so that the
+            circuits contain only :class:`.RZGate`, :class:`.SXGate` and :class:`.XGate` besides
+            the basis gate, as needed by hardware with fixed-angle :math:`X` rotations. The
+            residual :math:`X` rotations of the pulse optimal synthesis are then never emitted
+            as an :class:`.RXGate`, whether or not ``pulse_optimize`` is set. This raises a
+            :class:`.QiskitError` if neither basis is given.
+        basis_matrix: The calibrated :math:`4 \times 4` matrix of ``gate``, if it differs from
+            the operator of ``gate``. The synthesis then uses this matrix for every application
+            of the basis gate, and ``gate`` is treated as a custom gate even if it is a
+            :class:`.CXGate` or :class:`.CZGate`, as the pulse optimal decompositions assume the
+            ideal matrices. See the ``emit_basis_matrix`` argument of :meth:`__call__`.
 
 
     .. automethod:: __call__
@@ -523,24 +848,40 @@ class TwoQubitBasisDecomposer:
         basis_fidelity: float = 1.0,
         euler_basis: str = "U",
         pulse_optimize: bool | None = None,
+        euler_basis_candidates: Sequence[str] | None = None,
+        cache_size: int = 0,
+        fixed_angle_sx: bool = False,
+        basis_matrix: np.ndarray | None = None,
     ):
+        if cache_size < 0:
+            raise QiskitError(f"cache_size must be non-negative, got {cache_size}")
         self.gate = gate
         self.basis_fidelity = basis_fidelity
         self.pulse_optimize = pulse_optimize
-        # Use cx as gate name for pulse optimal decomposition detection
+        self._cache = _SequenceCache(cache_size) if cache_size > 0 else None
+        self._basis_matrix = (
+            Operator(gate).data if basis_matrix is None else np.asarray(basis_matrix, dtype=complex)
+        )
+        # Use cx or cz as gate name for pulse optimal decomposition detection
         # otherwise use USER_GATE as a unique key to support custom gates
         # including parameterized gates like UnitaryGate.
-        if isinstance(gate, CXGate):
+        if basis_matrix is None and isinstance(gate, CXGate):
             gate_name = "cx"
+        elif basis_matrix is None and isinstance(gate, CZGate):
+            gate_name = "cz"
         else:
             gate_name = "USER_GATE"
 
         self._inner_decomposer = two_qubit_decompose.TwoQubitBasisDecomposer(
             gate_name,
-            Operator(gate).data,
+            self._basis_matrix,
             basis_fidelity=basis_fidelity,
             euler_basis=euler_basis,
             pulse_optimize=pulse_optimize,
+            euler_basis_candidates=(
+                None if euler_basis_candidates is None else list(euler_basis_candidates)
+            ),
+            fixed_angle_sx=fixed_angle_sx,
         )
         self.is_supercontrolled = self._inner_decomposer.super_controlled
         if not self.is_supercontrolled:
@@ -549,6 +890,38 @@ class TwoQubitBasisDecomposer:
                 stacklevel=2,
             )
 
+    @classmethod
+    def from_unitary(
+        cls,
+        gate_matrix: np.ndarray,
+        basis_fidelity: float = 1.0,
+        euler_basis: str = "U",
+        pulse_optimize: bool | None = None,
+    ) -> TwoQubitBasisDecomposer:
+        r"""Construct a decomposer whose KAK gate is given only by its matrix.
+
+        The matrix is wrapped in a :class:`.UnitaryGate`, which is used as the basis gate in the
+        synthesized circuits.
+
+        Args:
+            gate_matrix: :math:`4 \times 4` unitary matrix of the two-qubit basis gate.
+            basis_fidelity: Fidelity to be assumed for applications of the basis gate.
+            euler_basis: Basis string to be provided to :class:`.OneQubitEulerDecomposer`
+                for 1Q synthesis.
+            pulse_optimize: See the ``pulse_optimize`` argument of the constructor.
+
+        Returns:
+            TwoQubitBasisDecomposer: The decomposer for the given basis gate.
+        """
+        from qiskit.circuit.library.generalized_gates.unitary import UnitaryGate
+
+        return cls(
+            UnitaryGate(gate_matrix),
+            basis_fidelity=basis_fidelity,
+            euler_basis=euler_basis,
+            pulse_optimize=pulse_optimize,
+        )
+
     def num_basis_gates(self, unitary):
         """Computes the number of basis gates needed in
         a decomposition of input unitary
@@ -556,6 +929,193 @@ class TwoQubitBasisDecomposer:
         unitary = np.asarray(unitary, dtype=complex)
         return self._inner_decomposer.num_basis_gates(unitary)
 
+    def cache_info(self) -> DecompositionCacheInfo:
+        """Return the hits, misses and evictions of the decomposition cache, with its maximum
+        and current number of entries. All of them are 0 if the cache is disabled."""
+        if self._cache is None:
+            return DecompositionCacheInfo(0, 0, 0, 0, 0)
+        return self._cache.info()
+
+    def clear_cache(self):
+        """Remove all entries of the decomposition cache, keeping its statistics."""
+        if self._cache is not None:
+            self._cache.clear()
+
+    def num_basis_gates_batch(self, unitaries) -> np.ndarray:
+        """Computes :meth:`num_basis_gates` for each of a batch of 2-qubit unitaries.
+
+        Args:
+            unitaries: An array of shape ``(N, 4, 4)`` of the unitaries to count.
+
+        Returns:
+            An integer array of shape ``(N,)`` with the number of basis gates for each unitary.
+        """
+        unitaries = np.asarray(unitaries, dtype=complex)
+        return self._inner_decomposer.num_basis_gates_batch(unitaries)
+
+    def batch_report(
+        self,
+        unitaries,
+        basis_fidelity: float | None = None,
+        approximate: bool = True,
+    ) -> tuple[list[int], list[float]]:
+        """Report the synthesis of each of a batch of 2-qubit unitaries.
+
+        The targets are processed in parallel, which makes this suitable to gather statistics of
+        the basis gate counts and fidelities over large sets of targets.
+
+        Args:
+            unitaries: An array of shape ``(N, 4, 4)`` of the unitaries to report on.
+            basis_fidelity: Fidelity to be assumed for applications of the basis gate, as in
+                :meth:`__call__`.
+            approximate: Approximates if basis fidelities are less than 1.0, as in
+                :meth:`__call__`.
+
+        Returns:
+            The number of basis gates used for each unitary, and the expected fidelity of each
+            decomposition including the fidelity of the basis gates.
+        """
+        unitaries = np.asarray(unitaries, dtype=complex)
+        return self._inner_decomposer.batch_report(unitaries, basis_fidelity, approximate)
+
+    def to_circuits_batch(
+        self,
+        unitaries,
+        basis_fidelity: float | None = None,
+        approximate: bool = True,
+    ) -> list[QuantumCircuit]:
+        """Synthesize each of a batch of 2-qubit unitaries.
+
+        The gate sequences of all the targets are first computed in parallel without holding the
+        GIL, and only then turned into circuits, so this is faster than calling the decomposer
+        on each target in turn for large batches.
+
+        Args:
+            unitaries: An array of shape ``(N, 4, 4)`` of the unitaries to synthesize.
+            basis_fidelity: Fidelity to be assumed for applications of the basis gate, as in
+                :meth:`__call__`.
+            approximate: Approximates if basis fidelities are less than 1.0, as in
+                :meth:`__call__`.
+
+        Returns:
+            The synthesized circuits, in the order of ``unitaries``. Each is the circuit
+            :meth:`__call__` returns for the unitary with the same arguments.
+        """
+        unitaries = np.asarray(unitaries, dtype=complex)
+        sequences = self._inner_decomposer.generate_sequence_batch(
+            unitaries, basis_fidelity, approximate
+        )
+        return [self._sequence_to_circuit(sequence) for sequence in sequences]
+
+    def to_circuit_with_coords(
+        self,
+        unitary: Operator | np.ndarray,
+        basis_fidelity: float | None = None,
+        approximate: bool = True,
+    ) -> tuple[QuantumCircuit, tuple[float, float, float]]:
+        r"""Synthesize a 2-qubit unitary and return the Weyl coordinates it was synthesized for.
+
+        This is useful for logging and analysis, as the coordinates come from the same Weyl
+        decomposition as the circuit instead of being computed a second time.
+
+        Args:
+            unitary: :math:`4 \times 4` unitary to synthesize.
+            basis_fidelity: Fidelity to be assumed for applications of the basis gate, as in
+                :meth:`__call__`.
+            approximate: Approximates if basis fidelities are less than 1.0, as in
+                :meth:`__call__`.
+
+        Returns:
+            The circuit :meth:`__call__` returns for ``unitary`` with the same arguments, and the
+            Weyl coordinates ``(a, b, c)`` of ``unitary``.
+        """
+        sequence, coords = self._inner_decomposer.generate_sequence_with_coords(
+            np.asarray(unitary, dtype=complex), basis_fidelity, approximate
+        )
+        return self._sequence_to_circuit(sequence), tuple(coords)
+
+    def expected_basis_cost(
+        self, unitary: Operator | np.ndarray, basis_fidelity: float | None = None
+    ) -> float:
+        r"""Return a smooth estimate of the number of basis gates needed for ``unitary``.
+
+        Unlike :meth:`num_basis_gates`, the estimate interpolates between the candidate counts
+        when a competing decomposition has almost the same expected fidelity, which makes it
+        suitable for cost models that need a continuous cost.  With :math:`F_k` the expected
+        fidelity of the best decomposition using :math:`k` basis gates, including the basis
+        fidelity :math:`f` of each of them, the estimate is
+
+        .. math::
+
+            \sum_{k=0}^3 k\,w_k \Big/ \sum_{k=0}^3 w_k, \qquad
+            w_k = \exp\big((F_k - \max_j F_j) / (1 - f)\big).
+
+        For :math:`f = 1` this is the integer returned by :meth:`num_basis_gates`.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to estimate the cost of.
+            basis_fidelity (float or None): Fidelity to be assumed for applications of KAK Gate.
+                If given, overrides ``basis_fidelity`` given at init.
+
+        Returns:
+            float: The estimated number of basis gates, between 0 and 3.
+        """
+        return self._inner_decomposer.expected_basis_cost(
+            np.asarray(unitary, dtype=complex), basis_fidelity
+        )
+
+    def max_basis_gates_for_basis(self) -> int:
+        """Return the worst-case number of basis gate applications in a synthesized circuit.
+
+        For a supercontrolled basis gate any two-qubit unitary is synthesized exactly with at
+        most 3 applications. For other basis gates the decomposer also never uses more than 3
+        applications, but the decompositions using 2 or 3 of them are not exact in general.
+        """
+        return self._inner_decomposer.max_basis_gates_for_basis()
+
+    def basis_weyl_coordinates(self) -> tuple[float, float, float]:
+        r"""Return the Weyl coordinates :math:`(a, b, c)` of the basis gate.
+
+        For example, a :class:`.CXGate` basis has coordinates :math:`(\pi/4, 0, 0)`.
+        """
+        return tuple(self._inner_decomposer.basis_weyl_coordinates())
+
+    def best_direction_and_count(
+        self, unitary: Operator | np.ndarray, reverse_fidelity: float | None = None
+    ) -> tuple[int, list[int]]:
+        """Computes the number of basis gates needed in a decomposition of ``unitary`` and the
+        basis gate direction with the better fidelity.
+
+        Args:
+            unitary: The two-qubit unitary to decompose.
+            reverse_fidelity: The fidelity of the basis gate applied with its qubits reversed.
+                The ``basis_fidelity`` of the decomposer is taken as the fidelity of the basis
+                gate in its original ``[0, 1]`` direction. Defaults to ``basis_fidelity``.
+
+        Returns:
+            The number of basis gates and the ``[control, target]`` direction of the basis gate
+            with the better fidelity. The direction is only chosen when two or three basis gates
+            are needed, otherwise it is ``[0, 1]``.
+        """
+        unitary = np.asarray(unitary, dtype=complex)
+        return self._inner_decomposer.best_direction_and_count(unitary, reverse_fidelity)
+
+    def pulse_optimal_applicable(self, unitary: Operator | np.ndarray) -> bool:
+        """Check whether the pulse optimal decomposition will be attempted for ``unitary``.
+
+        This is the case when ``pulse_optimize`` is not ``False``, the Euler basis is ``ZSX`` or
+        ``ZSXX``, the basis gate is a :class:`.CXGate` or a :class:`.CZGate` and ``unitary`` needs
+        two or three basis gates.
+
+        Args:
+            unitary: The two-qubit unitary to decompose.
+
+        Returns:
+            Whether the pulse optimal decomposition will be attempted.
+        """
+        unitary = np.asarray(unitary, dtype=complex)
+        return self._inner_decomposer.pulse_optimal_applicable(unitary)
+
     @staticmethod
     def decomp0(target):
         r"""
@@ -601,7 +1161,9 @@ class TwoQubitBasisDecomposer:
         be sub-optimal for :math:`b \neq 0` (i.e. there exists an exact decomposition for any target
         using :math:`B \sim U_d(\pi/4, \pi/8, 0)`, but it may not be this decomposition).
         This is an exact decomposition for supercontrolled basis and target :math:`\sim U_d(x, y, 0)`.
-        No guarantees for non-supercontrolled basis.
+
+        Raises:
+            QiskitError: if the basis gate is not supercontrolled.
         """
         return self._inner_decomposer.decomp2_supercontrolled(target)
 
@@ -609,10 +1171,35 @@ class TwoQubitBasisDecomposer:
         r"""
         Decompose target with :math:`3` uses of the basis.
         This is an exact decomposition for supercontrolled basis :math:`\sim U_d(\pi/4, b, 0)`, all b,
-        and any target. No guarantees for non-supercontrolled basis.
+        and any target.
+
+        Raises:
+            QiskitError: if the basis gate is not supercontrolled.
         """
         return self._inner_decomposer.decomp3_supercontrolled(target)
 
+    def decomposition_locals(
+        self, unitary: Operator | np.ndarray, approximate: bool = True
+    ) -> list[np.ndarray]:
+        r"""Return the single-qubit matrices of the KAK decomposition of ``unitary``.
+
+        The number of basis gate applications is chosen as when the decomposer is called.  For
+        :math:`n` applications, the :math:`2n + 2` returned matrices are ordered as pairs
+        ``[qubit 0, qubit 1]``, the :math:`i`-th pair acting before the :math:`i`-th basis gate
+        and the last pair after the last basis gate.  These are the matrices passed to the
+        single-qubit synthesis, before any pulse optimization.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to decompose.
+            approximate (bool): Approximates if basis fidelities are less than 1.0.
+
+        Returns:
+            list[np.ndarray]: The :math:`2 \times 2` single-qubit matrices.
+        """
+        return self._inner_decomposer.decomposition_locals(
+            np.asarray(unitary, dtype=complex), approximate
+        )
+
     def __call__(
         self,
         unitary: Operator | np.ndarray,
@@ -621,6 +1208,23 @@ class TwoQubitBasisDecomposer:
         use_dag: bool = False,
         *,
         _num_basis_uses: int | None = None,
+        min_acceptable_fidelity: float | None = None,
+        balance_single_qubit: bool = False,
+        schedule: bool = False,
+        snap_angles: float | None = None,
+        absorb_global_phase: bool = False,
+        minimize_1q: bool = False,
+        euler_basis_q1_error: tuple[float, float] | None = None,
+        depth_optimal: bool = False,
+        use_power: bool = False,
+        insert_barriers: bool = False,
+        tag: str | None = None,
+        prefer_specialized: bool = False,
+        input_endianness: Endianness = Endianness.Little,
+        pad_identities: bool = False,
+        verify_atol: float | None = None,
+        optimize_1q: bool = False,
+        emit_basis_matrix: bool = False,
     ) -> QuantumCircuit | DAGCircuit:
         r"""Decompose a two-qubit ``unitary`` over fixed basis and :math:`SU(2)` using the best
         approximation given that each basis application has a finite ``basis_fidelity``.
@@ -633,55 +1237,566 @@ class TwoQubitBasisDecomposer:
             use_dag (bool): If true a :class:`.DAGCircuit` is returned instead of a
                 :class:`QuantumCircuit` when this class is called.
             _num_basis_uses (int): force a particular approximation by passing a number in [0, 3].
+            min_acceptable_fidelity (float or None): If given, raise an error instead of returning
+                a circuit when the expected fidelity of the best decomposition, including the
+                ``basis_fidelity`` of each basis gate application, is below this value.
+            balance_single_qubit (bool): If ``True``, single-qubit gates that commute with an
+                adjacent ``cx`` are moved across it to even out the number of single-qubit gates
+                on the two qubits between consecutive basis gates, reducing idle time.
+            schedule (bool): If ``True``, the single-qubit gates between consecutive basis gates
+                are reordered so that gates which can run in parallel on the two qubits are
+                adjacent in the output, without changing the order of the gates on each qubit.
+            snap_angles (float or None): If given, single-qubit gate angles within this tolerance
+                of an angle used earlier in the circuit are replaced by that angle, so fewer
+                distinct angles need to be calibrated. This introduces an approximation error
+                bounded by the tolerance per angle.
+            absorb_global_phase (bool): If ``True``, the global phase is realized by gates of the
+                Euler basis on the first qubit, so the output has a ``global_phase`` of zero.
+                This is useful for backends that ignore the global phase of a circuit.  Only the
+                ``'U'``, ``'U3'``, ``'U321'``, ``'PSX'`` and ``'U1X'`` bases can realize any
+                phase.  The other bases consist of rotations, which only realize multiples of
+                :math:`\pi`, and a :class:`.QiskitError` is raised for other phases.
+            minimize_1q (bool): If ``True``, the single-qubit gates are synthesized in each of the
+                decomposer's ``euler_basis``, ``"ZYZ"``, ``"ZXZ"`` and ``"ZSX"``, and the circuit
+                with the fewest single-qubit gates is returned.  This is useful when single-qubit
+                pulses rather than two-qubit gates are the bottleneck.
+            euler_basis_q1_error (tuple[float, float] or None): If given, the single-qubit gate
+                error rates of qubits 0 and 1, used to choose the Euler basis by error.  The same
+                Euler bases as for ``minimize_1q`` are tried, and the circuit whose single-qubit
+                gates have the smallest total error, weighted by the error rate of their qubit, is
+                returned.  This takes precedence over ``minimize_1q``.  No gate is moved to the
+                other qubit; only the Euler basis of the single-qubit gates is chosen.
+            depth_optimal (bool): If ``True`` and the target needs three applications of the
+                basis gate, the target is also synthesized with its qubits swapped, as the
+                inverse of its adjoint and, for a :class:`.CXGate` or :class:`.CZGate` basis with
+                ``euler_basis="ZSX"`` or ``"ZSXX"``, with the pulse optimal placement of
+                ``pulse_optimize``.  The circuit with the smallest depth is returned among those
+                using the same gates as the default circuit and, unless the basis gate is symmetric
+                under swapping its qubits, applying it in the same directions.
+            use_power (bool): If ``True`` and ``unitary`` equals :math:`B^k` up to a global phase,
+                for the basis gate :math:`B` and a nonzero integer or half-integer
+                :math:`|k| \le 3`, the circuit is the single gate ``gate.power(k)`` (or ``gate``
+                itself for :math:`k = 1`).  This is useful when powers of the basis gate, such as
+                :math:`\sqrt{i\text{SWAP}}` for an :class:`.iSwapGate` basis, are natively
+                available.
+            insert_barriers (bool): If ``True``, a :class:`.Barrier` on both qubits is placed
+                directly before and after each two-qubit gate, which visually groups the
+                single-qubit gates between the basis gates.  This does not change the operator.
+            tag (str or None): If given, every synthesized operation is labeled with ``tag``, so
+                later passes can tell which operations came from two-qubit synthesis.
+            prefer_specialized (bool): If ``True`` and ``approximate`` is ``True``, ``unitary`` is
+                first replaced by the most specialized form of its Weyl decomposition (see
+                :class:`.TwoQubitWeylDecomposition`) with a fidelity of at least ``1 - 1e-3`` to
+                it.  This trades a small approximation error for a decomposition with fewer
+                free parameters, which reduces the calibration burden, and possibly fewer basis
+                gates.  ``min_acceptable_fidelity`` is checked against the specialized target.
+            input_endianness (Endianness): The qubit ordering of ``unitary``.  If
+                ``Endianness.Big``, ``unitary`` is taken to be in the big-endian ordering of most
+                textbooks and many other frameworks, in which qubit 0 is the most significant bit,
+                and its qubits are swapped before it is decomposed.  The returned circuit always
+                uses Qiskit's little-endian ordering, so a big-endian matrix imported from another
+                framework is synthesized with the qubit labels it had there.
+            pad_identities (bool): If ``True``, the single-qubit gates between consecutive basis
+                gates are scheduled as for ``schedule`` and the idle positions of the less busy
+                qubit are filled with :class:`.IGate`, so both qubits have a gate in every layer.
+                This is meant for simulators with a fixed layout that expect a gate on every
+                qubit at every layer, and does not change the operator.
+            verify_atol (float): If given, the operator of the synthesized gates is reconstructed,
+                including the global phase, and compared against ``unitary`` before
+                ``pad_identities`` is applied.  This is meant for verification contexts in which
+                floating-point errors must stay below a known bound, typically with
+                ``approximate=False``.  A single power of the basis gate from ``use_power`` is not
+                reconstructed again.
+            optimize_1q (bool): If ``True``, adjacent single-qubit gates on the same qubit that
+                are inverses of each other, such as an :class:`.SXGate` followed by an
+                :class:`.SXdgGate`, are cancelled and adjacent :class:`.RZGate` are merged.  This
+                is applied after ``balance_single_qubit``, ``snap_angles`` and
+                ``absorb_global_phase``, which can leave such gates next to each other, and does
+                not change the operator.
+            emit_basis_matrix (bool): If ``True``, every application of the basis gate, or of a
+                power of it from ``use_power``, is emitted as a :class:`.UnitaryGate` of the
+                matrix used in the synthesis, which is the ``basis_matrix`` given at init if any,
+                instead of as ``gate``.  For a calibrated gate whose matrix differs from the
+                ideal one, the operator of the circuit then matches the synthesis, rather than
+                only doing so for the ideal gate.  This does not change the gate sequence, so it
+                doesn't affect the cache.
 
         Returns:
             QuantumCircuit: Synthesized quantum circuit.
 
         Raises:
-            QiskitError: if ``pulse_optimize`` is True but we don't know how to do it.
+            QiskitError: if ``pulse_optimize`` is True but we don't know how to do it, if the
+                expected fidelity is below ``min_acceptable_fidelity``, if ``absorb_global_phase``
+                is ``True`` and the Euler basis can't realize the global phase, or if an element
+                of the reconstructed operator differs from ``unitary`` by more than
+                ``verify_atol``.
         """
 
-        sequence = self._inner_decomposer(
-            np.asarray(unitary, dtype=complex),
-            basis_fidelity,
-            approximate,
-            _num_basis_uses=_num_basis_uses,
-        )
-        q = QuantumRegister(2)
+        unitary = np.asarray(unitary, dtype=complex)
+        options = {
+            "min_acceptable_fidelity": min_acceptable_fidelity,
+            "balance_single_qubit": balance_single_qubit,
+            "schedule": schedule,
+            "snap_angles": snap_angles,
+            "absorb_global_phase": absorb_global_phase,
+            "minimize_1q": minimize_1q,
+            "euler_basis_q1_error": (
+                None if euler_basis_q1_error is None else tuple(euler_basis_q1_error)
+            ),
+            "depth_optimal": depth_optimal,
+            "use_power": use_power,
+            "prefer_specialized": prefer_specialized,
+            "input_endianness": input_endianness,
+            "pad_identities": pad_identities,
+            "verify_atol": verify_atol,
+            "optimize_1q": optimize_1q,
+        }
+        sequence = None
+        if self._cache is not None:
+            key = (unitary.shape, unitary.tobytes(), basis_fidelity, approximate, _num_basis_uses)
+            # The enums from Rust aren't hashable, so they're keyed by their values.
+            key += tuple(
+                int(value) if isinstance(value, Endianness) else value for value in options.values()
+            )
+            sequence = self._cache.get(key)
+        if sequence is None:
+            sequence = self._inner_decomposer.generate_sequence(
+                unitary,
+                basis_fidelity,
+                approximate,
+                _num_basis_uses,
+                two_qubit_decompose.SequenceOptions(**options),
+            )
+            if self._cache is not None:
+                self._cache.put(key, sequence)
+        basis_gate = None
+        if emit_basis_matrix:
+            from qiskit.circuit.library.generalized_gates.unitary import UnitaryGate
+
+            basis_gate = UnitaryGate(self._basis_matrix, check_input=False)
+        q = QuantumRegister(2, "q")
         if use_dag:
             from qiskit.dagcircuit.dagcircuit import DAGCircuit
 
             dag = DAGCircuit()
-            dag.global_phase = sequence.global_phase
             dag.add_qreg(q)
-            for name, params, qubits in sequence:
-                if name == "USER_GATE":
-                    dag.apply_operation_back(self.gate, tuple(q[x] for x in qubits), check=False)
-                else:
-                    gate = GATE_NAME_MAP[name](*params)
-                    dag.apply_operation_back(gate, tuple(q[x] for x in qubits), check=False)
+            self._append_sequence_to_dag(
+                sequence, dag, (q[0], q[1]), insert_barriers, tag, basis_gate
+            )
             return dag
         else:
-            circ = QuantumCircuit(q, global_phase=sequence.global_phase)
-            for name, params, qubits in sequence:
-                try:
-                    getattr(circ, name)(*params, *qubits)
-                except AttributeError as exc:
-                    if name == "USER_GATE":
-                        circ.append(self.gate, qubits)
-                    elif name == "u3":
-                        gate = U3Gate(*params)
-                        circ.append(gate, qubits)
-                    elif name == "u2":
-                        gate = U2Gate(*params)
-                        circ.append(gate, qubits)
-                    elif name == "u1":
-                        gate = U1Gate(*params)
-                        circ.append(gate, qubits)
-                    else:
-                        raise QiskitError(f"Unknown gate {name}") from exc
-
-            return circ
+            return self._sequence_to_circuit(sequence, insert_barriers, tag, basis_gate)
+
+    def _sequence_to_circuit(self, sequence, insert_barriers=False, tag=None, basis_gate=None):
+        basis_gate = self.gate if basis_gate is None else basis_gate
+        circ = QuantumCircuit(QuantumRegister(2, "q"), global_phase=sequence.global_phase)
+        for name, params, qubits in sequence:
+            barrier = insert_barriers and len(qubits) == 2
+            if barrier:
+                circ.barrier()
+            try:
+                getattr(circ, name)(*params, *qubits)
+            except AttributeError as exc:
+                if name == "USER_GATE":
+                    circ.append(basis_gate, qubits)
+                elif name == "BASIS_GATE_POWER":
+                    circ.append(basis_gate.power(*params), qubits)
+                elif name == "u3":
+                    gate = U3Gate(*params)
+                    circ.append(gate, qubits)
+                elif name == "u2":
+                    gate = U2Gate(*params)
+                    circ.append(gate, qubits)
+                elif name == "u1":
+                    gate = U1Gate(*params)
+                    circ.append(gate, qubits)
+                else:
+                    raise QiskitError(f"Unknown gate {name}") from exc
+            if barrier:
+                circ.barrier()
+        if tag is not None:
+            for index, instruction in enumerate(circ.data):
+                circ.data[index] = instruction.replace(
+                    operation=_with_label(instruction.operation, tag)
+                )
+
+        return circ
+
+    def phase_breakdown(
+        self, unitary: Operator | np.ndarray, approximate: bool = True
+    ) -> tuple[QuantumCircuit, list[float]]:
+        r"""Decompose a two-qubit ``unitary`` and report where its global phase comes from.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            approximate (bool): Approximates if basis fidelities are less than 1.0.
+
+        Returns:
+            The synthesized circuit and its global phase split into
+            ``[phase_wire0, phase_wire1, phase_entangler]``.  The first two are the phases picked
+            up by the single-qubit gates on qubits 0 and 1, which can be merged with neighboring
+            single-qubit blocks on the same wire, and the last is the phase of the Weyl
+            decomposition of ``unitary`` and of the basis gates.  The three sum to the global
+            phase of the circuit.  If a pulse optimal decomposition is used, the full phase is
+            reported as ``phase_entangler``.
+        """
+        sequence, phases = self._inner_decomposer.phase_breakdown(
+            np.asarray(unitary, dtype=complex), approximate
+        )
+        return self._sequence_to_circuit(sequence), list(phases)
+
+    def exact_and_approx(
+        self, unitary: Operator | np.ndarray, cap: int
+    ) -> tuple[QuantumCircuit, QuantumCircuit, float, float]:
+        r"""Synthesize ``unitary`` exactly and with at most ``cap`` basis gates in one call.
+
+        Both circuits share a single Weyl decomposition of ``unitary``, which is cheaper than
+        calling the decomposer twice.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            cap (int): The maximum number of basis gates of the approximate circuit, in [0, 3].
+
+        Returns:
+            tuple: The exact circuit, the capped circuit and the fidelities of each of them to
+            ``unitary``, assuming ideal basis gates.
+
+        Raises:
+            QiskitError: if ``cap`` is larger than 3.
+        """
+        exact, capped, exact_fidelity, capped_fidelity = self._inner_decomposer.exact_and_approx(
+            np.asarray(unitary, dtype=complex), cap
+        )
+        return (
+            self._sequence_to_circuit(exact),
+            self._sequence_to_circuit(capped),
+            exact_fidelity,
+            capped_fidelity,
+        )
+
+    def all_budget_circuits(
+        self, unitary: Operator | np.ndarray
+    ) -> tuple[list[QuantumCircuit], list[float]]:
+        r"""Synthesize ``unitary`` with each of 0, 1, 2 and 3 basis gates in one call.
+
+        All four circuits share a single Weyl decomposition of ``unitary``, which is cheaper than
+        calling the decomposer once per number of basis gates, for example to build a lookup
+        table indexed by the allowed number of basis gates.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+
+        Returns:
+            tuple: The four circuits, where the circuit at index ``k`` uses ``k`` basis gates,
+            and the fidelity of each of them to ``unitary``, assuming ideal basis gates.
+        """
+        sequences, fidelities = self._inner_decomposer.all_budget_circuits(
+            np.asarray(unitary, dtype=complex)
+        )
+        return [self._sequence_to_circuit(sequence) for sequence in sequences], list(fidelities)
+
+    def to_circuit_absorbing_diagonal(
+        self,
+        unitary: Operator | np.ndarray,
+        diag: Sequence[complex],
+        approximate: bool = True,
+    ) -> QuantumCircuit:
+        r"""Synthesize ``unitary`` up to a known diagonal applied after the circuit.
+
+        The returned circuit implements :math:`D^{-1} U`, where :math:`D` is ``diag``, so that
+        following it with the diagonal gate reproduces ``unitary``. When the diagonal is applied
+        anyway, for example because it can be merged into a neighboring gate, this can need fewer
+        basis gates than synthesizing ``unitary`` itself.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            diag (Sequence[complex]): The 4 entries of the diagonal applied after the circuit,
+                which must all have modulus 1.
+            approximate (bool): Approximates if basis fidelities are less than 1.0.
+
+        Returns:
+            QuantumCircuit: The synthesized circuit, not including the diagonal.
+
+        Raises:
+            QiskitError: if an entry of ``diag`` doesn't have modulus 1, so that the diagonal
+                isn't unitary.
+        """
+        sequence = self._inner_decomposer.generate_sequence_absorbing_diagonal(
+            np.asarray(unitary, dtype=complex),
+            [complex(x) for x in diag],
+            approximate,
+        )
+        return self._sequence_to_circuit(sequence)
+
+    def to_circuit_with_durations(
+        self,
+        unitary: Operator | np.ndarray,
+        durations: dict[str, float],
+        approximate: bool = True,
+    ) -> tuple[QuantumCircuit, float]:
+        r"""Synthesize ``unitary`` and compute the duration of the resulting circuit.
+
+        The duration is the length of the critical path of the circuit: gates on different
+        qubits run in parallel, and a two-qubit gate starts once both of its qubits are free.
+        This allows comparing synthesis options by their time rather than their gate count.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            durations (dict[str, float]): The duration of each gate, keyed by gate name.
+            approximate (bool): Approximates if basis fidelities are less than 1.0.
+
+        Returns:
+            The synthesized circuit and its duration.
+
+        Raises:
+            QiskitError: if ``durations`` is missing a gate used by the circuit.
+        """
+        durations = dict(durations)
+        if self.gate.name in durations:
+            durations["USER_GATE"] = durations[self.gate.name]
+        sequence, duration = self._inner_decomposer.generate_sequence_with_durations(
+            np.asarray(unitary, dtype=complex), durations, approximate
+        )
+        return self._sequence_to_circuit(sequence), duration
+
+    def to_circuit_with_fidelity(
+        self,
+        unitary: Operator | np.ndarray,
+        basis_fidelity: float | None = None,
+        approximate: bool = True,
+        *,
+        force_nbasis_best_effort: int | None = None,
+    ) -> tuple[QuantumCircuit, float]:
+        r"""Synthesize ``unitary`` and compute the fidelity of the resulting circuit to it.
+
+        With ``force_nbasis_best_effort``, the circuit uses exactly that many basis gates, and is
+        the highest-fidelity approximation of ``unitary`` with that many of them, regardless of
+        ``basis_fidelity`` and ``approximate``. This differs from ``_num_basis_uses`` in
+        :meth:`__call__`, which is meant for counts at which the synthesis is exact, in that the
+        achieved fidelity is reported.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            basis_fidelity (float or None): Fidelity to be assumed for applications of the
+                basis gate when choosing their number.
+            approximate (bool): Approximates if basis fidelities are less than 1.0.
+            force_nbasis_best_effort (int or None): The number of basis gates to use, in
+                [0, 3].
+
+        Returns:
+            The synthesized circuit and its fidelity to ``unitary``, assuming ideal basis gates.
+
+        Raises:
+            QiskitError: if ``force_nbasis_best_effort`` is larger than 3.
+        """
+        sequence, fidelity = self._inner_decomposer.generate_sequence_with_fidelity(
+            np.asarray(unitary, dtype=complex),
+            basis_fidelity,
+            approximate,
+            force_nbasis_best_effort=force_nbasis_best_effort,
+        )
+        return self._sequence_to_circuit(sequence), fidelity
+
+    def to_circuit_with_specialization(
+        self,
+        unitary: Operator | np.ndarray,
+        basis_fidelity: float | None = None,
+        approximate: bool = True,
+    ) -> tuple[QuantumCircuit, two_qubit_decompose.Specialization]:
+        r"""Synthesize ``unitary`` and report the specialization of its Weyl decomposition.
+
+        The specialization is the one :class:`.TwoQubitWeylDecomposition` chose for
+        ``unitary``, from which the circuit was built. It explains, for example, why a target
+        locally equivalent to a SWAP or with fewer free Weyl coordinates synthesized differently
+        from a generic one.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            basis_fidelity (float or None): Fidelity to be assumed for applications of the
+                basis gate when choosing their number.
+            approximate (bool): Approximates if basis fidelities are less than 1.0.
+
+        Returns:
+            The synthesized circuit and the specialization of the decomposition of ``unitary``.
+        """
+        sequence, specialization = self._inner_decomposer.generate_sequence_with_specialization(
+            np.asarray(unitary, dtype=complex), basis_fidelity, approximate
+        )
+        return self._sequence_to_circuit(sequence), specialization
+
+    def noise_optimal_circuit(
+        self, unitary: Operator | np.ndarray, cx_error: float
+    ) -> tuple[QuantumCircuit, int]:
+        r"""Synthesize ``unitary`` with the number of basis gates that minimizes the total error.
+
+        Using one more basis gate reduces the approximation error of the synthesis, but adds the
+        error of that gate. With :math:`F_k` the fidelity of the best approximation of
+        ``unitary`` with :math:`k` basis gates, the count maximizing
+        :math:`F_k (1 - \epsilon)^k` is used, where :math:`\epsilon` is ``cx_error``. For
+        example, a target needing 3 basis gates for an exact synthesis is approximated with 2
+        when the infidelity of that approximation is lower than the error of the third gate.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            cx_error (float): The error rate of the basis gate, in :math:`[0, 1)`.
+
+        Returns:
+            The synthesized circuit and the number of basis gates it uses.
+
+        Raises:
+            QiskitError: if ``cx_error`` is not in :math:`[0, 1)`.
+        """
+        sequence, num_basis_gates = self._inner_decomposer.noise_optimal_sequence(
+            np.asarray(unitary, dtype=complex), cx_error
+        )
+        return self._sequence_to_circuit(sequence), num_basis_gates
+
+    def try_directional(
+        self,
+        unitary: Operator | np.ndarray,
+        allowed_direction: tuple[int, int],
+        max_basis: int,
+    ) -> QuantumCircuit | None:
+        r"""Synthesize ``unitary`` with every basis gate applied in a single direction.
+
+        This is meant for coupling maps where the basis gate is only available in one direction
+        between two qubits. The synthesis is exact and doesn't add gates to reverse the basis
+        gates afterwards, so it's possible to check whether a target fits a budget of basis
+        gates before routing it.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            allowed_direction (tuple[int, int]): The qubits every basis gate of the circuit acts
+                on, in order, either ``(0, 1)`` or ``(1, 0)``.
+            max_basis (int): The maximum number of basis gates of the circuit.
+
+        Returns:
+            The synthesized circuit, or ``None`` if ``unitary`` can't be synthesized with at
+            most ``max_basis`` basis gates in the direction ``allowed_direction``.
+
+        Raises:
+            QiskitError: if ``allowed_direction`` is neither ``(0, 1)`` nor ``(1, 0)``.
+        """
+        sequence = self._inner_decomposer.try_directional(
+            np.asarray(unitary, dtype=complex), allowed_direction, max_basis
+        )
+        if sequence is None:
+            return None
+        return self._sequence_to_circuit(sequence)
+
+    def synthesize_weyl_only(
+        self, a: float, b: float, c: float, approximate: bool = True
+    ) -> QuantumCircuit:
+        r"""Synthesize only the entangling core :math:`U_d(a, b, c)` of a decomposition.
+
+        This is meant for callers that only need a circuit locally equivalent to a target with
+        Weyl coordinates :math:`(a, b, c)`, and fix up the single-qubit gates elsewhere.
+
+        Args:
+            a (float): The first Weyl coordinate.
+            b (float): The second Weyl coordinate.
+            c (float): The third Weyl coordinate.
+            approximate (bool): Approximates if the basis fidelity is less than 1.0.
+
+        Returns:
+            A circuit implementing :math:`e^{i (a XX + b YY + c ZZ)}`.
+        """
+        sequence = self._inner_decomposer.synthesize_weyl_only(a, b, c, approximate)
+        return self._sequence_to_circuit(sequence)
+
+    def approximation_error_channel(
+        self,
+        unitary: Operator | np.ndarray,
+        basis_fidelity: float | None = None,
+        approximate: bool = True,
+        *,
+        force_nbasis_best_effort: int | None = None,
+    ) -> float:
+        r"""Estimate the error of synthesizing ``unitary`` as a depolarizing channel.
+
+        This returns the parameter :math:`p` of the depolarizing channel
+        :math:`\rho \mapsto (1 - p) \rho + p I / 4` with the same average gate fidelity
+        :math:`F` as the circuit returned by :meth:`to_circuit_with_fidelity` with the same
+        arguments, that is :math:`p = \frac{4}{3} (1 - F)`. It only accounts for the
+        approximation error of the synthesis, not for errors of the basis gates, so it can be
+        added to a noise model alongside them.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            basis_fidelity (float or None): Fidelity to be assumed for applications of the
+                basis gate when choosing their number.
+            approximate (bool): Approximates if basis fidelities are less than 1.0.
+            force_nbasis_best_effort (int or None): The number of basis gates to use, in
+                [0, 3].
+
+        Returns:
+            The depolarizing parameter, which is 0 for an exact synthesis.
+
+        Raises:
+            QiskitError: if ``force_nbasis_best_effort`` is larger than 3.
+        """
+        return self._inner_decomposer.approximation_error_channel(
+            np.asarray(unitary, dtype=complex),
+            basis_fidelity,
+            approximate,
+            force_nbasis_best_effort=force_nbasis_best_effort,
+        )
+
+    def append_into_dag(
+        self,
+        unitary: Operator | np.ndarray,
+        dag: DAGCircuit,
+        qubits: Sequence[Qubit],
+        basis_fidelity: float | None = None,
+        approximate: bool = True,
+        tag: str | None = None,
+    ):
+        r"""Synthesize a two-qubit ``unitary`` and append the result to an existing ``dag``.
+
+        This avoids building a temporary two-qubit :class:`.DAGCircuit` only to splice it into
+        a larger one.
+
+        Args:
+            unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+            dag (DAGCircuit): The DAG to append the synthesized operations to. Its global phase
+                is updated by the global phase of the decomposition.
+            qubits (Sequence[Qubit]): The two qubits of ``dag`` the ``unitary`` acts on, with
+                ``qubits[0]`` as the least-significant qubit of ``unitary``.
+            basis_fidelity (float or None): Fidelity to be assumed for applications of KAK Gate.
+                If given, overrides ``basis_fidelity`` given at init.
+            approximate (bool): Approximates if basis fidelities are less than 1.0.
+            tag (str or None): If given, every appended operation is labeled with ``tag``.
+
+        Raises:
+            QiskitError: if ``qubits`` does not contain exactly two qubits.
+        """
+        if len(qubits) != 2:
+            raise QiskitError(f"Expected exactly 2 qubits, got {len(qubits)}.")
+        sequence = self._inner_decomposer.generate_sequence(
+            np.asarray(unitary, dtype=complex), basis_fidelity, approximate
+        )
+        self._append_sequence_to_dag(sequence, dag, tuple(qubits), tag=tag)
+
+    def _append_sequence_to_dag(
+        self, sequence, dag, qubits, insert_barriers=False, tag=None, basis_gate=None
+    ):
+        basis_gate = self.gate if basis_gate is None else basis_gate
+        dag.global_phase += sequence.global_phase
+        for name, params, seq_qubits in sequence:
+            if name == "USER_GATE":
+                gate = basis_gate
+            elif name == "BASIS_GATE_POWER":
+                gate = basis_gate.power(*params)
+            else:
+                gate = GATE_NAME_MAP[name](*params)
+            if tag is not None:
+                gate = _with_label(gate, tag)
+            barrier = insert_barriers and len(seq_qubits) == 2
+            if barrier:
+                dag.apply_operation_back(Barrier(2, label=tag), tuple(qubits), check=False)
+            dag.apply_operation_back(gate, tuple(qubits[x] for x in seq_qubits), check=False)
+            if barrier:
+                dag.apply_operation_back(Barrier(2, label=tag), tuple(qubits), check=False)
 
     def traces(self, target):
         r"""
@@ -691,6 +1806,173 @@ class TwoQubitBasisDecomposer:
         return self._inner_decomposer.traces(target._inner_decomposition)
 
 
+class TwoQubitTwoBasisDecomposer:
+    r"""A class for decomposing 2-qubit unitaries into two different basis gates :math:`A` and
+    :math:`B`, where :math:`B` is locally equivalent to :math:`\text{SWAP} \cdot A`, such as
+    :class:`.CXGate` and :class:`.iSwapGate`.
+
+    A target that would need three uses of either basis gate on its own, but for which
+    :math:`\text{SWAP} \cdot U` needs two uses of :math:`A`, is synthesized as :math:`A`,
+    single-qubit gates and :math:`B`. Other targets are synthesized with whichever of the two
+    basis gates needs fewer uses, preferring :math:`A`.
+
+    Args:
+        gate_a: The first two-qubit basis gate.
+        gate_b: The second two-qubit basis gate, locally equivalent to :math:`\text{SWAP}`
+            times ``gate_a``.
+        basis_fidelity: Fidelity to be assumed for applications of either basis gate.
+            Defaults to ``1.0``.
+        euler_basis: Basis string to be provided to :class:`.OneQubitEulerDecomposer` for 1Q
+            synthesis, with the same options as for :class:`.TwoQubitBasisDecomposer`.
+
+    Raises:
+        QiskitError: If ``gate_b`` is not locally equivalent to :math:`\text{SWAP}` times
+            ``gate_a``.
+
+    .. automethod:: __call__
+    """
+
+    def __init__(
+        self,
+        gate_a: Gate,
+        gate_b: Gate,
+        basis_fidelity: float = 1.0,
+        euler_basis: str = "U",
+    ):
+        self.gate_a = gate_a
+        self.gate_b = gate_b
+        self.basis_fidelity = basis_fidelity
+        # As in TwoQubitBasisDecomposer, the gates are passed under unique keys that are mapped
+        # back to the gate objects when building the circuit, which supports custom gates.
+        self._inner_decomposer = two_qubit_decompose.TwoQubitTwoBasisDecomposer(
+            "USER_GATE_A",
+            Operator(gate_a).data,
+            "USER_GATE_B",
+            Operator(gate_b).data,
+            basis_fidelity=basis_fidelity,
+            euler_basis=euler_basis,
+        )
+
+    def __call__(
+        self, unitary: Operator | np.ndarray, *, approximate: bool = True
+    ) -> QuantumCircuit:
+        r"""Decompose a two-qubit ``unitary`` over the two basis gates.
+
+        Args:
+            unitary: :math:`4 \times 4` unitary to synthesize.
+            approximate: Approximates if the basis fidelity is less than 1.0.
+
+        Returns:
+            QuantumCircuit: Synthesized quantum circuit.
+        """
+        sequence = self._inner_decomposer(np.asarray(unitary, dtype=complex), approximate)
+        basis_gates = {"USER_GATE_A": self.gate_a, "USER_GATE_B": self.gate_b}
+        circ = QuantumCircuit(QuantumRegister(2, "q"), global_phase=sequence.global_phase)
+        for name, params, qubits in sequence:
+            if name in basis_gates:
+                circ.append(basis_gates[name], qubits)
+            else:
+                circ.append(GATE_NAME_MAP[name](*params), qubits)
+        return circ
+
+
+def resynthesize_2q_blocks(
+    dag: DAGCircuit, decomposer: TwoQubitBasisDecomposer, approximate: bool = True
+) -> int:
+    r"""Consolidate and resynthesize every run of two-qubit gates in ``dag`` in place.
+
+    Each maximal run of gates acting on the same pair of qubits (as found by
+    :meth:`.DAGCircuit.collect_2q_runs`) is multiplied out into its :math:`4 \times 4` unitary
+    and synthesized with ``decomposer``. As in :class:`.ConsolidateBlocks`, the resulting
+    sequence is only substituted for the run if the run contains a two-qubit gate other than the
+    decomposer's basis gate, or if the sequence is better: it uses fewer two-qubit gates, or as
+    many two-qubit gates and fewer gates in total.
+
+    Args:
+        dag (DAGCircuit): The DAG to modify in place.
+        decomposer (TwoQubitBasisDecomposer): The decomposer used to synthesize each block.
+        approximate (bool): Approximates if basis fidelities are less than 1.0.
+
+    Returns:
+        int: The number of blocks that were replaced. Blocks left untouched are not counted.
+    """
+    from qiskit.circuit.library.generalized_gates.unitary import UnitaryGate
+    from qiskit.dagcircuit.dagcircuit import DAGCircuit
+
+    replaced = 0
+    for block in dag.collect_2q_runs():
+        qubits = sorted(
+            {qubit for node in block for qubit in node.qargs},
+            key=lambda qubit: dag.find_bit(qubit).index,
+        )
+        if len(qubits) != 2:
+            continue
+        wire_pos_map = {qubit: i for i, qubit in enumerate(qubits)}
+        block_circuit = QuantumCircuit(2)
+        for node in block:
+            block_circuit.append(node.op, [wire_pos_map[qubit] for qubit in node.qargs])
+        matrix = Operator(block_circuit).data
+        synth_dag = DAGCircuit()
+        synth_dag.add_qreg(QuantumRegister(2))
+        decomposer.append_into_dag(matrix, synth_dag, synth_dag.qubits, approximate=approximate)
+        block_2q = [node for node in block if len(node.qargs) == 2]
+        outside_basis = any(node.op.name != decomposer.gate.name for node in block_2q)
+        synth_cost = (len(synth_dag.two_qubit_ops()), synth_dag.size())
+        if not outside_basis and synth_cost >= (len(block_2q), len(block)):
+            continue
+        node = dag.replace_block_with_op(
+            block, UnitaryGate(matrix, check_input=False), wire_pos_map, cycle_check=False
+        )
+        dag.substitute_node_with_dag(node, synth_dag)
+        replaced += 1
+    return replaced
+
+
+def to_ising_circuit(unitary, *, atol: float = DEFAULT_ATOL) -> QuantumCircuit:
+    r"""Synthesize a two-qubit unitary with :class:`.RZZGate` as the only entangling gate and
+    :class:`.RZGate` and :class:`.RXGate` as the single-qubit gates.
+
+    This is the native form of QAOA-style backends. The unitary is synthesized by a
+    :class:`.TwoQubitControlledUDecomposer` with :class:`.RZZGate` as the basis gate, which uses
+    one :class:`.RZZGate` for each non-zero Weyl coordinate, so any two-qubit unitary needs at
+    most three of them. The single-qubit gates between the :class:`.RZZGate`\ s are then collapsed
+    into one ZXZ Euler decomposition per qubit.
+
+    Args:
+        unitary (Operator or ndarray): :math:`4 \times 4` unitary to synthesize.
+        atol (float): Absolute tolerance passed to the single-qubit decomposers.
+
+    Returns:
+        QuantumCircuit: The synthesized circuit.
+
+    Raises:
+        QiskitError: If ``unitary`` is not a :math:`4 \times 4` unitary matrix.
+    """
+    unitary = np.asarray(unitary, dtype=complex)
+    if unitary.shape != (4, 4) or not is_unitary_matrix(unitary):
+        raise QiskitError("to_ising_circuit requires a 4x4 unitary matrix.")
+    decomposed = TwoQubitControlledUDecomposer(RZZGate)(unitary, atol=atol)
+
+    oneq_decompose = OneQubitEulerDecomposer("ZXZ")
+    circ = QuantumCircuit(2, global_phase=decomposed.global_phase)
+    pending = [np.eye(2, dtype=complex), np.eye(2, dtype=complex)]
+
+    def flush():
+        for qubit in (0, 1):
+            circ.compose(oneq_decompose(pending[qubit], atol=atol), [qubit], inplace=True)
+            pending[qubit] = np.eye(2, dtype=complex)
+
+    for instruction in decomposed.data:
+        qubits = [decomposed.find_bit(qubit).index for qubit in instruction.qubits]
+        if len(qubits) == 1:
+            pending[qubits[0]] = instruction.operation.to_matrix() @ pending[qubits[0]]
+        else:
+            flush()
+            circ.append(instruction.operation, qubits)
+    flush()
+    return circ
+
+
 class TwoQubitDecomposeUpToDiagonal:
     """
     Class to decompose two qubit unitaries into the product of a diagonal gate
@@ -741,14 +2023,29 @@ class TwoQubitDecomposeUpToDiagonal:
         gamma = self._gamma(mat)
         return np.isclose(np.trace(gamma).imag, 0)
 
-    def _real_trace_transform(self, mat):
-        """
+    def real_trace_transform(self, mat, theta=0.0, phi=0.0):
+        r"""
         Determine diagonal gate such that
 
         U3 = D U2
 
         Where U3 is a general two-qubit gate which takes 3 cnots, D is a
         diagonal gate, and U2 is a gate which takes 2 cnots.
+
+        The diagonal is :math:`D = \text{diag}(e^{-i\theta}, e^{-i\phi}, e^{-i\psi},
+        e^{i(\theta + \phi + \psi)})`.  Only :math:`\phi + \psi` is fixed by the requirement
+        that :math:`D U3` has a real :math:`\gamma` trace, so ``theta`` and ``phi`` are a residual
+        gauge freedom and ``psi`` is solved for from them.  Every choice gives a valid
+        decomposition; the freedom can be used to shape the diagonal so that it merges better
+        with neighboring gates.
+
+        Args:
+            mat (ndarray): 4x4 special unitary matrix.
+            theta (float): phase of the first diagonal entry.
+            phi (float): phase of the second diagonal entry.
+
+        Returns:
+            ndarray: the 4x4 diagonal matrix D.
         """
         a1 = (
             -mat[1, 3] * mat[2, 0]
@@ -762,16 +2059,21 @@ class TwoQubitDecomposeUpToDiagonal:
             - mat[0, 1] * mat[3, 2]
             + mat[0, 0] * mat[3, 3]
         )
-        theta = 0  # arbitrary
-        phi = 0  # arbitrary
         psi = np.arctan2(a1.imag + a2.imag, a1.real - a2.real) - phi
         diag = np.diag(np.exp(-1j * np.array([theta, phi, psi, -(theta + phi + psi)])))
         return diag
 
-    def __call__(self, mat):
-        """do the decomposition"""
+    # Kept for backwards compatibility with the previous private name.
+    _real_trace_transform = real_trace_transform
+
+    def __call__(self, mat, theta=0.0, phi=0.0):
+        """do the decomposition
+
+        ``theta`` and ``phi`` select the gauge of the extracted diagonal, see
+        :meth:`real_trace_transform`.
+        """
         su4, phase = self._u4_to_su4(mat)
-        real_map = self._real_trace_transform(su4)
+        real_map = self.real_trace_transform(su4, theta=theta, phi=phi)
         mapped_su4 = real_map @ su4
         if not self._cx2_test(mapped_su4):
             warnings.warn("Unitary decomposition up to diagonal may use an additionl CX gate.")
@@ -822,6 +2124,14 @@ class _LazyTwoQubitCXDecomposer(TwoQubitBasisDecomposer):
         self._load()
         return self._inner.num_basis_gates(unitary)
 
+    def cache_info(self):
+        self._load()
+        return self._inner.cache_info()
+
+    def clear_cache(self):
+        self._load()
+        self._inner.clear_cache()
+
 
 two_qubit_cnot_decompose = _LazyTwoQubitCXDecomposer()
 """
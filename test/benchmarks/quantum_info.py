@@ -25,7 +25,10 @@ from qiskit.quantum_info import (
 )
 from qiskit.synthesis import synth_clifford_full
 from qiskit.quantum_info.operators.symplectic.random import random_pauli_list
-from qiskit.quantum_info import random_cnotdihedral, CNOTDihedral
+from qiskit.quantum_info import random_cnotdihedral, CNOTDihedral, random_unitary
+from qiskit.circuit.library import CXGate, RYGate, SwapGate
+from qiskit.synthesis import TwoQubitBasisDecomposer, TwoQubitWeylDecomposition
+from qiskit._accelerate.two_qubit_decompose import gate_sequence_unitary
 
 
 class RandomCliffordBench:
@@ -207,3 +210,136 @@ class SparsePauliOpBench:
         self.p1.to_matrix()
 
     time_to_matrix.params = [[2, 4, 6, 8, 10], [50]]
+
+
+class TwoQubitWeylDecompositionBench:
+    params = [100]
+    param_names = ["length"]
+
+    def setup(self, length):
+        rng = np.random.default_rng(2024)
+        self.generic = [random_unitary(4, seed=rng).data for _ in range(length)]
+        self.real_orthogonal = []
+        for _ in range(length):
+            thetas = 2 * np.pi * rng.random(size=4)
+            k1 = np.kron(RYGate(thetas[0]).to_matrix(), RYGate(thetas[1]).to_matrix())
+            k2 = np.kron(RYGate(thetas[2]).to_matrix(), RYGate(thetas[3]).to_matrix())
+            self.real_orthogonal.append((k1 @ CXGate().to_matrix() @ k2).real)
+
+    def time_generic(self, _):
+        for unitary in self.generic:
+            TwoQubitWeylDecomposition(unitary)
+
+    def time_real_orthogonal(self, _):
+        for unitary in self.real_orthogonal:
+            TwoQubitWeylDecomposition(unitary)
+
+
+class TwoQubitPulseOptimalBench:
+    params = [100]
+    param_names = ["length"]
+
+    def setup(self, length):
+        rng = np.random.default_rng(2024)
+        self.unitaries = [random_unitary(4, seed=rng).data for _ in range(length)]
+        self.decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX", pulse_optimize=True)
+
+    def time_pulse_optimal_3cx(self, _):
+        for unitary in self.unitaries:
+            self.decomposer(unitary)
+
+
+class TwoQubitProductBench:
+    params = [100]
+    param_names = ["length"]
+
+    def setup(self, length):
+        rng = np.random.default_rng(2445)
+        self.products = [
+            np.kron(random_unitary(2, seed=rng).data, random_unitary(2, seed=rng).data)
+            for _ in range(length)
+        ]
+        self.decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+
+    def time_product(self, _):
+        for unitary in self.products:
+            self.decomposer(unitary)
+
+
+class TwoQubitSwapBench:
+    params = [100]
+    param_names = ["length"]
+
+    def setup(self, length):
+        self.swaps = [np.exp(0.1j * i) * SwapGate().to_matrix() for i in range(length)]
+        self.decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+
+    def time_weyl_swap(self, _):
+        for unitary in self.swaps:
+            TwoQubitWeylDecomposition(unitary)
+
+    def time_decompose_swap(self, _):
+        for unitary in self.swaps:
+            self.decomposer(unitary)
+
+
+class TwoQubitBatchBench:
+    params = [100, 1000]
+    param_names = ["length"]
+
+    def setup(self, length):
+        rng = np.random.default_rng(2452)
+        self.unitaries = np.array([random_unitary(4, seed=rng).data for _ in range(length)])
+        self.decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+
+    def time_sequences_serial(self, _):
+        for unitary in self.unitaries:
+            self.decomposer._inner_decomposer.generate_sequence(unitary)
+
+    def time_sequences_batch(self, _):
+        self.decomposer._inner_decomposer.generate_sequence_batch(self.unitaries)
+
+    def time_circuits_batch(self, _):
+        self.decomposer.to_circuits_batch(self.unitaries)
+
+
+# The angles of the gates synthesized for Clifford targets repeat, so multiplying them out takes
+# their matrices from the gate matrix cache, while those of generic targets are all different.
+# The ``unitary`` benchmarks time only multiplying out the synthesized sequences, with and without
+# the cache, and the other ones the whole synthesis of the targets.
+class TwoQubitGateMatrixCacheBench:
+    params = [100, 1000]
+    param_names = ["length"]
+
+    def setup(self, length):
+        rng = np.random.default_rng(2024)
+        self.cliffords = [random_clifford(2, seed=rng).to_matrix() for _ in range(length)]
+        self.generic = [random_unitary(4, seed=rng).data for _ in range(length)]
+        self.decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX", pulse_optimize=True)
+        inner = self.decomposer._inner_decomposer
+        self.clifford_sequences = [inner.generate_sequence(unitary) for unitary in self.cliffords]
+        self.generic_sequences = [inner.generate_sequence(unitary) for unitary in self.generic]
+
+    def time_clifford(self, _):
+        for unitary in self.cliffords:
+            self.decomposer(unitary)
+
+    def time_generic(self, _):
+        for unitary in self.generic:
+            self.decomposer(unitary)
+
+    def time_clifford_unitary(self, _):
+        for sequence in self.clifford_sequences:
+            gate_sequence_unitary(sequence)
+
+    def time_clifford_unitary_uncached(self, _):
+        for sequence in self.clifford_sequences:
+            gate_sequence_unitary(sequence, use_matrix_cache=False)
+
+    def time_generic_unitary(self, _):
+        for sequence in self.generic_sequences:
+            gate_sequence_unitary(sequence)
+
+    def time_generic_unitary_uncached(self, _):
+        for sequence in self.generic_sequences:
+            gate_sequence_unitary(sequence, use_matrix_cache=False)
@@ -17,14 +17,16 @@ import unittest
 import contextlib
 import logging
 import math
+import warnings
+from unittest.mock import patch
 import numpy as np
 import scipy
 import scipy.stats
-from ddt import ddt, data
+from ddt import ddt, data, unpack
 
 from qiskit import QiskitError, transpile
 from qiskit.dagcircuit.dagcircuit import DAGCircuit
-from qiskit.circuit import QuantumCircuit, QuantumRegister
+from qiskit.circuit import Gate, QuantumCircuit, QuantumRegister
 from qiskit.converters import dag_to_circuit, circuit_to_dag
 from qiskit.circuit.library import (
     HGate,
@@ -38,7 +40,10 @@ from qiskit.circuit.library import (
     YGate,
     ZGate,
     CXGate,
+    CYGate,
     CZGate,
+    DCXGate,
+    ECRGate,
     iSwapGate,
     SwapGate,
     RXXGate,
@@ -46,25 +51,58 @@ from qiskit.circuit.library import (
     RZZGate,
     RZXGate,
     CPhaseGate,
+    CRXGate,
+    CRYGate,
     CRZGate,
     RXGate,
     RYGate,
     RZGate,
     UnitaryGate,
+    XXPlusYYGate,
+    PermutationGate,
 )
 from qiskit.quantum_info.operators import Operator
-from qiskit.quantum_info.random import random_unitary
-from qiskit.synthesis.one_qubit.one_qubit_decompose import OneQubitEulerDecomposer
+from qiskit.quantum_info.random import random_clifford, random_unitary
+from qiskit.synthesis.one_qubit.one_qubit_decompose import (
+    ONE_QUBIT_EULER_BASIS_GATES,
+    OneQubitEulerDecomposer,
+)
 from qiskit.synthesis.two_qubit.two_qubit_decompose import (
     TwoQubitWeylDecomposition,
     two_qubit_cnot_decompose,
     TwoQubitBasisDecomposer,
     TwoQubitControlledUDecomposer,
+    Endianness,
     Ud,
     decompose_two_qubit_product_gate,
     TwoQubitDecomposeUpToDiagonal,
+    trace_to_fid,
+    resynthesize_2q_blocks,
+    TwoQubitTwoBasisDecomposer,
+    to_ising_circuit,
+    WeylBasis,
+)
+from qiskit.synthesis.two_qubit.weyl import weyl_coordinates
+from qiskit._accelerate.two_qubit_decompose import (
+    TwoQubitBasisDecomposer as RustTwoQubitBasisDecomposer,
+    TwoQubitTwoBasisDecomposer as RustTwoQubitTwoBasisDecomposer,
+    MultiplicationOrder,
+    SequenceOptions,
+    Specialization,
+    average_gate_infidelity,
+    best_fixed_angle_rzz,
+    best_single_rzz_angle,
+    cluster_by_weyl,
+    effective_cx_count,
+    fidelity_curve,
+    kak_traces,
+    supercontrolled_basis_matrices,
+    synthesis_fidelity,
+    total_cx_for_blocks,
+    two_qubit_clifford_circuit,
+    ud_batch,
+    weyl_coordinates_and_specialization,
 )
-from qiskit._accelerate.two_qubit_decompose import Specialization
 from qiskit.synthesis.unitary import qsd
 from test import combine  # pylint: disable=wrong-import-order
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
@@ -640,6 +678,69 @@ class TestOneQubitEulerDecomposer(CheckDecompositions):
 class TestTwoQubitWeylDecomposition(CheckDecompositions):
     """Test TwoQubitWeylDecomposition()"""
 
+    def test_diagonalization_iterations(self):
+        """Verify a near-degenerate target needs more than one diagonalization attempt"""
+        # The first attempt diagonalizes 1.26 Re(M2) + 0.22 Im(M2), which is degenerate when two
+        # eigenphases of M2 are symmetric about the angle of (1.26, 0.22). The eigenphases of
+        # Ud(a, b, c) include 2(a - b + c) and 2(-a + b + c), which sum to 4c.
+        angle = math.atan2(0.22317849046722027, 1.2602066112249388)
+        su2 = []
+        for seed in range(4):
+            mat = random_unitary(2, seed=seed).data
+            su2.append(mat / np.sqrt(np.linalg.det(mat)))
+        k1 = np.kron(su2[0], su2[1])
+        k2 = np.kron(su2[2], su2[3])
+        target = k1 @ Ud(0.5, 0.3, angle / 2) @ k2
+        self.assertGreater(TwoQubitWeylDecomposition(target).diagonalization_iterations, 1)
+        self.check_two_qubit_weyl_decomposition(target)
+        generic = TwoQubitWeylDecomposition(random_unitary(4, seed=2400))
+        self.assertEqual(generic.diagonalization_iterations, 1)
+
+    def test_max_diagonalization_iterations(self):
+        """Verify the number of diagonalization attempts can be capped"""
+        unitary = random_unitary(4, seed=2400).data
+        capped = TwoQubitWeylDecomposition(unitary, max_diagonalization_iterations=1)
+        self.assertEqual(capped.diagonalization_iterations, 1)
+        self.check_two_qubit_weyl_decomposition(unitary)
+        # The near-degenerate target of test_diagonalization_iterations needs a second attempt.
+        angle = math.atan2(0.22317849046722027, 1.2602066112249388)
+        su2 = []
+        for seed in range(4):
+            mat = random_unitary(2, seed=seed).data
+            su2.append(mat / np.sqrt(np.linalg.det(mat)))
+        target = np.kron(su2[0], su2[1]) @ Ud(0.5, 0.3, angle / 2) @ np.kron(su2[2], su2[3])
+        self.assertGreater(TwoQubitWeylDecomposition(target).diagonalization_iterations, 1)
+        with self.assertRaisesRegex(QiskitError, "failed to diagonalize M2"):
+            TwoQubitWeylDecomposition(target, max_diagonalization_iterations=1)
+        # Local targets skip the diagonalization, and real-orthogonal targets are diagonalized
+        # directly in one attempt, which counts towards the cap.
+        local = TwoQubitWeylDecomposition(np.eye(4), max_diagonalization_iterations=0)
+        self.assertEqual(local.diagonalization_iterations, 0)
+        cx = TwoQubitWeylDecomposition(CXGate().to_matrix(), max_diagonalization_iterations=1)
+        self.assertEqual(cx.diagonalization_iterations, 1)
+        with self.assertRaisesRegex(QiskitError, "failed to diagonalize M2 in 0 attempts"):
+            TwoQubitWeylDecomposition(CXGate().to_matrix(), max_diagonalization_iterations=0)
+
+    def test_near_local_factors(self):
+        """Verify near-local targets are split into their local factors directly"""
+        su2 = []
+        for seed in (2431, 2432):
+            mat = random_unitary(2, seed=seed).data
+            su2.append(mat / np.sqrt(np.linalg.det(mat)))
+        for eps in (0.0, 1e-14):
+            with self.subTest(eps=eps):
+                target = np.exp(0.2j) * np.kron(su2[0], su2[1]) @ Ud(eps, 0.0, 0.0)
+                decomp = TwoQubitWeylDecomposition(target)
+                self.assertEqual(decomp.diagonalization_iterations, 0)
+                self.assertEqual((decomp.a, decomp.b, decomp.c), (0.0, 0.0, 0.0))
+                # The SU(2) factors are only defined up to a sign.
+                for actual, expected in [(decomp.K1l, su2[0]), (decomp.K1r, su2[1])]:
+                    sign = np.sign(np.real(np.trace(expected.conj().T @ actual)))
+                    np.testing.assert_allclose(actual, sign * expected, atol=1e-12)
+                np.testing.assert_allclose(decomp.K2l, np.eye(2), atol=1e-12)
+                np.testing.assert_allclose(decomp.K2r, np.eye(2), atol=1e-12)
+                self.check_two_qubit_weyl_decomposition(target)
+
     def test_TwoQubitWeylDecomposition_repr(self, seed=42):
         """Check that eval(__repr__) is exact round trip"""
         target = random_unitary(4, seed=seed)
@@ -652,6 +753,245 @@ class TestTwoQubitWeylDecomposition(CheckDecompositions):
         weyl1 = TwoQubitWeylDecomposition(target, fidelity=0.99)
         self.assertRoundTripPickle(weyl1)
 
+    def test_euler_angles(self, seed=11):
+        """Verify the K matrices are reconstructed from their Euler angles"""
+        decomp = TwoQubitWeylDecomposition(random_unitary(4, seed=seed))
+        angles = decomp.euler_angles("U")
+        for k, (theta, phi, lam, phase) in zip(
+            [decomp.K1l, decomp.K1r, decomp.K2l, decomp.K2r], angles
+        ):
+            reconstructed = np.exp(1j * phase) * UGate(theta, phi, lam).to_matrix()
+            np.testing.assert_allclose(reconstructed, k, atol=1e-12)
+
+    def test_specialization_num_params(self):
+        """Verify the number of parameters bound by each specialization"""
+        expected = [
+            (Specialization.General, 6),
+            (Specialization.IdEquiv, 0),
+            (Specialization.SWAPEquiv, 0),
+            (Specialization.PartialSWAPEquiv, 3),
+            (Specialization.PartialSWAPFlipEquiv, 3),
+            (Specialization.ControlledEquiv, 4),
+            (Specialization.MirrorControlledEquiv, 4),
+            (Specialization.fSimaabEquiv, 5),
+            (Specialization.fSimabbEquiv, 5),
+            (Specialization.fSimabmbEquiv, 5),
+        ]
+        for specialization, num_params in expected:
+            with self.subTest(specialization=specialization):
+                self.assertEqual(specialization.num_params(), num_params)
+                self.assertIsInstance(specialization.description(), str)
+
+    def test_classify_fsim_boundaries(self):
+        """Verify the specialization classification around the fSim boundaries"""
+        cases = [
+            (Ud(0.4, 0.4, 0.1), 1.0 - 1.0e-9, Specialization.fSimaabEquiv),
+            (Ud(0.4, 0.35, 0.1), 1.0 - 1.0e-9, Specialization.General),
+            (Ud(0.4, 0.35, 0.1), 0.99, Specialization.fSimaabEquiv),
+            (Ud(0.5, 0.2, 0.2), 1.0 - 1.0e-9, Specialization.fSimabbEquiv),
+            (Ud(0.5, 0.2, 0.18), 0.9999, Specialization.General),
+            (Ud(0.5, 0.2, 0.18), 0.999, Specialization.fSimabbEquiv),
+            (Ud(0.5, 0.2, -0.2), 1.0 - 1.0e-9, Specialization.fSimabmbEquiv),
+            (Ud(0.5, 0.2, -0.18), 0.999, Specialization.fSimabmbEquiv),
+            (Ud(0.5, 0.2, 0.1), None, Specialization.General),
+        ]
+        for unitary, fidelity, expected in cases:
+            with self.subTest(unitary=unitary, fidelity=fidelity):
+                self.assertEqual(TwoQubitWeylDecomposition.classify(unitary, fidelity), expected)
+                decomp = TwoQubitWeylDecomposition(unitary, fidelity=fidelity)
+                self.assertEqual(decomp._inner_decomposition.specialization, expected)
+
+    def test_special_unitary_input(self):
+        """Verify an SU(4) target gives the same decomposition as the same target with a phase"""
+        unitary = random_unitary(4, seed=2420).data
+        unitary = unitary / np.linalg.det(unitary) ** 0.25
+        self.assertAlmostEqual(np.linalg.det(unitary), 1.0, places=13)
+        special = TwoQubitWeylDecomposition(unitary)
+        phased = TwoQubitWeylDecomposition(np.exp(0.3j) * unitary)
+        np.testing.assert_allclose(
+            [special.a, special.b, special.c], [phased.a, phased.b, phased.c], atol=1e-13
+        )
+        # The local gates are only defined up to a sign, so the phases agree modulo pi.
+        self.assertAlmostEqual(
+            np.angle(np.exp(2j * (phased.global_phase - special.global_phase - 0.3))), 0.0
+        )
+        self.assertTrue(np.allclose(Operator(special.circuit()).data, unitary, atol=1e-12))
+
+    def test_circuit_high_precision_phase(self):
+        """Verify the global phase of the circuit with and without compensated summation"""
+        for seed in range(2471, 2476):
+            target = random_unitary(4, seed=seed).data
+            decomp = TwoQubitWeylDecomposition(target)
+            for euler_basis in ["ZSX", "U3", "ZYZ"]:
+                with self.subTest(seed=seed, euler_basis=euler_basis):
+                    default = decomp.circuit(euler_basis=euler_basis)
+                    precise = decomp.circuit(euler_basis=euler_basis, high_precision=True)
+                    self.assertEqual(default.data, precise.data)
+                    self.assertAlmostEqual(default.global_phase, precise.global_phase, places=13)
+                    for circ in (default, precise):
+                        np.testing.assert_allclose(Operator(circ).data, target, atol=1e-12)
+
+    def test_determinant_branch_cut(self):
+        """Verify targets on either side of the branch cut of the determinant phase get
+        continuous global phases"""
+        unitary = random_unitary(4, seed=2458).data
+        unitary = unitary / np.linalg.det(unitary) ** 0.25
+        phases = []
+        # The determinant of exp(i (pi / 4 + eps)) * unitary crosses the negative real axis.
+        for eps in [-1e-11, -1e-13, 0.0, 1e-13, 1e-11]:
+            target = np.exp(1j * (np.pi / 4 + eps)) * unitary
+            decomp = TwoQubitWeylDecomposition(target)
+            self.assertTrue(np.allclose(Operator(decomp.circuit()).data, target, atol=1e-12))
+            phases.append(decomp.global_phase)
+        # The local gates are only defined up to a sign, so the phases are compared modulo pi.
+        for phase in phases[1:]:
+            self.assertAlmostEqual(np.angle(np.exp(2j * (phase - phases[0]))), 0.0, places=8)
+
+    def test_reconstructed_matrix(self):
+        """Verify the reconstructed matrix matches the input of a decomposition"""
+        for seed in range(2429, 2434):
+            with self.subTest(seed=seed):
+                unitary = random_unitary(4, seed=seed).data
+                decomp = TwoQubitWeylDecomposition(unitary)
+                np.testing.assert_allclose(decomp.reconstructed_matrix, unitary, atol=1e-12)
+        unitary = Ud(np.pi / 4, 0.0, 0.0)
+        decomp = TwoQubitWeylDecomposition(unitary)
+        np.testing.assert_allclose(decomp.reconstructed_matrix, unitary, atol=1e-12)
+
+    def test_circuit_absorbing_swap(self):
+        """Verify the circuit without the SWAP followed by the permutation is the full circuit"""
+        local = np.kron(random_unitary(2, seed=2461).data, random_unitary(2, seed=2462).data)
+        cases = [
+            (local @ iSwapGate().to_matrix(), [1, 0]),
+            (local @ SwapGate().to_matrix(), [1, 0]),
+            (random_unitary(4, seed=2461).data, [0, 1]),
+        ]
+        for unitary, expected in cases:
+            decomp = TwoQubitWeylDecomposition(unitary)
+            for basis in [WeylBasis.Rotations, WeylBasis.CXRZ]:
+                with self.subTest(expected=expected, basis=basis):
+                    full = decomp.circuit(basis=basis)
+                    circ, permutation = decomp.circuit_absorbing_swap(basis=basis)
+                    self.assertEqual(permutation, expected)
+                    self.assertNotIn("swap", circ.count_ops())
+                    if expected == [1, 0] and basis == WeylBasis.CXRZ:
+                        self.assertEqual(
+                            circ.count_ops().get("cx", 0), full.count_ops()["cx"] - 3
+                        )
+                    circ.append(PermutationGate(permutation), [0, 1])
+                    self.assertEqual(Operator(circ), Operator(full))
+                    self.assertEqual(Operator(circ), Operator(unitary))
+
+    def test_weyl_gate_matrix(self):
+        """Verify the local gates around the Weyl gate reconstruct the input"""
+        unitary = random_unitary(4, seed=2459).data
+        decomp = TwoQubitWeylDecomposition(unitary)
+        k1 = np.kron(decomp.K1l, decomp.K1r)
+        k2 = np.kron(decomp.K2l, decomp.K2r)
+        np.testing.assert_allclose(k1 @ decomp.weyl_gate_matrix @ k2, unitary, atol=1e-12)
+
+    def test_big_endian_input(self):
+        """Verify a big-endian input is decomposed as its little-endian equivalent"""
+        unitary = random_unitary(4, seed=2442).data
+        big_endian = unitary.reshape(2, 2, 2, 2).transpose(1, 0, 3, 2).reshape(4, 4)
+        little = TwoQubitWeylDecomposition(unitary)
+        big = TwoQubitWeylDecomposition(big_endian, input_endianness=Endianness.Big)
+        np.testing.assert_allclose(big.unitary_matrix, unitary, atol=1e-14)
+        np.testing.assert_allclose([big.a, big.b, big.c], [little.a, little.b, little.c])
+        np.testing.assert_allclose(big.K1l, little.K1l, atol=1e-12)
+        np.testing.assert_allclose(big.K2r, little.K2r, atol=1e-12)
+
+    def test_cxrz_basis(self):
+        """Verify the CX + RZ basis emits no rotation interactions and the expected CX count"""
+        cases = [
+            (random_unitary(4, seed=2430).data, 6),
+            (Ud(np.pi / 4, np.pi / 4, 0.1), 5),
+            (SwapGate().to_matrix(), 3),
+        ]
+        for unitary, num_cx in cases:
+            with self.subTest(num_cx=num_cx):
+                circ = TwoQubitWeylDecomposition(unitary).circuit(basis=WeylBasis.CXRZ)
+                ops = circ.count_ops()
+                for name in ("rxx", "ryy", "rzz", "swap"):
+                    self.assertNotIn(name, ops)
+                self.assertEqual(ops["cx"], num_cx)
+                self.assertTrue(np.allclose(Operator(circ).data, unitary, atol=1e-12))
+
+    def test_givens_rotation_specialization(self):
+        """Verify Givens (XX+YY) rotations are recognized as fSimaabEquiv with c == 0"""
+        for theta in [0.3, 1.1, 2.0]:
+            for beta in [0.0, 0.7]:
+                with self.subTest(theta=theta, beta=beta):
+                    unitary = XXPlusYYGate(theta, beta).to_matrix()
+                    decomp = TwoQubitWeylDecomposition(unitary)
+                    specialization = decomp._inner_decomposition.specialization
+                    self.assertEqual(specialization, Specialization.fSimaabEquiv)
+                    self.assertAlmostEqual(decomp.a, theta / 4)
+                    self.assertAlmostEqual(decomp.b, theta / 4)
+                    self.assertAlmostEqual(decomp.c, 0.0)
+                    circ = decomp.circuit()
+                    self.assertNotIn("rzz", circ.count_ops())
+                    self.assertTrue(Operator(circ).equiv(unitary))
+        with self.assertWarns(UserWarning):
+            decomposer = TwoQubitBasisDecomposer(XXPlusYYGate(np.pi / 2))
+        target = XXPlusYYGate(np.pi / 2, 0.4).to_matrix()
+        circ = decomposer(target)
+        self.assertEqual(circ.count_ops()["xx_plus_yy"], 1)
+        self.assertTrue(Operator(circ).equiv(target))
+
+    def test_forced_specialization_mismatch(self):
+        """Verify forcing a specialization that doesn't fit the target raises an error"""
+        cases = [
+            (CXGate().to_matrix(), Specialization.IdEquiv),
+            (random_unitary(4, seed=2410).data, Specialization.SWAPEquiv),
+            (SwapGate().to_matrix(), Specialization.ControlledEquiv),
+        ]
+        for unitary, specialization in cases:
+            with self.subTest(specialization=specialization):
+                with self.assertRaisesRegex(QiskitError, "was forced"):
+                    TwoQubitWeylDecomposition(
+                        unitary, fidelity=None, _specialization=specialization
+                    )
+        decomp = TwoQubitWeylDecomposition(
+            Ud(0.3, 0, 0), fidelity=None, _specialization=Specialization.ControlledEquiv
+        )
+        self.assertAlmostEqual(decomp.calculated_fidelity, 1.0)
+
+    def test_weyl_coordinates_and_specialization(self):
+        """Verify the cheap classification matches the full Weyl decomposition"""
+        special = [np.eye(4), CXGate().to_matrix(), SwapGate().to_matrix(), Ud(0.4, 0.4, 0.1)]
+        for unitary in special:
+            for fidelity in [1.0 - 1.0e-9, 0.99]:
+                with self.subTest(unitary=unitary, fidelity=fidelity):
+                    _, specialization = weyl_coordinates_and_specialization(unitary, fidelity)
+                    decomp = TwoQubitWeylDecomposition(unitary, fidelity=fidelity)
+                    self.assertEqual(decomp._inner_decomposition.specialization, specialization)
+        for seed in range(10):
+            unitary = random_unitary(4, seed=2404 + seed).data
+            with self.subTest(seed=seed):
+                coords, specialization = weyl_coordinates_and_specialization(unitary)
+                decomp = TwoQubitWeylDecomposition(unitary)
+                self.assertEqual(specialization, Specialization.General)
+                self.assertEqual(decomp._inner_decomposition.specialization, specialization)
+                np.testing.assert_allclose(coords, [decomp.a, decomp.b, decomp.c], atol=1e-12)
+        with self.assertRaises(QiskitError):
+            weyl_coordinates_and_specialization(np.eye(2, dtype=complex))
+
+    def test_to_canonical_qasm_lines(self):
+        """Verify the OpenQASM 3 lines of the canonical gate"""
+        decomp = TwoQubitWeylDecomposition(CXGate().to_matrix())
+        self.assertAlmostEqual(decomp.a, np.pi / 4)
+        self.assertEqual(
+            decomp.to_canonical_qasm_lines(),
+            [
+                f"rxx({-2 * decomp.a!r}) q[0], q[1];",
+                "ryy(0) q[0], q[1];",
+                "rzz(0) q[0], q[1];",
+            ],
+        )
+        decomp = TwoQubitWeylDecomposition(SwapGate().to_matrix())
+        self.assertEqual(decomp.to_canonical_qasm_lines(), ["swap q[0], q[1];"])
+
     def test_two_qubit_weyl_decomposition_cnot(self):
         """Verify Weyl KAK decomposition for U~CNOT"""
         for k1l, k1r, k2l, k2r in K1K2S:
@@ -660,6 +1000,627 @@ class TestTwoQubitWeylDecomposition(CheckDecompositions):
             a = Ud(np.pi / 4, 0, 0)
             self.check_two_qubit_weyl_decomposition(k1 @ a @ k2)
 
+    def test_two_qubit_weyl_decomposition_real_orthogonal(self, seed=7):
+        """Verify Weyl KAK decomposition for real orthogonal U~CNOT"""
+        state = np.random.default_rng(seed)
+        for _ in range(5):
+            thetas = 2 * np.pi * state.random(size=4)
+            k1 = np.kron(RYGate(thetas[0]).to_matrix(), RYGate(thetas[1]).to_matrix())
+            k2 = np.kron(RYGate(thetas[2]).to_matrix(), RYGate(thetas[3]).to_matrix())
+            for gate in [CXGate(), CZGate()]:
+                target = (k1 @ gate.to_matrix() @ k2).real
+                self.check_two_qubit_weyl_decomposition(target)
+                decomp = TwoQubitWeylDecomposition(target)
+                np.testing.assert_allclose(
+                    [decomp.a, decomp.b, decomp.c], [np.pi / 4, 0, 0], atol=1e-12
+                )
+
+    def test_cluster_by_weyl(self):
+        """Verify unitaries are grouped by their Weyl coordinates"""
+        unitaries = []
+        for i, gate in enumerate([CXGate(), iSwapGate(), CXGate(), CZGate(), iSwapGate()]):
+            k1 = np.kron(random_unitary(2, seed=i).data, random_unitary(2, seed=10 + i).data)
+            k2 = np.kron(random_unitary(2, seed=20 + i).data, random_unitary(2, seed=30 + i).data)
+            unitaries.append(k1 @ gate.to_matrix() @ k2)
+        self.assertEqual(cluster_by_weyl(np.array(unitaries), 1e-8), [[0, 2, 3], [1, 4]])
+
+        # (pi/4, b, c) and (pi/4, b, -c) are the same point on the face of the Weyl chamber.
+        unitaries = [Ud(np.pi / 4, 0.2, 0.1), Ud(np.pi / 4, 0.2, -0.1), Ud(0.3, 0.2, -0.1)]
+        self.assertEqual(cluster_by_weyl(np.array(unitaries), 1e-8), [[0, 1], [2]])
+
+        with self.assertRaisesRegex(QiskitError, "expected an"):
+            cluster_by_weyl(np.zeros((2, 2, 2), dtype=complex), 1e-8)
+
+    def test_to_circuit_with_durations(self):
+        """Verify the critical-path duration of a 3-CX decomposition"""
+        durations = {"rz": 0.0, "sx": 35.5, "x": 35.5, "cx": 300.0}
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        unitary = random_unitary(4, seed=2395)
+        circ, duration = decomposer.to_circuit_with_durations(unitary, durations)
+        self.assertEqual(Operator(circ), unitary)
+        self.assertEqual(circ.count_ops()["cx"], 3)
+
+        finish = [0.0, 0.0]
+        for instruction in circ.data:
+            qubits = [circ.find_bit(qubit).index for qubit in instruction.qubits]
+            start = max(finish[qubit] for qubit in qubits)
+            for qubit in qubits:
+                finish[qubit] = start + durations[instruction.operation.name]
+        self.assertAlmostEqual(duration, max(finish))
+        serial = sum(durations[instruction.operation.name] for instruction in circ.data)
+        self.assertLess(duration, serial)
+
+        with self.assertRaises(QiskitError):
+            decomposer.to_circuit_with_durations(unitary, {"cx": 300.0})
+
+    def test_minimize_1q(self):
+        """Verify minimize_1q picks the euler basis with the fewest single-qubit gates"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        for gate, name in [(RXGate, "rx"), (RYGate, "ry")]:
+            target = np.kron(gate(0.3).to_matrix(), gate(0.5).to_matrix())
+            circ = decomposer(target, minimize_1q=True)
+            self.assertEqual(Operator(circ), Operator(target))
+            self.assertEqual(circ.count_ops(), {name: 2})
+            self.assertLess(len(circ), len(decomposer(target)))
+        target = random_unitary(4, seed=2397)
+        circ = decomposer(target, minimize_1q=True)
+        self.assertEqual(Operator(circ), target)
+        self.assertEqual(circ.count_ops()["cx"], 3)
+        self.assertLessEqual(len(circ), len(decomposer(target)))
+
+    def test_euler_basis_q1_error_prefers_better_qubit(self):
+        """Verify an asymmetric 1q error map places fewer gates on the worse qubit"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        target = np.kron(RXGate(0.3).to_matrix(), RYGate(0.5).to_matrix())
+        for q1_error, worse, better in [((1e-2, 1e-5), 0, 1), ((1e-5, 1e-2), 1, 0)]:
+            circ = decomposer(target, euler_basis_q1_error=q1_error)
+            self.assertEqual(Operator(circ), Operator(target))
+            counts = [0, 0]
+            for instruction in circ.data:
+                counts[circ.find_bit(instruction.qubits[0]).index] += 1
+            self.assertEqual(counts[worse], 1)
+            self.assertGreater(counts[better], 1)
+
+    @combine(seed=range(5), name="seed_{seed}")
+    def test_euler_basis_q1_error_entangling_target(self, seed):
+        """Verify the Euler basis is chosen by weighted 1q error for a 3-CX target"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="U")
+        target = random_unitary(4, seed=2398 + seed)
+        q1_error = (1e-2, 1e-4)
+
+        def weighted_error(circ):
+            return sum(
+                q1_error[circ.find_bit(instruction.qubits[0]).index]
+                for instruction in circ.data
+                if len(instruction.qubits) == 1
+            )
+
+        circ = decomposer(target, euler_basis_q1_error=q1_error)
+        self.assertEqual(Operator(circ), target)
+        self.assertEqual(circ.count_ops()["cx"], 3)
+        candidates = [
+            TwoQubitBasisDecomposer(CXGate(), euler_basis=basis, pulse_optimize=False)(target)
+            for basis in ["U", "ZYZ", "ZXZ", "ZSX"]
+        ]
+        self.assertAlmostEqual(
+            weighted_error(circ), min(weighted_error(candidate) for candidate in candidates)
+        )
+
+    def test_depth_optimal(self):
+        """Verify depth_optimal reduces the depth of 3-CX decompositions keeping the CX direction"""
+
+        def cx_qubits(circ):
+            return [
+                tuple(circ.find_bit(qubit).index for qubit in inst.qubits)
+                for inst in circ.data
+                if inst.operation.name == "cx"
+            ]
+
+        for pulse_optimize in [None, False]:
+            decomposer = TwoQubitBasisDecomposer(
+                CXGate(), euler_basis="ZSX", pulse_optimize=pulse_optimize
+            )
+            reduced = []
+            for seed in range(10):
+                target = random_unitary(4, seed=2403 + seed)
+                circ = decomposer(target, depth_optimal=True)
+                default = decomposer(target)
+                self.assertEqual(Operator(circ), target)
+                self.assertEqual(circ.count_ops()["cx"], 3)
+                self.assertEqual(cx_qubits(circ), cx_qubits(default))
+                self.assertLessEqual(set(circ.count_ops()), set(default.count_ops()))
+                self.assertLessEqual(circ.depth(), default.depth())
+                reduced.append(circ.depth() < default.depth())
+            if pulse_optimize is False:
+                self.assertTrue(any(reduced))
+        target = np.kron(RXGate(0.3).to_matrix(), RYGate(0.5).to_matrix())
+        self.assertEqual(decomposer(target, depth_optimal=True), decomposer(target))
+
+    def test_ideal_basis_fidelity_matches_exact(self):
+        """Verify an ideal basis gate gives the same decomposition as exact synthesis"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), basis_fidelity=0.99)
+        local = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        targets = [local, local @ CXGate().to_matrix(), Ud(np.pi / 4, 0.3, 0)] + [
+            random_unitary(4, seed=2405 + seed).data for seed in range(5)
+        ]
+        for target in targets:
+            exact = decomposer(target, approximate=False)
+            self.assertEqual(decomposer(target, basis_fidelity=1.0, approximate=True), exact)
+            self.assertEqual(Operator(exact), Operator(target))
+        self.assertEqual(decomposer(targets[1], approximate=False).count_ops()["cx"], 1)
+
+    def test_decomposition_locals(self):
+        """Verify the decomposition locals interleaved with the basis gates give the target"""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        local = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        targets = [local, local @ CXGate().to_matrix(), Ud(np.pi / 4, 0.3, 0)] + [
+            random_unitary(4, seed=2406 + seed).data for seed in range(5)
+        ]
+        for target in targets:
+            locals_ = decomposer.decomposition_locals(target, approximate=False)
+            num_basis = len(locals_) // 2 - 1
+            circ = decomposer(target, approximate=False)
+            self.assertEqual(num_basis, circ.count_ops().get("cx", 0))
+            matrix = np.kron(locals_[1], locals_[0])
+            for k in range(num_basis):
+                matrix = np.kron(locals_[2 * k + 3], locals_[2 * k + 2]) @ (
+                    CXGate().to_matrix() @ matrix
+                )
+            self.assertTrue(Operator(matrix).equiv(Operator(target)))
+
+    def test_swap_target(self):
+        """Verify a literal SWAP is decomposed exactly, with three CX gates"""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        for phase in [0, 0.7, np.pi, -np.pi / 2]:
+            with self.subTest(phase=phase):
+                target = np.exp(1j * phase) * SwapGate().to_matrix()
+                weyl = TwoQubitWeylDecomposition(target)
+                self.assertEqual(weyl._inner_decomposition.specialization, Specialization.SWAPEquiv)
+                self.assertEqual((weyl.a, weyl.b, weyl.c), (np.pi / 4,) * 3)
+                self.assertEqual(weyl.diagonalization_iterations, 0)
+                circ = decomposer(target)
+                self.assertEqual(circ.count_ops()["cx"], 3)
+                self.assertEqual(Operator(circ), Operator(target))
+
+    def test_use_power(self):
+        """Verify powers of the basis gate are emitted as a single powered gate"""
+        decomposer = TwoQubitBasisDecomposer(iSwapGate())
+        for exponent in [0.5, -0.5, 1.5]:
+            with self.subTest(exponent=exponent):
+                target = np.exp(0.3j) * Operator(iSwapGate().power(exponent)).data
+                circ = decomposer(target, use_power=True)
+                self.assertEqual(Operator(circ), Operator(target))
+                self.assertEqual(len(circ), 1)
+                self.assertEqual(circ.data[0].operation, iSwapGate().power(exponent))
+                self.assertGreater(len(decomposer(target)), 1)
+        circ = decomposer(iSwapGate().to_matrix(), use_power=True)
+        self.assertEqual(circ.count_ops(), {"iswap": 1})
+        target = random_unitary(4, seed=2409)
+        self.assertEqual(decomposer(target, use_power=True), decomposer(target))
+
+    def test_insert_barriers(self):
+        """Verify barriers are placed around each basis gate without changing the operator"""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        for nbasis, target in enumerate(
+            [np.eye(4), CXGate().to_matrix(), Ud(0.6, 0.4, 0.0), random_unitary(4, seed=2413)]
+        ):
+            with self.subTest(nbasis=nbasis):
+                circ = decomposer(target, insert_barriers=True)
+                self.assertEqual(circ.count_ops().get("cx", 0), nbasis)
+                self.assertEqual(circ.count_ops().get("barrier", 0), 2 * nbasis)
+                self.assertEqual(Operator(circ), Operator(decomposer(target)))
+                for index, instruction in enumerate(circ.data):
+                    if instruction.operation.name == "cx":
+                        self.assertEqual(circ.data[index - 1].operation.name, "barrier")
+                        self.assertEqual(circ.data[index + 1].operation.name, "barrier")
+                dag = decomposer(target, use_dag=True, insert_barriers=True)
+                self.assertEqual(dag.count_ops().get("barrier", 0), 2 * nbasis)
+
+    def test_verify_atol(self):
+        """Verify the reconstructed operator is checked against the requested bound"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        targets = [random_unitary(4, seed=2473 + seed).data for seed in range(5)]
+        failures = 0
+        for target in targets:
+            circ = decomposer(target, approximate=False, verify_atol=1e-10)
+            self.assertEqual(circ, decomposer(target, approximate=False))
+            try:
+                decomposer(target, approximate=False, verify_atol=1e-18)
+            except QiskitError:
+                failures += 1
+        # Rounding errors are far above the tight bound for generic targets.
+        self.assertGreater(failures, 0)
+
+        # An approximate synthesis differs from the target by much more than rounding errors.
+        target = Ud(np.pi / 4, 0.3, 0.01)
+        decomposer(target, basis_fidelity=0.9, verify_atol=0.1)
+        with self.assertRaisesRegex(QiskitError, "verify_atol"):
+            decomposer(target, basis_fidelity=0.9, verify_atol=1e-6)
+
+    def test_optimize_1q(self):
+        """Verify cancelling adjacent single-qubit gates reduces the gate count"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        options = {"balance_single_qubit": True, "absorb_global_phase": True}
+        reduced = 0
+        for seed in range(10):
+            target = random_unitary(4, seed=2475 + seed).data
+            # A global phase of pi is the only one the ZSX basis can absorb, as an `rz(2 pi)`.
+            phase = decomposer(target, balance_single_qubit=True).global_phase
+            target = np.exp(1j * (np.pi - phase)) * target
+            before = decomposer(target, **options)
+            after = decomposer(target, optimize_1q=True, **options)
+            self.assertLessEqual(len(after), len(before))
+            self.assertEqual(after.count_ops()["cx"], before.count_ops()["cx"])
+            self.assertEqual(Operator(after), Operator(target))
+            reduced += len(after) < len(before)
+        # The phase absorbed into an `rz` merges with the last `rz` on the first qubit.
+        self.assertGreater(reduced, 0)
+
+    def test_emit_basis_matrix(self):
+        """Verify a calibrated basis matrix is emitted so the circuit reconstructs the target"""
+        # A CX with small calibration errors, which is still locally equivalent to a CX.
+        basis_matrix = (
+            np.kron(RXGate(0.01).to_matrix(), RZGate(0.02).to_matrix())
+            @ CXGate().to_matrix()
+            @ np.kron(RYGate(0.03).to_matrix(), RXGate(-0.02).to_matrix())
+        )
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX", basis_matrix=basis_matrix)
+        for seed in range(5):
+            target = random_unitary(4, seed=2476 + seed)
+            circ = decomposer(target, emit_basis_matrix=True)
+            self.assertEqual(circ.count_ops()["unitary"], 3)
+            self.assertNotIn("cx", circ.count_ops())
+            self.assertEqual(Operator(circ), target)
+            for use_dag in [False, True]:
+                nominal = decomposer(target, use_dag=use_dag)
+                if use_dag:
+                    nominal = dag_to_circuit(nominal)
+                self.assertEqual(nominal.count_ops()["cx"], 3)
+                # The ideal CX of the nominal circuit differs from the calibrated matrix.
+                self.assertNotEqual(Operator(nominal), target)
+            dag = decomposer(target, use_dag=True, emit_basis_matrix=True)
+            self.assertEqual(Operator(dag_to_circuit(dag)), target)
+
+    def test_multiplication_order(self):
+        """Verify the gates multiply to the target in the order of the requested convention"""
+
+        def gates(circ):
+            return [
+                (instruction.operation, [circ.find_bit(q).index for q in instruction.qubits])
+                for instruction in circ.data
+            ]
+
+        def gate_matrices(circ):
+            for operation, qubits in gates(circ):
+                single = QuantumCircuit(2)
+                single.append(operation, qubits)
+                yield Operator(single).data
+
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        unitary = random_unitary(4, seed=2462).data
+        sequence = decomposer._inner_decomposer.generate_sequence(
+            unitary, options=SequenceOptions(convention=MultiplicationOrder.RightToLeft)
+        )
+        reversed_sequence = decomposer._inner_decomposer.generate_sequence(
+            unitary, options=SequenceOptions(convention=MultiplicationOrder.LeftToRight)
+        )
+        circ = decomposer._sequence_to_circuit(sequence)
+        reversed_circ = decomposer._sequence_to_circuit(reversed_sequence)
+        self.assertEqual(gates(reversed_circ), gates(circ)[::-1])
+        with self.assertRaises(TypeError):
+            decomposer(unitary, convention=MultiplicationOrder.LeftToRight)
+
+        right_to_left = np.exp(1j * circ.global_phase) * np.eye(4)
+        for matrix in gate_matrices(circ):
+            right_to_left = matrix @ right_to_left
+        np.testing.assert_allclose(right_to_left, unitary, atol=1e-12)
+        left_to_right = np.exp(1j * reversed_circ.global_phase) * np.eye(4)
+        for matrix in gate_matrices(reversed_circ):
+            left_to_right = left_to_right @ matrix
+        np.testing.assert_allclose(left_to_right, unitary, atol=1e-12)
+
+    def test_sequence_options(self):
+        """Verify SequenceOptions defaults to the plain decomposition and rejects unknown options"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        unitary = random_unitary(4, seed=2367).data
+        options = SequenceOptions(schedule=True)
+        self.assertTrue(options.schedule)
+        self.assertFalse(options.depth_optimal)
+        self.assertEqual(
+            decomposer._sequence_to_circuit(
+                decomposer._inner_decomposer.generate_sequence(unitary, options=SequenceOptions())
+            ),
+            decomposer(unitary),
+        )
+        self.assertEqual(
+            decomposer._sequence_to_circuit(
+                decomposer._inner_decomposer.generate_sequence(unitary, options=options)
+            ),
+            decomposer(unitary, schedule=True),
+        )
+        with self.assertRaises(AttributeError):
+            SequenceOptions(schedul=True)
+        with self.assertRaises(TypeError):
+            SequenceOptions(snap_angles="small")
+
+    def test_pad_identities(self):
+        """Verify both qubits get the same number of gates between basis gates"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX", pulse_optimize=False)
+        for nbasis, target in enumerate(
+            [np.eye(4), CXGate().to_matrix(), Ud(0.6, 0.4, 0.0), random_unitary(4, seed=2456)]
+        ):
+            with self.subTest(nbasis=nbasis):
+                circ = decomposer(target, pad_identities=True)
+                self.assertEqual(circ.count_ops().get("cx", 0), nbasis)
+                self.assertEqual(Operator(circ), Operator(target))
+                counts = [0, 0]
+                for instruction in circ.data:
+                    if instruction.operation.num_qubits == 2:
+                        self.assertEqual(counts[0], counts[1])
+                        counts = [0, 0]
+                    else:
+                        counts[circ.find_bit(instruction.qubits[0]).index] += 1
+                self.assertEqual(counts[0], counts[1])
+                dag = decomposer(target, use_dag=True, pad_identities=True)
+                self.assertEqual(dag.count_ops(), circ.count_ops())
+
+    def test_decomposition_cache(self):
+        """Verify the decomposition cache is bounded and evicts the least recently used entry"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), cache_size=2)
+        uncached = TwoQubitBasisDecomposer(CXGate())
+        targets = [random_unitary(4, seed=2457 + i).data for i in range(3)]
+        for index in [0, 1, 0, 2, 0, 1]:
+            self.assertEqual(decomposer(targets[index]), uncached(targets[index]))
+        # Synthesizing target 2 evicts target 1, and synthesizing target 1 again evicts target 2.
+        self.assertEqual(decomposer.cache_info(), (2, 4, 2, 2, 2))
+        decomposer(targets[1], approximate=False)
+        self.assertEqual(decomposer.cache_info().misses, 5)
+        decomposer.clear_cache()
+        self.assertEqual(decomposer.cache_info(), (2, 5, 3, 2, 0))
+        decomposer(targets[0])
+        self.assertEqual(decomposer.cache_info().misses, 6)
+        self.assertEqual(uncached.cache_info(), (0, 0, 0, 0, 0))
+        with self.assertRaises(QiskitError):
+            TwoQubitBasisDecomposer(CXGate(), cache_size=-1)
+
+    def test_prefer_specialized(self):
+        """Verify a target near a controlled gate is synthesized in the controlled form"""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        k1 = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        k2 = np.kron(random_unitary(2, seed=3).data, random_unitary(2, seed=4).data)
+        target = k1 @ Ud(np.pi / 4, 0.02, 0.01) @ k2
+        self.assertEqual(decomposer(target).count_ops()["cx"], 3)
+        circ = decomposer(target, prefer_specialized=True)
+        self.assertEqual(circ.count_ops()["cx"], 1)
+        fidelity = trace_to_fid(np.trace(target.conj().T @ Operator(circ).data))
+        self.assertGreater(fidelity, 1 - 1e-3)
+        self.assertLess(fidelity, 1 - 1e-6)
+        exact = decomposer(target, approximate=False, prefer_specialized=True)
+        self.assertEqual(Operator(exact), Operator(target))
+
+    def test_tag_synthesized_operations(self):
+        """Verify every synthesized operation carries the provenance tag"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        target = random_unitary(4, seed=2422)
+        dag = decomposer(target, use_dag=True, tag="2q_synth", insert_barriers=True)
+        self.assertGreater(len(dag.op_nodes()), 0)
+        self.assertTrue(all(node.op.label == "2q_synth" for node in dag.op_nodes()))
+        circ = decomposer(target, tag="2q_synth")
+        self.assertTrue(all(inst.operation.label == "2q_synth" for inst in circ.data))
+        self.assertEqual(Operator(circ), Operator(target))
+        dag = DAGCircuit()
+        qr = QuantumRegister(2)
+        dag.add_qreg(qr)
+        decomposer.append_into_dag(target, dag, list(qr), tag="2q_synth")
+        self.assertTrue(all(node.op.label == "2q_synth" for node in dag.op_nodes()))
+        self.assertIsNone(decomposer.gate.label)
+        self.assertIsNone(decomposer(target).data[0].operation.label)
+
+    def test_expected_basis_cost_monotonic(self):
+        """Verify the smooth basis cost grows as a target moves from the 2-CX to the 3-CX region"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), basis_fidelity=0.99)
+        targets = [Ud(0.6, 0.4, c) for c in np.linspace(0, 0.4, 41)]
+        costs = [decomposer.expected_basis_cost(target) for target in targets]
+        self.assertTrue(np.all(np.diff(costs) >= -1e-12))
+        self.assertEqual(decomposer.num_basis_gates(targets[0]), 2)
+        self.assertEqual(decomposer.num_basis_gates(targets[-1]), 3)
+        self.assertLess(costs[0], 2.5)
+        self.assertGreater(costs[-1], 2.9)
+        for target in targets[1:]:
+            self.assertEqual(
+                decomposer.expected_basis_cost(target, basis_fidelity=1.0),
+                TwoQubitBasisDecomposer(CXGate()).num_basis_gates(target),
+            )
+
+    def test_total_cx_for_blocks(self):
+        """Verify the total CX count over a batch of 2-qubit blocks"""
+        local = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        one_cx = local @ CXGate().to_matrix() @ local.conj().T
+        blocks = [local, one_cx, random_unitary(4, seed=3).data, random_unitary(4, seed=4).data]
+        self.assertEqual(total_cx_for_blocks(np.array(blocks)), 0 + 1 + 3 + 3)
+        decomposer = TwoQubitBasisDecomposer(CXGate(), basis_fidelity=0.9)
+        self.assertEqual(
+            total_cx_for_blocks(np.array(blocks), 0.9),
+            sum(decomposer.num_basis_gates(block) for block in blocks),
+        )
+        with self.assertRaisesRegex(QiskitError, "expected an \\(N, 4, 4\\) array"):
+            total_cx_for_blocks(np.zeros((2, 2, 2), dtype=complex))
+
+    def test_effective_cx_count(self):
+        """Verify a trailing SWAP is free in the CX count only when requested"""
+        local = np.kron(random_unitary(2, seed=5).data, random_unitary(2, seed=6).data)
+        mirror = local @ iSwapGate().to_matrix()
+        self.assertEqual(effective_cx_count(mirror), 2)
+        self.assertEqual(effective_cx_count(mirror, swap_is_free=False), 2)
+        self.assertEqual(effective_cx_count(mirror, swap_is_free=True), 1)
+        swap = SwapGate().to_matrix() @ local
+        self.assertEqual(effective_cx_count(swap, swap_is_free=False), 3)
+        self.assertEqual(effective_cx_count(swap, swap_is_free=True), 0)
+        generic = random_unitary(4, seed=7).data
+        self.assertEqual(effective_cx_count(generic, swap_is_free=True), 3)
+        with self.assertRaises(QiskitError):
+            effective_cx_count(np.eye(2, dtype=complex))
+
+    def test_fidelity_curve(self):
+        """Verify the decomposer picks the number of CX gates maximizing the fidelity curve"""
+        local = np.kron(random_unitary(2, seed=8).data, random_unitary(2, seed=9).data)
+        targets = [local, local @ CXGate().to_matrix(), Ud(np.pi / 4, 0.3, 0.01)]
+        targets += [random_unitary(4, seed=2468 + seed).data for seed in range(5)]
+        for cx_fidelity in [1.0, 0.99, 0.9]:
+            decomposer = TwoQubitBasisDecomposer(CXGate(), basis_fidelity=cx_fidelity)
+            for target in targets:
+                curve = fidelity_curve(target, cx_fidelity)
+                self.assertEqual(len(curve), 4)
+                self.assertAlmostEqual(curve[3], cx_fidelity**3)
+                num_cx = decomposer(target).count_ops().get("cx", 0)
+                self.assertEqual(num_cx, np.argmax(curve))
+
+    def test_two_qubit_clifford_circuit(self):
+        """Verify Cliffords are synthesized with h, s and the fewest cx gates"""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        unitaries = [np.exp(0.3j) * CXGate().to_matrix(), SwapGate().to_matrix()]
+        unitaries += [Operator(random_clifford(2, seed=seed)).data for seed in range(2460, 2480)]
+        for unitary in unitaries:
+            circuit = QuantumCircuit._from_circuit_data(two_qubit_clifford_circuit(unitary))
+            self.assertEqual(Operator(circuit), Operator(unitary))
+            self.assertLessEqual(set(circuit.count_ops()), {"h", "s", "cx"})
+            self.assertEqual(
+                circuit.count_ops().get("cx", 0), decomposer.num_basis_gates(unitary)
+            )
+        with self.assertRaises(QiskitError):
+            two_qubit_clifford_circuit(np.kron(np.eye(2), RZGate(0.3).to_matrix()))
+        with self.assertRaises(QiskitError):
+            two_qubit_clifford_circuit(random_unitary(4, seed=2460).data)
+
+    def test_num_basis_gates_batch(self):
+        """Verify the batched basis gate counts match num_basis_gates element by element"""
+        local = np.kron(random_unitary(2, seed=5).data, random_unitary(2, seed=6).data)
+        blocks = [local, local @ CXGate().to_matrix(), Ud(0.6, 0.4, 0.0)]
+        blocks += [random_unitary(4, seed=seed).data for seed in range(7, 12)]
+        for decomposer in [
+            TwoQubitBasisDecomposer(CXGate()),
+            TwoQubitBasisDecomposer(CXGate(), basis_fidelity=0.95),
+            TwoQubitBasisDecomposer(iSwapGate()),
+        ]:
+            with self.subTest(decomposer=decomposer.gate.name):
+                counts = decomposer.num_basis_gates_batch(np.array(blocks))
+                self.assertEqual(counts.shape, (len(blocks),))
+                self.assertEqual(
+                    counts.tolist(), [decomposer.num_basis_gates(block) for block in blocks]
+                )
+
+    def test_batch_report(self):
+        """Verify the batch report gives the basis gate counts and fidelities of a mixed batch"""
+        local = np.kron(random_unitary(2, seed=5).data, random_unitary(2, seed=6).data)
+        blocks = [local, local @ CXGate().to_matrix(), Ud(0.6, 0.4, 0.0)]
+        blocks += [random_unitary(4, seed=seed).data for seed in range(2432, 2435)]
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        counts, fidelities = decomposer.batch_report(np.array(blocks))
+        self.assertEqual(counts, [0, 1, 2, 3, 3, 3])
+        self.assertEqual(len(fidelities), len(blocks))
+        np.testing.assert_allclose(fidelities, 1.0, atol=1e-12)
+        counts, fidelities = decomposer.batch_report(np.array(blocks), basis_fidelity=0.9)
+        self.assertEqual(counts[:3], [0, 1, 2])
+        np.testing.assert_allclose(fidelities[:3], [1.0, 0.9, 0.81], atol=1e-12)
+        counts, fidelities = decomposer.batch_report(
+            np.array(blocks), basis_fidelity=0.9, approximate=False
+        )
+        self.assertEqual(counts, [0, 1, 2, 3, 3, 3])
+        np.testing.assert_allclose(fidelities, 1.0, atol=1e-12)
+
+    def test_to_circuits_batch(self):
+        """Verify batched synthesis gives the same circuits as synthesizing each target"""
+        local = np.kron(random_unitary(2, seed=7).data, random_unitary(2, seed=8).data)
+        blocks = [local, local @ CXGate().to_matrix(), Ud(0.6, 0.4, 0.0)]
+        blocks += [random_unitary(4, seed=seed).data for seed in range(2452, 2462)]
+        for decomposer, kwargs in [
+            (TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX"), {}),
+            (TwoQubitBasisDecomposer(CXGate()), {"basis_fidelity": 0.99}),
+        ]:
+            with self.subTest(kwargs=kwargs):
+                circuits = decomposer.to_circuits_batch(np.array(blocks), **kwargs)
+                self.assertEqual(circuits, [decomposer(block, **kwargs) for block in blocks])
+        self.assertEqual(decomposer.to_circuits_batch(np.zeros((0, 4, 4))), [])
+
+    def test_to_circuit_with_coords(self):
+        """Verify the circuit comes with the Weyl coordinates of its target"""
+        local = np.kron(random_unitary(2, seed=9).data, random_unitary(2, seed=10).data)
+        blocks = [local, local @ CXGate().to_matrix(), Ud(0.6, 0.4, 0.0)]
+        blocks += [random_unitary(4, seed=seed).data for seed in range(2472, 2477)]
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        for block in blocks:
+            circuit, coords = decomposer.to_circuit_with_coords(block)
+            self.assertEqual(circuit, decomposer(block))
+            np.testing.assert_allclose(coords, weyl_coordinates(block), atol=1e-7)
+
+    def test_supercontrolled_basis_matrices(self):
+        """Verify the supercontrolled basis matrices are named single-qubit unitaries"""
+        names = ["k11l", "k11r", "k12l", "k12r", "k21r", "k22l", "k22r", "k31l", "k31r"]
+        names += ["k32l_k21l", "k32r"]
+        for b in [0.0, 0.3, np.pi / 8]:
+            matrices = supercontrolled_basis_matrices(b)
+            self.assertEqual(sorted(matrices), sorted(names))
+            for name, matrix in matrices.items():
+                with self.subTest(b=b, name=name):
+                    self.assertEqual(matrix.shape, (2, 2))
+                    np.testing.assert_allclose(matrix.conj().T @ matrix, np.eye(2), atol=1e-14)
+
+    def test_ud_batch(self):
+        """Verify the batched canonical gates match Ud row by row"""
+        coords = np.random.default_rng(1234).uniform(-np.pi, np.pi, size=(6, 3))
+        batch = ud_batch(coords)
+        self.assertEqual(batch.shape, (6, 4, 4))
+        for matrix, (a, b, c) in zip(batch, coords):
+            np.testing.assert_allclose(matrix, Ud(a, b, c), atol=1e-14)
+        self.assertEqual(ud_batch(np.zeros((0, 3))).shape, (0, 4, 4))
+        with self.assertRaises(QiskitError):
+            ud_batch(np.zeros((2, 2)))
+
+    def test_circuit_clifford_interaction(self):
+        """Verify Clifford interaction angles are emitted as Clifford gates"""
+        for k1l, k1r, k2l, k2r in K1K2S:
+            k1 = np.kron(k1l.data, k1r.data)
+            k2 = np.kron(k2l.data, k2r.data)
+            for coordinates in [(np.pi / 4, 0, 0), (np.pi / 4, np.pi / 4, 0)]:
+                target = k1 @ Ud(*coordinates) @ k2
+                decomp = TwoQubitWeylDecomposition(target)
+                circ = decomp.circuit(simplify=True, clifford=True)
+                self.assertEqual(Operator(circ), Operator(target))
+                ops = circ.count_ops()
+                self.assertNotIn("rxx", ops)
+                self.assertNotIn("ryy", ops)
+                self.assertEqual(ops["cz"], sum(abs(x) > 1e-12 for x in coordinates))
+
+    def test_circuit_avoid_ryy(self):
+        """Verify the YY interaction is emitted as a conjugated RXX when avoiding RYY"""
+        for seed in range(10):
+            target = random_unitary(4, seed=seed)
+            decomp = TwoQubitWeylDecomposition(target)
+            circ = decomp.circuit(avoid_ryy=True)
+            self.assertEqual(Operator(circ), target)
+            ops = circ.count_ops()
+            self.assertNotIn("ryy", ops)
+            self.assertEqual(ops["rxx"], 2)
+            self.assertEqual(ops["rzz"], 1)
+
+    def test_circuit_drop_atol(self):
+        """Verify interactions are dropped exactly up to drop_atol, independently of atol"""
+        drop_atol = 1e-3
+        kept = []
+        for a in np.linspace(0, 2 * drop_atol, 41):
+            decomp = TwoQubitWeylDecomposition(Ud(a, 0, 0), fidelity=None)
+            circ = decomp.circuit(simplify=True, atol=1e-12, drop_atol=drop_atol)
+            kept.append(circ.count_ops().get("rxx", 0))
+            if kept[-1] == 0:
+                self.assertLessEqual(abs(decomp.a), drop_atol)
+            else:
+                self.assertEqual(Operator(circ), Operator(Ud(a, 0, 0)))
+        self.assertEqual(kept, sorted(kept))
+        self.assertEqual(kept[0], 0)
+        self.assertEqual(kept[-1], 1)
+        decomp = TwoQubitWeylDecomposition(Ud(1e-6, 0, 0), fidelity=None)
+        self.assertEqual(decomp.circuit(simplify=True, atol=1e-12).count_ops().get("rxx", 0), 1)
+
     def test_two_qubit_weyl_decomposition_iswap(self):
         """Verify Weyl KAK decomposition for U~iswap"""
         for k1l, k1r, k2l, k2r in K1K2S:
@@ -1077,6 +2038,17 @@ class TestTwoQubitDecompose(CheckDecompositions):
         with self.assertWarns(UserWarning, msg="Supposed to warn when basis non-supercontrolled"):
             TwoQubitBasisDecomposer(UnitaryGate(Ud(np.pi / 4, 0.2, 0.1)))
 
+    def test_supercontrolled_helpers_reject_nonsupercontrolled(self):
+        """Check the supercontrolled helpers raise for a nonsupercontrolled basis"""
+        with self.assertWarns(UserWarning):
+            decomposer = TwoQubitBasisDecomposer(UnitaryGate(Ud(np.pi / 4, 0.2, 0.1)))
+        target = TwoQubitWeylDecomposition(random_unitary(4, seed=2411))._inner_decomposition
+        with self.assertRaisesRegex(QiskitError, "not supercontrolled"):
+            decomposer.decomp2_supercontrolled(target)
+        with self.assertRaisesRegex(QiskitError, "not supercontrolled"):
+            decomposer.decomp3_supercontrolled(target)
+        self.assertEqual(len(decomposer.decomp1(target)), 4)
+
     @combine(seed=range(10), name="seed_{seed}")
     def test_approx_supercontrolled_decompose_random(self, seed):
         """Check that n-uses of supercontrolled basis give the expected trace distance"""
@@ -1137,6 +2109,21 @@ class TestTwoQubitDecompose(CheckDecompositions):
         self.assertEqual(two_qubit_cnot_decompose.num_basis_gates(unitary), 1)
         self.assertTrue(Operator(two_qubit_cnot_decompose(unitary)).equiv(unitary))
 
+    def test_basis_gate_target(self):
+        """Check synthesizing the basis gate itself gives a single basis gate"""
+        for gate in [CXGate(), iSwapGate()]:
+            with self.subTest(gate=gate.name):
+                decomposer = TwoQubitBasisDecomposer(gate, euler_basis="U")
+                circ = decomposer(gate.to_matrix())
+                self.assertEqual(circ.count_ops(), {gate.name: 1})
+                self.assertTrue(Operator(circ).equiv(gate.to_matrix()))
+                local = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+                target = local @ gate.to_matrix()
+                circ = decomposer(target)
+                self.assertEqual(circ.count_ops().get(gate.name, 0), 1)
+                self.assertLessEqual(circ.count_ops().get("u", 0), 4)
+                self.assertTrue(Operator(circ).equiv(target))
+
     def test_cx_equivalence_2cx(self, seed=2):
         """Check circuits with  2 cx gates locally equivalent to some circuit with 2 cx."""
         state = np.random.default_rng(seed)
@@ -1271,11 +2258,797 @@ class TestTwoQubitDecompose(CheckDecompositions):
             requested_basis = set(oneq_gates + [kak_gate_name])
             self.assertTrue(decomposition_basis.issubset(requested_basis))
 
+    def test_append_into_dag(self):
+        """Test appending two synthesized blocks into one existing DAG."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        unitary_a = random_unitary(4, seed=1)
+        unitary_b = random_unitary(4, seed=2)
+        qr = QuantumRegister(3)
+        dag = DAGCircuit()
+        dag.add_qreg(qr)
+        decomposer.append_into_dag(unitary_a, dag, [qr[0], qr[1]])
+        decomposer.append_into_dag(unitary_b, dag, [qr[2], qr[1]])
+
+        expected = QuantumCircuit(qr)
+        expected.unitary(unitary_a, [0, 1])
+        expected.unitary(unitary_b, [2, 1])
+        self.assertEqual(Operator(dag_to_circuit(dag)), Operator(expected))
+        self.assertEqual(set(dag.count_ops()), {"cx", "u"})
+
+    def test_big_endian_cx(self):
+        """Verify a big-endian CX decomposes to the little-endian circuit"""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        # In the big-endian ordering, qubit 0 is the most significant bit, so this is a CX
+        # controlled on qubit 0.
+        big_endian_cx = np.array(
+            [[1, 0, 0, 0], [0, 1, 0, 0], [0, 0, 0, 1], [0, 0, 1, 0]], dtype=complex
+        )
+        little = decomposer(CXGate().to_matrix())
+        big = decomposer(big_endian_cx, input_endianness=Endianness.Big)
+        self.assertEqual(big, little)
+        self.assertEqual(Operator(big), Operator(CXGate()))
+        # Read as little endian, the same matrix is a CX controlled on qubit 1.
+        reversed_labels = decomposer(big_endian_cx)
+        self.assertEqual(Operator(reversed_labels), Operator(big.reverse_bits()))
+
+    def test_resynthesize_2q_blocks(self):
+        """Test consolidating and resynthesizing a collapsible two-qubit run in a DAG."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        circ = QuantumCircuit(3)
+        circ.cx(0, 1)
+        circ.rz(0.1, 0)
+        circ.cx(0, 1)
+        circ.cx(0, 1)
+        circ.h(2)
+        dag = circuit_to_dag(circ)
+        self.assertEqual(resynthesize_2q_blocks(dag, decomposer), 1)
+        out = dag_to_circuit(dag)
+        self.assertEqual(Operator(out), Operator(circ))
+        self.assertEqual(out.count_ops()["cx"], 1)
+        self.assertEqual(out.count_ops()["h"], 1)
+
+    def test_resynthesize_2q_blocks_keeps_optimal_blocks(self):
+        """Test a two-qubit run that is already optimal is left untouched and not counted."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        circ = QuantumCircuit(3)
+        circ.cx(0, 1)
+        circ.h(2)
+        dag = circuit_to_dag(circ)
+        self.assertEqual(resynthesize_2q_blocks(dag, decomposer), 0)
+        self.assertEqual(dag_to_circuit(dag), circ)
+
+        # A run of gates outside the basis is always replaced.
+        circ = QuantumCircuit(2)
+        circ.swap(0, 1)
+        dag = circuit_to_dag(circ)
+        self.assertEqual(resynthesize_2q_blocks(dag, decomposer), 1)
+        out = dag_to_circuit(dag)
+        self.assertEqual(Operator(out), Operator(circ))
+        self.assertNotIn("swap", out.count_ops())
+
+    @combine(seed=range(10), name="seed_{seed}")
+    def test_balance_single_qubit(self, seed):
+        """Test balancing single-qubit gates across the two wires between basis gates."""
+
+        def one_qubit_layers(circ):
+            layers = 0
+            counts = [0, 0]
+            for instruction in circ.data:
+                if instruction.operation.num_qubits == 2:
+                    layers += max(counts)
+                    counts = [0, 0]
+                else:
+                    counts[circ.find_bit(instruction.qubits[0]).index] += 1
+            return layers + max(counts)
+
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX", pulse_optimize=False)
+        unitary = random_unitary(4, seed=seed)
+        circ = decomposer(unitary)
+        balanced = decomposer(unitary, balance_single_qubit=True)
+        self.assertEqual(Operator(balanced), Operator(unitary))
+        self.assertEqual(balanced.count_ops().get("cx"), circ.count_ops().get("cx"))
+        self.assertLessEqual(one_qubit_layers(balanced), one_qubit_layers(circ))
+
+    def test_average_gate_infidelity(self):
+        """Test the average gate infidelity between two-qubit unitaries."""
+        unitary = random_unitary(4, seed=3).data
+        self.assertAlmostEqual(average_gate_infidelity(unitary, unitary), 0.0, places=12)
+        # Tr(XI . ZI^dag) = 0 so this is the maximum average gate infidelity of 1 - 4 / 20.
+        x_i = np.kron(np.eye(2), XGate().to_matrix())
+        z_i = np.kron(np.eye(2), ZGate().to_matrix())
+        self.assertAlmostEqual(average_gate_infidelity(x_i, z_i), 0.8, places=12)
+        with self.assertRaises(QiskitError):
+            average_gate_infidelity(np.eye(2, dtype=complex), unitary)
+
+    @combine(seed=range(5), name="seed_{seed}")
+    def test_from_unitary(self, seed):
+        """Test constructing a decomposer from a random supercontrolled basis unitary."""
+        state = np.random.default_rng(seed)
+        basis_k1 = np.kron(random_unitary(2, seed=state).data, random_unitary(2, seed=state).data)
+        basis_k2 = np.kron(random_unitary(2, seed=state).data, random_unitary(2, seed=state).data)
+        basis_unitary = basis_k1 @ Ud(np.pi / 4, 0.3, 0) @ basis_k2
+        decomposer = TwoQubitBasisDecomposer.from_unitary(basis_unitary)
+        self.assertIsInstance(decomposer.gate, UnitaryGate)
+        unitary = random_unitary(4, seed=state)
+        circ = decomposer(unitary)
+        self.assertEqual(Operator(circ), Operator(unitary))
+        self.assertEqual(circ.count_ops().get("unitary"), 3)
+
+    def test_from_coordinates(self):
+        """Test a decomposer built from the coordinates of CX behaves like a CX decomposer."""
+        decomposer = RustTwoQubitBasisDecomposer.from_coordinates(np.pi / 4, 0, 0)
+        cx_decomposer = RustTwoQubitBasisDecomposer("cx", CXGate().to_matrix())
+        self.assertTrue(decomposer.super_controlled)
+        basis_gate = UnitaryGate(Ud(np.pi / 4, 0, 0))
+        for seed in range(5):
+            unitary = random_unitary(4, seed=seed).data
+            self.assertEqual(
+                decomposer.num_basis_gates(unitary), cx_decomposer.num_basis_gates(unitary)
+            )
+            sequence = decomposer(unitary)
+            circ = QuantumCircuit(2, global_phase=sequence.global_phase)
+            for name, params, qubits in sequence:
+                if name == "USER_GATE":
+                    circ.append(basis_gate, qubits)
+                else:
+                    getattr(circ, name)(*params, *qubits)
+            self.assertEqual(Operator(circ), Operator(unitary))
+            self.assertEqual(circ.count_ops()["unitary"], 3)
+
+    @combine(
+        euler_basis=["U", "U3", "ZSX", "PSX", "RR", "XZX"],
+        basis_gate=[CXGate(), CYGate(), CZGate(), ECRGate(), iSwapGate(), DCXGate()],
+        seed=range(2),
+    )
+    def test_sequence_inverse(self, euler_basis, basis_gate, seed):
+        """Test composing a gate sequence with its inverse gives the identity."""
+
+        def to_circuit(sequence):
+            circ = QuantumCircuit(2, global_phase=sequence.global_phase)
+            for name, params, qubits in sequence:
+                getattr(circ, name)(*params, *qubits)
+            return circ
+
+        decomposer = RustTwoQubitBasisDecomposer(
+            basis_gate.name, basis_gate.to_matrix(), euler_basis=euler_basis, pulse_optimize=False
+        )
+        sequence = decomposer(random_unitary(4, seed=seed).data)
+        inverse = sequence.inverse()
+        # Only an iSWAP needs extra gates to be inverted.
+        num_iswap = sum(name == "iswap" for name, _, _ in sequence)
+        self.assertEqual(len(inverse), len(sequence) + 2 * num_iswap)
+        circ = to_circuit(sequence).compose(to_circuit(inverse))
+        self.assertEqual(Operator(circ), Operator(np.eye(4)))
+
+    def test_two_basis_decomposer(self):
+        """Test mixing a CX and an iSWAP in a single decomposition."""
+        decomposer = TwoQubitTwoBasisDecomposer(CXGate(), iSwapGate(), euler_basis="ZYZ")
+        local = np.kron(random_unitary(2, seed=2444).data, random_unitary(2, seed=2445).data)
+        mixed = iSwapGate().to_matrix() @ local @ CXGate().to_matrix()
+        circ = decomposer(mixed)
+        self.assertEqual(Operator(circ), Operator(mixed))
+        self.assertEqual(circ.count_ops()["cx"], 1)
+        self.assertEqual(circ.count_ops()["iswap"], 1)
+        two_qubit_gates = [inst.name for inst in circ.data if len(inst.qubits) == 2]
+        self.assertEqual(two_qubit_gates, ["cx", "iswap"])
+
+        # Targets that need fewer gates with a single basis gate fall back to it.
+        for target, name in [(CXGate().to_matrix(), "cx"), (iSwapGate().to_matrix(), "iswap")]:
+            circ = decomposer(target)
+            self.assertEqual(Operator(circ), Operator(target))
+            self.assertEqual(circ.count_ops()[name], 1)
+        circ = decomposer(random_unitary(4, seed=2446))
+        self.assertEqual(Operator(circ), Operator(random_unitary(4, seed=2446)))
+        self.assertEqual(circ.count_ops()["cx"], 3)
+
+        with self.assertRaisesRegex(QiskitError, "not locally equivalent"):
+            TwoQubitTwoBasisDecomposer(CXGate(), CZGate())
+
+    def test_two_basis_decomposer_pickle(self):
+        """Test pickling the two basis decomposer keeps its basis gates."""
+        decomposer = TwoQubitTwoBasisDecomposer(CXGate(), iSwapGate(), euler_basis="ZSX")
+        unpickled = pickle.loads(pickle.dumps(decomposer))
+        target = random_unitary(4, seed=2444)
+        self.assertEqual(unpickled(target), decomposer(target))
+        inner = pickle.loads(pickle.dumps(decomposer._inner_decomposer))
+        sequence = inner(target.data)
+        expected = decomposer._inner_decomposer(target.data)
+        self.assertEqual(list(sequence), list(expected))
+        self.assertEqual(sequence.global_phase, expected.global_phase)
+
+    def test_non_unitary_basis_gate(self):
+        """Test a non-unitary basis gate matrix is rejected unless unchecked."""
+        matrix = 1.01 * CXGate().to_matrix()
+        with self.assertRaisesRegex(QiskitError, "not unitary"):
+            RustTwoQubitBasisDecomposer("cx", matrix)
+        with self.assertRaisesRegex(QiskitError, "not unitary"):
+            RustTwoQubitTwoBasisDecomposer("cx", matrix, "iswap", iSwapGate().to_matrix())
+        # Rounding errors far below the tolerance are accepted.
+        RustTwoQubitBasisDecomposer("cx", CXGate().to_matrix() * (1 + 1e-12))
+        decomposer = RustTwoQubitBasisDecomposer("cx", CXGate().to_matrix(), unchecked=True)
+        self.assertEqual(decomposer.num_basis_gates(random_unitary(4, seed=2447).data), 3)
+
+    def test_sequence_inverse_custom_1q(self):
+        """Test inverting a gate sequence with a non-standard single-qubit gate."""
+        decomposer = RustTwoQubitBasisDecomposer(
+            "cx", CXGate().to_matrix(), euler_basis="XZX", pulse_optimize=False
+        )
+        sequence = decomposer(random_unitary(4, seed=3).data)
+        gates, global_phase = sequence.__getstate__()
+        renamed = [
+            ("my_rx" if name == "rx" else name, params, qubits) for name, params, qubits in gates
+        ]
+        sequence.__setstate__((renamed, global_phase))
+        with self.assertRaisesRegex(QiskitError, "Unable to invert single-qubit gate my_rx"):
+            sequence.inverse()
+        inverse = sequence.inverse(lambda name, params: (name, [-params[0]]))
+
+        def to_circuit(sequence):
+            circ = QuantumCircuit(2, global_phase=sequence.global_phase)
+            for name, params, qubits in sequence:
+                getattr(circ, "rx" if name == "my_rx" else name)(*params, *qubits)
+            return circ
+
+        self.assertIn("my_rx", [name for name, _, _ in inverse])
+        circ = to_circuit(sequence).compose(to_circuit(inverse))
+        self.assertEqual(Operator(circ), Operator(np.eye(4)))
+
+    @combine(seed=range(5), name="seed_{seed}")
+    def test_schedule(self, seed):
+        """Test scheduling places parallel single-qubit gates next to each other."""
+
+        def adjacent_parallel_pairs(circ):
+            qubits = [tuple(instruction.qubits) for instruction in circ.data]
+            return sum(
+                len(first) == len(second) == 1 and first != second
+                for first, second in zip(qubits, qubits[1:])
+            )
+
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX", pulse_optimize=False)
+        unitary = random_unitary(4, seed=seed)
+        circ = decomposer(unitary)
+        scheduled = decomposer(unitary, schedule=True)
+        self.assertEqual(Operator(scheduled), Operator(unitary))
+        self.assertEqual(scheduled.count_ops(), circ.count_ops())
+        self.assertGreater(adjacent_parallel_pairs(scheduled), adjacent_parallel_pairs(circ))
+
+    def test_all_decomp_candidates(self):
+        """Test the parallel and serial candidate decompositions agree."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        target = TwoQubitWeylDecomposition(random_unitary(4, seed=9).data)._inner_decomposition
+        serial = decomposer._inner_decomposer.all_decomp_candidates(target, parallel=False)
+        parallel = decomposer._inner_decomposer.all_decomp_candidates(target, parallel=True)
+        expected = [
+            decomposer.decomp0(target),
+            decomposer.decomp1(target),
+            decomposer.decomp2_supercontrolled(target),
+            decomposer.decomp3_supercontrolled(target),
+        ]
+        self.assertEqual(len(serial), 4)
+        self.assertEqual(len(parallel), 4)
+        for serial_mats, parallel_mats, expected_mats in zip(serial, parallel, expected):
+            self.assertEqual(len(serial_mats), len(expected_mats))
+            self.assertEqual(len(parallel_mats), len(expected_mats))
+            for serial_mat, parallel_mat, expected_mat in zip(
+                serial_mats, parallel_mats, expected_mats
+            ):
+                np.testing.assert_array_equal(serial_mat, parallel_mat)
+                np.testing.assert_array_equal(serial_mat, expected_mat)
+
+    def test_generate_sequence_flat(self):
+        """Test a flat row-major target gives the same circuit as the 2D target."""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        unitary = random_unitary(4, seed=4).data
+        sequence = decomposer._inner_decomposer.generate_sequence(unitary)
+        flat_sequence = decomposer._inner_decomposer.generate_sequence_flat(unitary.ravel())
+        self.assertEqual(
+            decomposer._sequence_to_circuit(flat_sequence),
+            decomposer._sequence_to_circuit(sequence),
+        )
+        strided = np.empty(32, dtype=complex)
+        strided[::2] = unitary.ravel()
+        strided_sequence = decomposer._inner_decomposer.generate_sequence_flat(strided[::2])
+        self.assertEqual(
+            decomposer._sequence_to_circuit(strided_sequence),
+            decomposer._sequence_to_circuit(sequence),
+        )
+        with self.assertRaises(QiskitError):
+            decomposer._inner_decomposer.generate_sequence_flat(unitary.ravel()[:15])
+
+    def test_non_c_contiguous_target(self):
+        """Test Fortran-ordered and strided targets give the same circuit as a C-ordered one."""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        unitary = np.ascontiguousarray(random_unitary(4, seed=2449).data)
+        expected = decomposer._sequence_to_circuit(
+            decomposer._inner_decomposer.generate_sequence(unitary)
+        )
+        strided = np.zeros((8, 8), dtype=complex)
+        strided[::2, ::2] = unitary
+        for name, target in [
+            ("fortran", np.asfortranarray(unitary)),
+            ("strided", strided[::2, ::2]),
+            ("reversed", np.ascontiguousarray(unitary[::-1])[::-1]),
+        ]:
+            with self.subTest(layout=name):
+                np.testing.assert_array_equal(target, unitary)
+                sequence = decomposer._inner_decomposer.generate_sequence(target)
+                circ = decomposer._sequence_to_circuit(sequence)
+                self.assertEqual(circ, expected)
+                self.assertEqual(Operator(decomposer(target)), Operator(unitary))
+
+    def test_exact_and_approx(self):
+        """Test the exact and capped decompositions returned together."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        unitary = random_unitary(4, seed=14)
+        exact, capped, exact_fidelity, capped_fidelity = decomposer.exact_and_approx(unitary, 2)
+        self.assertEqual(Operator(exact), Operator(unitary))
+        self.assertAlmostEqual(exact_fidelity, 1.0, places=10)
+        self.assertEqual(exact.count_ops()["cx"], 3)
+        self.assertEqual(capped, decomposer(unitary, _num_basis_uses=2))
+        self.assertEqual(capped.count_ops()["cx"], 2)
+        traces = decomposer.traces(TwoQubitWeylDecomposition(unitary.data))
+        self.assertAlmostEqual(capped_fidelity, trace_to_fid(traces[2]), places=12)
+        self.assertLess(capped_fidelity, exact_fidelity)
+        with self.assertRaises(QiskitError):
+            decomposer.exact_and_approx(unitary, 4)
+
+    def test_all_budget_circuits(self):
+        """Test the decompositions for every number of basis gates returned together."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        unitary = random_unitary(4, seed=2417)
+        circuits, fidelities = decomposer.all_budget_circuits(unitary)
+        self.assertEqual(len(circuits), 4)
+        self.assertEqual(len(fidelities), 4)
+        traces = decomposer.traces(TwoQubitWeylDecomposition(unitary.data))
+        for nbasis, (circuit, fidelity) in enumerate(zip(circuits, fidelities)):
+            with self.subTest(nbasis=nbasis):
+                self.assertEqual(circuit.count_ops().get("cx", 0), nbasis)
+                self.assertEqual(circuit, decomposer(unitary, _num_basis_uses=nbasis))
+                self.assertAlmostEqual(fidelity, trace_to_fid(traces[nbasis]), places=12)
+        self.assertEqual(Operator(circuits[3]), Operator(unitary))
+        self.assertAlmostEqual(fidelities[3], 1.0, places=10)
+
+    def test_euler_basis_candidates(self):
+        """Test picking the best of several Euler bases per single-qubit block."""
+
+        def num_1q(circuit):
+            return sum(1 for instruction in circuit.data if instruction.operation.num_qubits == 1)
+
+        zyz = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZYZ")
+        xyx = TwoQubitBasisDecomposer(CXGate(), euler_basis="XYX")
+        mixed = TwoQubitBasisDecomposer(
+            CXGate(), euler_basis="ZYZ", euler_basis_candidates=["ZYZ", "XYX"]
+        )
+        # An X rotation needs three gates in ZYZ and a Z rotation three gates in XYX.
+        local = np.kron(RXGate(0.3).to_matrix(), RZGate(0.4).to_matrix())
+        self.assertEqual(num_1q(zyz(local)), 4)
+        self.assertEqual(num_1q(xyx(local)), 4)
+        circuit = mixed(local)
+        self.assertEqual(circuit.count_ops(), {"rx": 1, "rz": 1})
+        self.assertEqual(Operator(circuit), Operator(local))
+        for seed in range(2437, 2442):
+            with self.subTest(seed=seed):
+                unitary = random_unitary(4, seed=seed)
+                circuit = mixed(unitary)
+                best_single = min(num_1q(zyz(unitary)), num_1q(xyx(unitary)))
+                self.assertLessEqual(num_1q(circuit), best_single)
+                self.assertEqual(Operator(circuit), Operator(unitary))
+
+    def test_to_circuit_with_fidelity(self):
+        """Test forcing a best-effort number of basis gates on a target needing more."""
+        unitary = random_unitary(4, seed=2434)
+        traces = TwoQubitBasisDecomposer(CXGate()).traces(TwoQubitWeylDecomposition(unitary.data))
+        for basis_fidelity in (1.0, 0.5):
+            with self.subTest(basis_fidelity=basis_fidelity):
+                decomposer = TwoQubitBasisDecomposer(CXGate(), basis_fidelity=basis_fidelity)
+                circuit, fidelity = decomposer.to_circuit_with_fidelity(
+                    unitary, force_nbasis_best_effort=2
+                )
+                self.assertEqual(circuit.count_ops().get("cx", 0), 2)
+                self.assertAlmostEqual(fidelity, trace_to_fid(traces[2]), places=12)
+                self.assertLess(fidelity, 1.0 - 1e-6)
+                actual = np.trace(Operator(circuit).data.conj().T @ unitary.data)
+                self.assertAlmostEqual(fidelity, trace_to_fid(actual), places=10)
+        circuit, fidelity = TwoQubitBasisDecomposer(CXGate()).to_circuit_with_fidelity(unitary)
+        self.assertEqual(circuit.count_ops().get("cx", 0), 3)
+        self.assertAlmostEqual(fidelity, 1.0, places=10)
+        with self.assertRaises(QiskitError):
+            decomposer.to_circuit_with_fidelity(unitary, force_nbasis_best_effort=4)
+
+    def test_to_circuit_with_specialization(self):
+        """Test the specialization used for the synthesis is reported."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        local = np.kron(random_unitary(2, seed=2450).data, random_unitary(2, seed=2451).data)
+        targets = [
+            (local @ iSwapGate().to_matrix(), Specialization.MirrorControlledEquiv, 2),
+            (SwapGate().to_matrix(), Specialization.SWAPEquiv, 3),
+            (random_unitary(4, seed=2450).data, Specialization.General, 3),
+        ]
+        for unitary, expected, num_cx in targets:
+            with self.subTest(expected=expected):
+                circuit, specialization = decomposer.to_circuit_with_specialization(unitary)
+                self.assertEqual(specialization, expected)
+                self.assertEqual(specialization, TwoQubitWeylDecomposition.classify(unitary))
+                self.assertEqual(circuit.count_ops().get("cx", 0), num_cx)
+                self.assertEqual(Operator(circuit), Operator(unitary))
+
+    def test_noise_optimal_circuit(self):
+        """Test the basis gate count minimizing the total error is chosen."""
+        local = np.kron(random_unitary(2, seed=2453).data, random_unitary(2, seed=2454).data)
+        # With c = 0.02, the best 2-CX approximation has an infidelity of about 3.2e-4.
+        unitary = local @ Ud(0.5, 0.3, 0.02)
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        circuit, num_cx = decomposer.noise_optimal_circuit(unitary, cx_error=1e-2)
+        self.assertEqual(num_cx, 2)
+        self.assertEqual(circuit.count_ops()["cx"], 2)
+        actual = np.trace(Operator(circuit).data.conj().T @ unitary)
+        self.assertLess(trace_to_fid(actual), 1 - 1e-4)
+        circuit, num_cx = decomposer.noise_optimal_circuit(unitary, cx_error=1e-5)
+        self.assertEqual(num_cx, 3)
+        self.assertEqual(circuit.count_ops()["cx"], 3)
+        self.assertEqual(Operator(circuit), Operator(unitary))
+        with self.assertRaises(QiskitError):
+            decomposer.noise_optimal_circuit(unitary, cx_error=1.0)
+
+    def test_synthesize_weyl_only(self):
+        """Test synthesis of the entangling core alone is locally equivalent to it."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        for (a, b, c), num_cx in [
+            ((0, 0, 0), 0),
+            ((np.pi / 4, 0, 0), 1),
+            ((0.5, 0.2, 0), 2),
+            ((0.6, 0.3, -0.1), 3),
+        ]:
+            with self.subTest(coordinates=(a, b, c)):
+                circuit = decomposer.synthesize_weyl_only(a, b, c)
+                self.assertEqual(circuit.count_ops().get("cx", 0), num_cx)
+                self.assertEqual(Operator(circuit), Operator(Ud(a, b, c)))
+                weyl = TwoQubitWeylDecomposition(Operator(circuit).data)
+                np.testing.assert_allclose([weyl.a, weyl.b, weyl.c], [a, b, c], atol=1e-12)
+
+    def test_try_directional(self):
+        """Test synthesis with the basis gate restricted to one direction."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        unitary = random_unitary(4, seed=2455)
+        for direction in [(0, 1), (1, 0)]:
+            with self.subTest(direction=direction):
+                circuit = decomposer.try_directional(unitary, direction, max_basis=3)
+                self.assertIsNotNone(circuit)
+                self.assertEqual(Operator(circuit), Operator(unitary))
+                cx_qubits = [
+                    tuple(circuit.find_bit(qubit).index for qubit in instruction.qubits)
+                    for instruction in circuit.data
+                    if instruction.operation.name == "cx"
+                ]
+                self.assertEqual(cx_qubits, [direction] * 3)
+        self.assertIsNone(decomposer.try_directional(unitary, (0, 1), max_basis=2))
+
+        reversed_cx = QuantumCircuit(2)
+        reversed_cx.cx(1, 0)
+        circuit = decomposer.try_directional(Operator(reversed_cx), (0, 1), max_basis=1)
+        self.assertIsNotNone(circuit)
+        self.assertEqual(Operator(circuit), Operator(reversed_cx))
+        self.assertEqual(circuit.count_ops()["cx"], 1)
+        self.assertIsNone(decomposer.try_directional(unitary, (0, 1), max_basis=0))
+        with self.assertRaises(QiskitError):
+            decomposer.try_directional(unitary, (0, 0), max_basis=3)
+
+    def test_approximation_error_channel(self):
+        """Test the depolarizing parameter matches the fidelity of the synthesis."""
+        unitary = random_unitary(4, seed=2443)
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        self.assertAlmostEqual(decomposer.approximation_error_channel(unitary), 0.0, places=10)
+        _, fidelity = decomposer.to_circuit_with_fidelity(unitary, force_nbasis_best_effort=2)
+        error = decomposer.approximation_error_channel(unitary, force_nbasis_best_effort=2)
+        self.assertGreater(error, 0.0)
+        self.assertAlmostEqual(error, 4 / 3 * (1 - fidelity), places=12)
+        with self.assertRaises(QiskitError):
+            decomposer.approximation_error_channel(unitary, force_nbasis_best_effort=4)
+
+    def test_to_circuit_absorbing_diagonal(self):
+        """Test absorbing a known diagonal reduces the basis gate count."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        local = np.kron(random_unitary(2, seed=6).data, random_unitary(2, seed=7).data)
+        diag = np.array([1, 1, 1, -1], dtype=complex)
+        unitary = np.diag(diag) @ local
+        full = decomposer(unitary)
+        absorbed = decomposer.to_circuit_absorbing_diagonal(unitary, diag)
+        self.assertEqual(full.count_ops().get("cx", 0), 1)
+        self.assertEqual(absorbed.count_ops().get("cx", 0), 0)
+        self.assertEqual(Operator(np.diag(diag)) @ Operator(absorbed), Operator(unitary))
+        with self.assertRaises(QiskitError):
+            decomposer.to_circuit_absorbing_diagonal(unitary, [1, 0, 1, 1])
+        # An invertible diagonal that isn't unitary would leave a non-unitary target.
+        with self.assertRaisesRegex(QiskitError, "must be unitary"):
+            decomposer.to_circuit_absorbing_diagonal(unitary, [2, 1, 1, 1])
+        # Any phases are accepted.
+        phases = np.exp(1j * np.array([0.1, 0.2, 0.3, 0.4]))
+        absorbed = decomposer.to_circuit_absorbing_diagonal(unitary, phases)
+        self.assertEqual(Operator(np.diag(phases)) @ Operator(absorbed), Operator(unitary))
+
+    @combine(euler_basis=["U", "U3", "U321", "PSX", "U1X"], seed=range(3))
+    def test_absorb_global_phase(self, euler_basis, seed):
+        """Test absorbing the global phase into single-qubit gates of the Euler basis."""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis=euler_basis)
+        unitary = random_unitary(4, seed=seed)
+        circ = decomposer(unitary, absorb_global_phase=True)
+        self.assertEqual(circ.global_phase, 0)
+        np.testing.assert_allclose(Operator(circ).data, unitary.data, atol=1e-10)
+        self.assertLessEqual(
+            set(circ.count_ops()), {"cx", *ONE_QUBIT_EULER_BASIS_GATES[euler_basis]}
+        )
+
+    def test_absorb_global_phase_rotation_basis(self):
+        """Test a basis of rotations only absorbs a global phase that is a multiple of pi."""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        unitary = random_unitary(4, seed=2390).data
+        with self.assertRaisesRegex(QiskitError, "can't be realized"):
+            decomposer(unitary, absorb_global_phase=True)
+        # The global phase of the synthesis follows the phase of the target.
+        unitary = np.exp(1j * (np.pi - decomposer(unitary).global_phase)) * unitary
+        circ = decomposer(unitary, absorb_global_phase=True)
+        self.assertEqual(circ.global_phase, 0)
+        np.testing.assert_allclose(Operator(circ).data, unitary, atol=1e-10)
+        self.assertLessEqual(set(circ.count_ops()), {"cx", "rz", "sx"})
+
+    def test_snap_angles(self):
+        """Test snapping single-qubit angles reduces the number of distinct angles."""
+
+        def distinct_angles(circ):
+            return sorted(
+                {
+                    float(param)
+                    for instruction in circ.data
+                    if instruction.operation.num_qubits == 1
+                    for param in instruction.operation.params
+                }
+            )
+
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX", pulse_optimize=False)
+        unitary = random_unitary(4, seed=11)
+        circ = decomposer(unitary)
+        before = distinct_angles(circ)
+        self.assertGreater(len(before), 1)
+
+        exact = decomposer(unitary, snap_angles=0.0)
+        self.assertEqual(Operator(exact), Operator(unitary))
+        self.assertEqual(distinct_angles(exact), before)
+
+        atol = 0.05
+        snapped = distinct_angles(decomposer(unitary, snap_angles=atol))
+        self.assertLessEqual(len(snapped), len(before))
+        self.assertTrue(all(y - x > atol for x, y in zip(snapped, snapped[1:])))
+
+        self.assertEqual(len(distinct_angles(decomposer(unitary, snap_angles=4 * np.pi))), 1)
+
+    def test_best_direction_and_count(self):
+        """Test choosing the basis gate direction by fidelity."""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), basis_fidelity=0.99)
+        unitary = random_unitary(4, seed=8)
+        count, direction = decomposer.best_direction_and_count(unitary)
+        self.assertEqual(count, decomposer.num_basis_gates(unitary))
+        self.assertEqual(list(direction), [0, 1])
+        count, direction = decomposer.best_direction_and_count(unitary, reverse_fidelity=0.999)
+        reverse_decomposer = TwoQubitBasisDecomposer(CXGate(), basis_fidelity=0.999)
+        self.assertEqual(count, reverse_decomposer.num_basis_gates(unitary))
+        self.assertEqual(list(direction), [1, 0])
+        count, direction = decomposer.best_direction_and_count(unitary, reverse_fidelity=0.9)
+        self.assertEqual(list(direction), [0, 1])
+        # Local unitaries need no basis gates, so there is no direction to choose.
+        local = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        self.assertEqual(
+            tuple(decomposer.best_direction_and_count(local, reverse_fidelity=0.999)), (0, [0, 1])
+        )
+
+    def test_pulse_optimal_applicable(self):
+        """Test reporting whether the pulse optimal decomposition will be used."""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        targets = [
+            (CXGate().to_matrix(), 1, False),
+            (Ud(0.5, 0.2, 0.0), 2, True),
+            (random_unitary(4, seed=3), 3, True),
+        ]
+        for unitary, num_cx, expected in targets:
+            with self.subTest(num_cx=num_cx):
+                self.assertEqual(decomposer.num_basis_gates(unitary), num_cx)
+                self.assertEqual(decomposer.pulse_optimal_applicable(unitary), expected)
+        unitary = random_unitary(4, seed=3)
+        for other in [
+            TwoQubitBasisDecomposer(CXGate(), euler_basis="U"),
+            TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX", pulse_optimize=False),
+            TwoQubitBasisDecomposer(iSwapGate(), euler_basis="ZSX"),
+        ]:
+            self.assertFalse(other.pulse_optimal_applicable(unitary))
+        self.assertTrue(
+            TwoQubitBasisDecomposer(CZGate(), euler_basis="ZSX").pulse_optimal_applicable(unitary)
+        )
+
+    def test_basis_weyl_coordinates(self):
+        """Test the Weyl coordinates of the basis gate are reported."""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        np.testing.assert_allclose(decomposer.basis_weyl_coordinates(), (np.pi / 4, 0, 0))
+        with self.assertWarns(UserWarning):
+            off = TwoQubitBasisDecomposer.from_unitary(Ud(np.pi / 4 - 0.01, 0.0, 0.0))
+        np.testing.assert_allclose(
+            off.basis_weyl_coordinates(), (np.pi / 4 - 0.01, 0, 0), atol=1e-12
+        )
+
+    def test_max_basis_gates_for_basis(self):
+        """Test the worst-case basis gate count is never exceeded."""
+        for gate, supercontrolled in [
+            (CXGate(), True),
+            (iSwapGate(), True),
+            (RXXGate(np.pi / 3), False),
+        ]:
+            with self.subTest(gate=gate.name):
+                with warnings.catch_warnings():
+                    warnings.simplefilter("ignore")
+                    decomposer = TwoQubitBasisDecomposer(gate)
+                self.assertEqual(decomposer.is_supercontrolled, supercontrolled)
+                max_count = decomposer.max_basis_gates_for_basis()
+                self.assertEqual(max_count, 3)
+                for seed in range(5):
+                    unitary = random_unitary(4, seed=seed)
+                    self.assertLessEqual(decomposer.num_basis_gates(unitary), max_count)
+                    if supercontrolled:
+                        self.assertEqual(Operator(decomposer(unitary)), Operator(unitary))
+
+    def test_basis_gate_negative_c(self):
+        """Test a supercontrolled basis whose Weyl c comes out slightly negative."""
+        basis = Ud(np.pi / 4 - 1e-11, 0.2, -1e-11)
+        self.assertLess(TwoQubitWeylDecomposition(basis, fidelity=None).c, 0)
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            decomposer = TwoQubitBasisDecomposer.from_unitary(basis)
+        self.assertTrue(decomposer.is_supercontrolled)
+        self.assertEqual(decomposer.basis_weyl_coordinates()[2], 0.0)
+        unitary = random_unitary(4, seed=13)
+        self.assertEqual(Operator(decomposer(unitary)), Operator(unitary))
+
+    def test_invalid_euler_basis_lists_valid_bases(self):
+        """Test an invalid Euler basis raises an error listing the valid bases."""
+        with self.assertRaises(QiskitError) as exc:
+            TwoQubitBasisDecomposer(CXGate(), euler_basis="ZYX")
+        self.assertIn("'ZYX'", exc.exception.message)
+        for basis in ["ZYZ", "ZXZ", "XYX", "U", "ZSX", "ZSXX"]:
+            self.assertIn(basis, exc.exception.message)
+        decomp = TwoQubitWeylDecomposition(random_unitary(4, seed=3).data)
+        with self.assertRaises(QiskitError) as exc:
+            decomp.circuit(euler_basis="ZYX")
+        self.assertIn("ZSXX", exc.exception.message)
+
+    def test_basis_gate_wrong_size_raises(self):
+        """Test that a basis gate which doesn't act on two qubits raises a clean error."""
+        with self.assertRaises(QiskitError) as exc:
+            TwoQubitBasisDecomposer(XGate())
+        self.assertIn("expected a 4x4 unitary", exc.exception.message)
+
+    @combine(seed=range(5), name="seed_{seed}")
+    def test_phase_breakdown(self, seed):
+        """Test the global phase contributions sum to the total global phase."""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZYZ")
+        unitary = random_unitary(4, seed=seed)
+        circ, phases = decomposer.phase_breakdown(unitary)
+        self.assertEqual(len(phases), 3)
+        self.assertAlmostEqual(np.exp(1j * sum(phases)), np.exp(1j * circ.global_phase), places=12)
+        self.assertEqual(Operator(circ), Operator(unitary))
+
+    @combine(seed=range(5), name="seed_{seed}")
+    def test_best_single_rzz_angle(self, seed):
+        """Test the best single RZZ angle against a brute-force angle search."""
+        unitary = random_unitary(4, seed=seed).data
+        target = TwoQubitWeylDecomposition(unitary, fidelity=None)
+
+        def fidelity(theta):
+            basis = TwoQubitWeylDecomposition(RZZGate(theta).to_matrix(), fidelity=None)
+            da, db, dc = target.a - basis.a, target.b - basis.b, target.c - basis.c
+            trace = 4 * complex(
+                np.cos(da) * np.cos(db) * np.cos(dc), np.sin(da) * np.sin(db) * np.sin(dc)
+            )
+            return trace_to_fid(trace)
+
+        theta, best_fidelity = best_single_rzz_angle(unitary)
+        self.assertAlmostEqual(fidelity(theta), best_fidelity, places=10)
+        brute_force = max(fidelity(angle) for angle in np.linspace(0, np.pi, 1001))
+        self.assertLessEqual(brute_force, best_fidelity + 1e-10)
+        self.assertGreaterEqual(brute_force, best_fidelity - 1e-4)
+
+    def test_best_single_rzz_angle_wrong_shape_raises(self):
+        """Test a non-4x4 input raises instead of panicking."""
+        with self.assertRaisesRegex(QiskitError, "expected a 4x4 unitary"):
+            best_single_rzz_angle(np.eye(2, dtype=complex))
+
+    def test_best_fixed_angle_rzz(self):
+        """Test approximating a target with RZZ gates of fixed angles."""
+        local1 = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        local2 = np.kron(random_unitary(2, seed=3).data, random_unitary(2, seed=4).data)
+        unitary = local1 @ Ud(3 * np.pi / 16, np.pi / 8, 0) @ local2
+        angles = [-np.pi / 4, -np.pi / 8]
+        circuit, fidelity = best_fixed_angle_rzz(unitary, angles, 3)
+        circuit = QuantumCircuit._from_circuit_data(circuit)
+        self.assertAlmostEqual(fidelity, 1.0, places=10)
+        self.assertTrue(Operator(circuit).equiv(Operator(unitary), atol=1e-7))
+        self.assertEqual(
+            {inst.operation.name for inst in circuit.data if inst.operation.num_qubits == 2},
+            {"rzz"},
+        )
+        self.assertEqual(circuit.count_ops()["rzz"], 3)
+        _, fidelity = best_fixed_angle_rzz(unitary, angles, 2)
+        self.assertLess(fidelity, 1.0 - 1e-6)
+        with self.assertRaises(QiskitError):
+            best_fixed_angle_rzz(unitary, list(np.linspace(0, np.pi, 100)), 12)
+
+    def test_min_acceptable_fidelity_raises(self):
+        """Test that a fidelity floor above the best achievable fidelity raises."""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), basis_fidelity=0.5)
+        unitary = Ud(np.pi / 4, np.pi / 4, np.pi / 4)
+        with self.assertRaises(QiskitError) as exc:
+            decomposer(unitary, min_acceptable_fidelity=0.99)
+        self.assertIn("below the minimum acceptable fidelity", exc.exception.message)
+        # A floor that is met leaves the output unchanged.
+        self.assertEqual(decomposer(unitary, min_acceptable_fidelity=0.0), decomposer(unitary))
+
+    @combine(seed=range(5), name="seed_{seed}")
+    def test_kak_traces_matches_decomposer(self, seed):
+        """Test the free kak_traces function against TwoQubitBasisDecomposer.traces"""
+        decomposer = TwoQubitBasisDecomposer(CXGate())
+        basis_b = TwoQubitWeylDecomposition(Operator(CXGate()).data).b
+        target = TwoQubitWeylDecomposition(random_unitary(4, seed=seed).data)
+        np.testing.assert_allclose(
+            kak_traces(target.a, target.b, target.c, basis_b),
+            decomposer.traces(target),
+            atol=1e-12,
+        )
+
+    def test_synthesis_fidelity(self):
+        """Test the end-to-end fidelity of exact and approximate syntheses."""
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")
+        for seed in range(5):
+            unitary = random_unitary(4, seed=2477 + seed).data
+            fidelity = synthesis_fidelity(decomposer._inner_decomposer, unitary)
+            self.assertAlmostEqual(fidelity, 1.0, places=12)
+
+        # The approximate synthesis drops a basis gate, which lowers the fidelity to the one of
+        # the best decomposition with fewer basis gates.
+        decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX", basis_fidelity=0.9)
+        unitary = Ud(np.pi / 4, 0.3, 0.01)
+        num_basis = decomposer(unitary).count_ops()["cx"]
+        self.assertLess(num_basis, 3)
+        expected = trace_to_fid(decomposer.traces(TwoQubitWeylDecomposition(unitary))[num_basis])
+        fidelity = synthesis_fidelity(decomposer._inner_decomposer, unitary)
+        self.assertLess(fidelity, 1.0 - 1e-6)
+        self.assertAlmostEqual(fidelity, expected, places=10)
+        exact = synthesis_fidelity(decomposer._inner_decomposer, unitary, approximate=False)
+        self.assertAlmostEqual(exact, 1.0, places=12)
+
 
 @ddt
 class TestPulseOptimalDecompose(CheckDecompositions):
     """Check pulse optimal decomposition."""
 
+    def test_fixed_angle_sx(self):
+        """Test the residual X rotations are never emitted as RX gates"""
+        unitaries = [random_unitary(4, seed=seed) for seed in range(2463, 2473)]
+        mixed = TwoQubitBasisDecomposer(
+            CXGate(), euler_basis="ZSX", euler_basis_candidates=["ZXZ", "ZSX"]
+        )
+        self.assertTrue(any("rx" in mixed(unitary).count_ops() for unitary in unitaries))
+        for pulse_optimize in [None, False]:
+            decomposer = TwoQubitBasisDecomposer(
+                CXGate(),
+                euler_basis="ZSX",
+                pulse_optimize=pulse_optimize,
+                euler_basis_candidates=["ZXZ", "ZSX"],
+                fixed_angle_sx=True,
+            )
+            for unitary in unitaries:
+                with self.subTest(pulse_optimize=pulse_optimize):
+                    circ = decomposer(unitary)
+                    self.assertLessEqual(set(circ.count_ops()), {"cx", "rz", "sx", "x"})
+                    self.assertEqual(Operator(circ), unitary)
+        with self.assertRaises(QiskitError):
+            TwoQubitBasisDecomposer(CXGate(), euler_basis="ZXZ", fixed_angle_sx=True)
+
     @combine(seed=range(10), name="seed_{seed}")
     def test_sx_virtz_3cnot_optimal(self, seed):
         """Test 3 CNOT ZSX pulse optimal decomposition"""
@@ -1298,6 +3071,45 @@ class TestPulseOptimalDecompose(CheckDecompositions):
         circ = decomposer(tgt_unitary)
         self.assertEqual(Operator(tgt_unitary), Operator(circ))
 
+    @combine(seed=range(10), name="seed_{seed}")
+    def test_sx_virtz_3cz_optimal(self, seed):
+        """Test 3 CZ ZSX pulse optimal decomposition"""
+        unitary = random_unitary(4, seed=seed)
+        decomposer = TwoQubitBasisDecomposer(CZGate(), euler_basis="ZSX", pulse_optimize=True)
+        circ = decomposer(unitary)
+        self.assertEqual(Operator(unitary), Operator(circ))
+        self.assertEqual(circ.count_ops()["cz"], 3)
+        self.check_cz_pulse_counts(unitary, circ)
+
+    @combine(seed=range(10), name="seed_{seed}")
+    def test_sx_virtz_2cz_optimal(self, seed):
+        """Test 2 CZ ZSX pulse optimal decomposition"""
+        rng = np.random.default_rng(seed)
+        decomposer = TwoQubitBasisDecomposer(CZGate(), euler_basis="ZSX", pulse_optimize=True)
+        tgt_k1 = np.kron(random_unitary(2, seed=rng).data, random_unitary(2, seed=rng).data)
+        tgt_k2 = np.kron(random_unitary(2, seed=rng).data, random_unitary(2, seed=rng).data)
+        tgt_phase = rng.random() * 2 * np.pi
+        tgt_a, tgt_b = rng.random(size=2) * np.pi / 4
+        tgt_unitary = np.exp(1j * tgt_phase) * tgt_k1 @ Ud(tgt_a, tgt_b, 0) @ tgt_k2
+        circ = decomposer(tgt_unitary)
+        self.assertEqual(Operator(tgt_unitary), Operator(circ))
+        self.assertEqual(circ.count_ops()["cz"], 2)
+        self.check_cz_pulse_counts(tgt_unitary, circ)
+
+    def check_cz_pulse_counts(self, unitary, circ):
+        """Check the CZ pulse optimal circuit against the generic and the CX decompositions"""
+        generic = TwoQubitBasisDecomposer(CZGate(), euler_basis="ZSX", pulse_optimize=False)
+        generic_ops = generic(unitary).count_ops()
+        ops = circ.count_ops()
+        self.assertLessEqual(ops.get("sx", 0), generic_ops.get("sx", 0))
+        cx_decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX", pulse_optimize=True)
+        cx_circ = cx_decomposer(unitary)
+        self.assertEqual(Operator(cx_circ), Operator(circ))
+        self.assertEqual(cx_circ.count_ops()["cx"], ops["cz"])
+        # The CZ circuit is built from the CX one, with a Hadamard, worth at most one SX gate, on
+        # either side of each CZ.
+        self.assertLessEqual(ops.get("sx", 0), cx_circ.count_ops().get("sx", 0) + 2 * ops["cz"])
+
     def _remove_pre_post_1q(self, circ):
         """remove single qubit operations before and after all multi-qubit ops"""
         dag = circuit_to_dag(circ)
@@ -1405,6 +3217,24 @@ class TestTwoQubitDecomposeApprox(CheckDecompositions):
 
 
 @ddt
+def _custom_controlled_rotation(base):
+    """Return a user-defined gate class wrapping the standard gate ``base``."""
+
+    class CustomGate(Gate):
+        """Custom single-parameter two-qubit gate defined in terms of ``base``."""
+
+        def __init__(self, theta, label=None):
+            super().__init__(f"custom_{base.__name__.lower()}", 2, [theta], label=label)
+
+        def _define(self):
+            qc = QuantumCircuit(2)
+            qc.append(base(self.params[0]), [0, 1])
+            self.definition = qc
+
+    CustomGate.__name__ = f"Custom{base.__name__}"
+    return CustomGate
+
+
 class TestTwoQubitControlledUDecompose(CheckDecompositions):
     """Test TwoQubitControlledUDecomposer() for exact decompositions and raised exceptions"""
 
@@ -1417,6 +3247,107 @@ class TestTwoQubitControlledUDecompose(CheckDecompositions):
             circ = decomposer(unitary)
             self.assertEqual(Operator(unitary), Operator(circ))
 
+    @combine(seed=range(5), name="seed_{seed}")
+    def test_verify_signed_c(self, seed):
+        """Verify mode accepts targets on both sides of the c = 0 plane"""
+        k1 = np.kron(random_unitary(2, seed=seed).data, random_unitary(2, seed=seed + 10).data)
+        k2 = np.kron(random_unitary(2, seed=seed + 20).data, random_unitary(2, seed=seed + 30).data)
+        for c in [0.1, -0.1]:
+            unitary = k1 @ Ud(0.3, 0.2, c) @ k2
+            for gate in [RXXGate, RZZGate, CPhaseGate, CRZGate]:
+                decomposer = TwoQubitControlledUDecomposer(gate)
+                circ = decomposer(unitary, verify=True)
+                self.assertEqual(Operator(unitary), Operator(circ))
+
+    def test_verify_raises_on_mismatch(self):
+        """Verify mode raises if the synthesized circuit does not match the target"""
+        decomposer = TwoQubitControlledUDecomposer(RXXGate)
+        unitary = random_unitary(4, seed=42)
+        weyl_gate = decomposer._weyl_gate  # pylint: disable=protected-access
+
+        def weyl_gate_dropping_phase(circ, atol=1.0e-13):
+            # A bug in the global-phase bookkeeping of the Weyl gate.
+            weyl_gate(circ, atol)
+            circ.global_phase -= 0.5
+            return circ
+
+        with patch.object(decomposer, "_weyl_gate", weyl_gate_dropping_phase):
+            # Without verification the wrong circuit goes unnoticed.
+            self.assertNotEqual(Operator(decomposer(unitary)), Operator(unitary))
+            with self.assertRaises(QiskitError) as exc:
+                decomposer(unitary, verify=True)
+        self.assertIn("deviates from the target", exc.exception.message)
+        self.assertEqual(Operator(decomposer(unitary, verify=True)), Operator(unitary))
+
+    def test_controlled_rotation_family(self):
+        """Verify the inferred scale and reconstruction for controlled rotations"""
+        self.assertAlmostEqual(TwoQubitControlledUDecomposer(RXXGate).scale, 1.0)
+        for base in [CPhaseGate, CRZGate, CRXGate, CRYGate]:
+            for gate in [base, _custom_controlled_rotation(base)]:
+                with self.subTest(gate=gate.__name__):
+                    decomposer = TwoQubitControlledUDecomposer(gate)
+                    # These gates have Weyl coordinates (angle / 4, 0, 0), half of those of the
+                    # RXXGate with the same angle.
+                    self.assertAlmostEqual(decomposer.scale, 2.0)
+                    for seed in range(3):
+                        unitary = random_unitary(4, seed=seed)
+                        circ = decomposer(unitary, verify=True)
+                        self.assertEqual(Operator(unitary), Operator(circ))
+
+    def test_emitted_basis_angles(self):
+        """Verify the reported angles match the gates emitted for all three interactions"""
+        k1 = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        k2 = np.kron(random_unitary(2, seed=3).data, random_unitary(2, seed=4).data)
+        unitary = k1 @ Ud(0.5, 0.3, 0.1) @ k2
+        for gate, name, scale in [(RXXGate, "rxx", 1.0), (CPhaseGate, "cp", 2.0)]:
+            with self.subTest(gate=name):
+                decomposer = TwoQubitControlledUDecomposer(gate)
+                angles = decomposer.emitted_basis_angles(unitary)
+                np.testing.assert_allclose(angles, [-1.0 * scale, -0.6 * scale, -0.2 * scale])
+                circ = decomposer(unitary)
+                emitted = [inst.operation.params[0] for inst in circ.data if inst.name == name]
+                np.testing.assert_allclose(emitted, angles)
+        decomposer = TwoQubitControlledUDecomposer(RXXGate)
+        np.testing.assert_allclose(decomposer.emitted_basis_angles(k1 @ Ud(0.5, 0, 0) @ k2), [-1.0])
+
+    @combine(seed=range(5), name="seed_{seed}")
+    def test_to_ising_circuit(self, seed):
+        """Verify the Ising form only uses RZZ, RZ and RX gates"""
+        unitary = random_unitary(4, seed=seed)
+        circ = to_ising_circuit(unitary)
+        self.assertEqual(Operator(unitary), Operator(circ))
+        self.assertLessEqual(set(circ.count_ops()), {"rzz", "rz", "rx"})
+        self.assertEqual(circ.count_ops()["rzz"], 3)
+
+    def test_to_ising_circuit_rzz_native(self):
+        """Verify an RZZ-native target is synthesized with a single RZZ"""
+        k1 = np.kron(RZGate(0.4).to_matrix(), RXGate(0.7).to_matrix())
+        k2 = np.kron(RXGate(-0.2).to_matrix(), RZGate(1.1).to_matrix())
+        unitary = k1 @ RZZGate(0.6).to_matrix() @ k2
+        circ = to_ising_circuit(unitary)
+        self.assertEqual(Operator(unitary), Operator(circ))
+        self.assertEqual(circ.count_ops()["rzz"], 1)
+        self.assertLessEqual(set(circ.count_ops()), {"rzz", "rz", "rx"})
+        with self.assertRaises(QiskitError):
+            to_ising_circuit(np.ones((4, 4)))
+
+    def test_non_two_qubit_matrix(self):
+        """Test that an exception is raised if the gate's matrix is not 4x4"""
+
+        class SingleQubitMatrixGate(Gate):
+            """Two-qubit gate whose matrix is wrongly a single-qubit one."""
+
+            def __init__(self, theta, label=None):
+                super().__init__("single_qubit_matrix", 2, [theta], label=label)
+
+            def __array__(self, dtype=None, copy=None):
+                return RZGate(self.params[0]).to_matrix()
+
+        with self.assertRaises(QiskitError) as exc:
+            TwoQubitControlledUDecomposer(SingleQubitMatrixGate)
+        self.assertIn("SingleQubitMatrixGate", exc.exception.message)
+        self.assertIn("(2, 2)", exc.exception.message)
+
     def test_not_rxx_equivalent(self):
         """Test that an exception is raised if the gate is not equivalent to an RXXGate"""
         gate = SwapGate
@@ -1427,6 +3358,30 @@ class TestTwoQubitControlledUDecompose(CheckDecompositions):
         )
 
 
+class TestDecomposeProductGauge(QiskitTestCase):
+    """Check the phase gauge choices of decompose_two_qubit_product_gate"""
+
+    def test_gauges_reconstruct_same_product(self):
+        """Check that every gauge reconstructs the same product"""
+        kl = random_unitary(2, seed=2428).data
+        kr = random_unitary(2, seed=2429).data
+        klkr = np.exp(0.7j) * np.kron(kl, kr)
+        for gauge in ("phase", "left", "right", "split"):
+            with self.subTest(gauge=gauge):
+                l, r, phase = decompose_two_qubit_product_gate(klkr, gauge=gauge)
+                np.testing.assert_allclose(np.exp(1j * phase) * np.kron(l, r), klkr, atol=1e-12)
+                if gauge != "phase":
+                    self.assertEqual(phase, 0.0)
+        l, r, _ = decompose_two_qubit_product_gate(klkr)
+        self.assertAlmostEqual(np.linalg.det(l), 1.0)
+        self.assertAlmostEqual(np.linalg.det(r), 1.0)
+
+    def test_unknown_gauge(self):
+        """Check that an unknown gauge raises"""
+        with self.assertRaisesRegex(QiskitError, "unknown gauge"):
+            decompose_two_qubit_product_gate(np.eye(4), gauge="middle")
+
+
 class TestDecomposeProductRaises(QiskitTestCase):
     """Check that exceptions are raised when 2q matrix is not a product of 1q unitaries"""
 
@@ -1670,6 +3625,7 @@ class TestQuantumShannonDecomposer(QiskitTestCase):
             self.fail(str(uerr))
 
 
+@ddt
 class TestTwoQubitDecomposeUpToDiagonal(QiskitTestCase):
     """test TwoQubitDecomposeUpToDiagonal class"""
 
@@ -1801,6 +3757,21 @@ class TestTwoQubitDecomposeUpToDiagonal(QiskitTestCase):
         real_map = dec._real_trace_transform(su4)
         self.assertTrue(dec._cx2_test(real_map @ su4))
 
+    @data((0.0, 0.0), (0.3, -1.2), (np.pi / 2, np.pi / 5), (-2.1, 0.7))
+    @unpack
+    def test_real_trace_transform_gauge(self, theta, phi):
+        """test that any gauge of the diagonal gives a valid decomposition"""
+        dec = TwoQubitDecomposeUpToDiagonal()
+        u4 = scipy.stats.unitary_group.rvs(4, random_state=83)
+        su4, _ = dec._u4_to_su4(u4)
+        real_map = dec.real_trace_transform(su4, theta=theta, phi=phi)
+        self.assertTrue(np.allclose(np.angle(real_map[0, 0]), np.angle(np.exp(-1j * theta))))
+        self.assertTrue(np.allclose(np.angle(real_map[1, 1]), np.angle(np.exp(-1j * phi))))
+        self.assertTrue(dec._cx2_test(real_map @ su4))
+        dmat, circ2cx = dec(u4, theta=theta, phi=phi)
+        self.assertLessEqual(circ2cx.count_ops().get("cx", 0), 2)
+        self.assertTrue(Operator(u4) == Operator(dmat @ Operator(circ2cx).data))
+
     def test_call_decompose(self):
         """
         test __call__ method to decompose
@@ -17,7 +17,17 @@ import unittest
 import numpy as np
 from numpy.testing import assert_allclose
 
+from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.random import random_unitary
+from qiskit._accelerate.two_qubit_decompose import (
+    closest_partial_swap,
+    distance_to_perfect_entangler,
+    gate_class,
+    local_equivalence_maps,
+    locally_equivalent,
+    makhlin_from_weyl,
+    weyl_interpolate,
+)
 from qiskit.synthesis.two_qubit.weyl import weyl_coordinates
 from qiskit.synthesis.two_qubit.local_invariance import (
     two_qubit_local_invariants,
@@ -70,6 +80,163 @@ class TestWeyl(QiskitTestCase):
             local = two_qubit_local_invariants(U)
             assert_allclose(local, local_equiv)
 
+    def test_makhlin_from_weyl_random(self):
+        """Check the invariants computed from Weyl coordinates match those of the matrix."""
+        for _ in range(10):
+            U = random_unitary(4).data
+            weyl = weyl_coordinates(U)
+            local = two_qubit_local_invariants(U)
+            assert_allclose(makhlin_from_weyl(*weyl), local, atol=1e-10)
+
+    def test_closest_partial_swap(self):
+        """Check the closest partial swap against reference values."""
+        self.assertAlmostEqual(closest_partial_swap(0.5, 0.2, 0.1), 0.26756831275720167)
+        self.assertAlmostEqual(closest_partial_swap(0.3, 0.3, 0.3), 0.3)
+        self.assertAlmostEqual(closest_partial_swap(0.3, 0.2, 0.1), 0.2)
+
+    def test_weyl_interpolate(self):
+        """Check interpolating Weyl coordinates at the end points and half way."""
+        cx = np.array([[1, 0, 0, 0], [0, 0, 0, 1], [0, 0, 1, 0], [0, 1, 0, 0]], dtype=complex)
+        sqrt_iswap = np.array(
+            [
+                [1, 0, 0, 0],
+                [0, 1 / np.sqrt(2), 1j / np.sqrt(2), 0],
+                [0, 1j / np.sqrt(2), 1 / np.sqrt(2), 0],
+                [0, 0, 0, 1],
+            ],
+            dtype=complex,
+        )
+        local = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        dressed_cx = local @ cx @ local.conj().T
+        for t, expected in [
+            (0.0, [np.pi / 4, 0, 0]),
+            (1.0, [np.pi / 8, np.pi / 8, 0]),
+            (0.5, [3 * np.pi / 16, np.pi / 16, 0]),
+        ]:
+            with self.subTest(t=t):
+                assert_allclose(weyl_interpolate(cx, sqrt_iswap, t), expected, atol=1e-10)
+                assert_allclose(weyl_interpolate(dressed_cx, sqrt_iswap, t), expected, atol=1e-10)
+        assert_allclose(weyl_interpolate(np.eye(4), cx, 0.5), [np.pi / 8, 0, 0], atol=1e-10)
+        with self.assertRaisesRegex(QiskitError, "u2: expected a 4x4 unitary"):
+            weyl_interpolate(cx, np.eye(2, dtype=complex), 0.5)
+
+    def test_locally_equivalent(self):
+        """Check local equivalence of dressed and distinct two-qubit unitaries."""
+        cx = np.array([[1, 0, 0, 0], [0, 0, 0, 1], [0, 0, 1, 0], [0, 1, 0, 0]], dtype=complex)
+        iswap = np.array(
+            [[1, 0, 0, 0], [0, 0, 1j, 0], [0, 1j, 0, 0], [0, 0, 0, 1]], dtype=complex
+        )
+        for seed in range(5):
+            with self.subTest(seed=seed):
+                U = random_unitary(4, seed=seed).data
+                k1l, k1r, k2l, k2r = (random_unitary(2, seed=10 * i + seed).data for i in range(4))
+                k1, k2 = np.kron(k1l, k1r), np.kron(k2l, k2r)
+                self.assertTrue(locally_equivalent(U, k1 @ U @ k2))
+                self.assertTrue(locally_equivalent(U, 1j * U))
+                self.assertFalse(locally_equivalent(U, random_unitary(4, seed=seed + 30).data))
+        self.assertFalse(locally_equivalent(cx, iswap))
+        self.assertTrue(locally_equivalent(cx, np.kron(np.eye(2), np.diag([1, 1j])) @ cx))
+        # U and its complex conjugate differ in the sign of c, which only identifies the same
+        # point of the Weyl chamber on the a = pi/4 face.
+        swap_like = iswap @ np.diag(np.exp(0.2j * np.array([1, -1, -1, 1])))
+        self.assertTrue(locally_equivalent(swap_like, swap_like.conj()))
+        with self.assertRaisesRegex(QiskitError, "u1: expected a 4x4 unitary"):
+            locally_equivalent(np.eye(2, dtype=complex), cx)
+
+    def test_local_equivalence_maps(self):
+        """Check the returned local gates map a unitary to a locally equivalent one."""
+
+        def check(u, v):
+            la, lb, ra, rb = local_equivalence_maps(u, v)
+            for local in (la, lb, ra, rb):
+                assert_allclose(local @ local.conj().T, np.eye(2), atol=1e-12)
+            assert_allclose(np.kron(la, lb) @ u @ np.kron(ra, rb), v, atol=1e-10)
+
+        for seed in range(5):
+            with self.subTest(seed=seed):
+                U = random_unitary(4, seed=seed).data
+                k1l, k1r, k2l, k2r = (random_unitary(2, seed=10 * i + seed).data for i in range(4))
+                check(U, np.exp(0.4j) * np.kron(k1l, k1r) @ U @ np.kron(k2l, k2r))
+                check(U, U)
+        iswap = np.array(
+            [[1, 0, 0, 0], [0, 0, 1j, 0], [0, 1j, 0, 0], [0, 0, 0, 1]], dtype=complex
+        )
+        # On the a = pi/4 face the maps also relate unitaries whose c differ in sign.
+        swap_like = iswap @ np.diag(np.exp(0.2j * np.array([1, -1, -1, 1])))
+        check(swap_like, swap_like.conj())
+        with self.assertRaises(QiskitError):
+            local_equivalence_maps(random_unitary(4, seed=1).data, iswap)
+
+    def test_distance_to_perfect_entangler(self):
+        """Check the distance to the perfect entanglers of known gates."""
+        cx = np.array([[1, 0, 0, 0], [0, 0, 0, 1], [0, 0, 1, 0], [0, 1, 0, 0]], dtype=complex)
+        iswap = np.array(
+            [[1, 0, 0, 0], [0, 0, 1j, 0], [0, 1j, 0, 0], [0, 0, 0, 1]], dtype=complex
+        )
+        swap = np.array([[1, 0, 0, 0], [0, 0, 1, 0], [0, 1, 0, 0], [0, 0, 0, 1]], dtype=complex)
+        sqrt_swap = np.array(
+            [
+                [1, 0, 0, 0],
+                [0, (1 + 1j) / 2, (1 - 1j) / 2, 0],
+                [0, (1 - 1j) / 2, (1 + 1j) / 2, 0],
+                [0, 0, 0, 1],
+            ],
+            dtype=complex,
+        )
+        for gate in (cx, iswap, sqrt_swap):
+            self.assertAlmostEqual(distance_to_perfect_entangler(gate), 0.0)
+        max_distance = np.pi / (4 * np.sqrt(2))
+        self.assertAlmostEqual(distance_to_perfect_entangler(np.eye(4)), max_distance)
+        local = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        self.assertAlmostEqual(distance_to_perfect_entangler(local), max_distance)
+        self.assertAlmostEqual(distance_to_perfect_entangler(swap), max_distance)
+        # Halfway from the identity to CX, the distance is halved.
+        xx = np.kron([[0, 1], [1, 0]], [[0, 1], [1, 0]])
+        half_cx = np.cos(np.pi / 8) * np.eye(4) + 1j * np.sin(np.pi / 8) * xx
+        self.assertAlmostEqual(distance_to_perfect_entangler(half_cx), max_distance / 2)
+        for seed in range(10):
+            with self.subTest(seed=seed):
+                distance = distance_to_perfect_entangler(random_unitary(4, seed=seed).data)
+                self.assertGreaterEqual(distance, 0.0)
+                self.assertLessEqual(distance, max_distance + 1e-12)
+        with self.assertRaisesRegex(QiskitError, "expected a 4x4 unitary"):
+            distance_to_perfect_entangler(np.eye(2, dtype=complex))
+
+    def test_gate_class(self):
+        """Check the gate class of representatives of each class."""
+
+        def canonical(a, b, c):
+            paulis = [np.array(p, dtype=complex) for p in ([[0, 1], [1, 0]], [[0, -1j], [1j, 0]])]
+            paulis.append(np.diag([1, -1]).astype(complex))
+            generator = sum(x * np.kron(p, p) for x, p in zip((a, b, c), paulis))
+            values, vectors = np.linalg.eigh(generator)
+            return vectors @ np.diag(np.exp(1j * values)) @ vectors.conj().T
+
+        cx = np.array([[1, 0, 0, 0], [0, 0, 0, 1], [0, 0, 1, 0], [0, 1, 0, 0]], dtype=complex)
+        local = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        cases = [
+            (np.eye(4), "Local"),
+            (local, "Local"),
+            (canonical(np.pi / 4, np.pi / 4, np.pi / 4), "SWAP"),
+            (local @ canonical(np.pi / 4, np.pi / 4, -np.pi / 4), "SWAP"),
+            (canonical(np.pi / 8, np.pi / 8, np.pi / 8), "SWAP-root"),
+            (canonical(0.3, 0.3, -0.3), "SWAP-root"),
+            (cx, "ControlledPhase"),
+            (canonical(0.1, 0, 0), "ControlledPhase"),
+            (canonical(np.pi / 4, np.pi / 4, 0), "iSWAP-like"),
+            (local @ canonical(np.pi / 8, np.pi / 8, 0), "iSWAP-like"),
+            (canonical(np.pi / 4, np.pi / 8, 0), "PerfectEntangler"),
+            (canonical(0.3, 0.2, 0.1), "General"),
+        ]
+        for unitary, expected in cases:
+            with self.subTest(expected=expected):
+                self.assertEqual(gate_class(unitary), expected)
+        # A looser tolerance merges nearby classes.
+        self.assertEqual(gate_class(canonical(0.3, 1e-4, 0)), "General")
+        self.assertEqual(gate_class(canonical(0.3, 1e-4, 0), atol=1e-3), "ControlledPhase")
+        with self.assertRaises(QiskitError):
+            gate_class(np.eye(2))
+
 
 if __name__ == "__main__":
     unittest.main()